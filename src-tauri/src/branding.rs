@@ -0,0 +1,204 @@
+// PDF export'undaki filigran ve alt bilgi (marka) ayarları. Varsayılanlar önceki çıktıyla aynıdır: eğik
+// "PigNote" filigranı ve sol altta PigNote ikonuyla yazısı. İkisi de kapatılabilir ya da başka metin,
+// logo, saydamlık ve konumla değiştirilebilir. Notun ön bilgisindeki işaret ("watermark: GİZLİ" ya da
+// "status: draft") marka filigranının yerine basılır.
+use base64::{engine::general_purpose, Engine as _};
+use serde::Deserialize;
+
+use crate::frontmatter::NoteMetadata;
+use crate::images::{self, ImageFormat};
+use crate::themes::escape_html;
+use crate::workspace::ReadScope;
+
+pub const BRAND_NAME: &str = "PigNote";
+const BRAND_ICON: &[u8] = include_bytes!("../icons/icon.png");
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatermarkPosition {
+    // Sayfa ortasında -30° eğik
+    #[default]
+    Diagonal,
+    Center,
+    Top,
+    Bottom,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FooterPosition {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WatermarkOptions {
+    pub enabled: bool,
+    // Verilmezse "PigNote"
+    pub text: Option<String>,
+    // 0-1
+    pub opacity: f32,
+    pub position: WatermarkPosition,
+    // Ön bilgideki işaret, filigran kapalı olsa da basılır
+    pub from_metadata: bool,
+}
+
+impl Default for WatermarkOptions {
+    fn default() -> Self {
+        WatermarkOptions { enabled: true, text: None, opacity: 0.06, position: WatermarkPosition::default(), from_metadata: true }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FooterOptions {
+    pub enabled: bool,
+    // Verilmezse "PigNote"; boş metinle yalnızca logo basılır
+    pub text: Option<String>,
+    // PNG/JPEG logo yolu (notun klasörüne göre göreli olabilir); verilmezse PigNote ikonu
+    pub logo: Option<String>,
+    pub show_logo: bool,
+    pub opacity: f32,
+    pub position: FooterPosition,
+}
+
+impl Default for FooterOptions {
+    fn default() -> Self {
+        FooterOptions { enabled: true, text: None, logo: None, show_logo: true, opacity: 1.0, position: FooterPosition::default() }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BrandingOptions {
+    pub watermark: WatermarkOptions,
+    pub footer: FooterOptions,
+}
+
+// Export motorlarının çizdiği, ayarlardan ve nottan çözülmüş filigran
+#[derive(Debug, Clone)]
+pub struct Watermark {
+    pub text: String,
+    pub opacity: f32,
+    pub position: WatermarkPosition,
+}
+
+#[derive(Debug, Clone)]
+pub struct Footer {
+    pub text: String,
+    // data: URI
+    pub logo: Option<String>,
+    pub opacity: f32,
+    pub position: FooterPosition,
+}
+
+impl BrandingOptions {
+    pub fn watermark(&self, metadata: Option<&NoteMetadata>) -> Option<Watermark> {
+        let options = &self.watermark;
+        let marking = metadata.and_then(|m| m.watermark.clone()).filter(|_| options.from_metadata);
+        let text = match marking {
+            Some(text) => text,
+            None if options.enabled => options.text.clone().unwrap_or_else(|| BRAND_NAME.to_string()),
+            None => return None,
+        };
+        let text = text.trim().to_string();
+        (!text.is_empty()).then(|| Watermark { text, opacity: options.opacity.clamp(0.0, 1.0), position: options.position })
+    }
+
+    // Okunamayan ya da desteklenmeyen logo export'u durdurur; müşteriye yanlış logo gitmesin
+    pub fn footer(&self, scope: &ReadScope) -> Result<Option<Footer>, String> {
+        let options = &self.footer;
+        if !options.enabled {
+            return Ok(None);
+        }
+        let logo = match (&options.logo, options.show_logo) {
+            (_, false) => None,
+            (None, true) => Some(data_uri(ImageFormat::Png, BRAND_ICON)),
+            (Some(path), true) => {
+                let bytes = images::load_image_bytes(path, scope).ok_or_else(|| format!("Logo okunamadı: {}", path))?;
+                match ImageFormat::detect(&bytes) {
+                    Some(format @ (ImageFormat::Png | ImageFormat::Jpeg)) => Some(data_uri(format, &bytes)),
+                    _ => return Err(format!("Logo PNG ya da JPEG olmalı: {}", path)),
+                }
+            }
+        };
+        let text = options.text.as_deref().unwrap_or(BRAND_NAME).trim().to_string();
+        if text.is_empty() && logo.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(Footer { text, logo, opacity: options.opacity.clamp(0.0, 1.0), position: options.position }))
+    }
+}
+
+fn data_uri(format: ImageFormat, bytes: &[u8]) -> String {
+    format!("data:{};base64,{}", format.mime(), general_purpose::STANDARD.encode(bytes))
+}
+
+// Tarayıcı motoru için; görünüm temanın .watermark ve .brand-footer stillerinden gelir
+impl Watermark {
+    pub fn html(&self) -> String {
+        let position = match self.position {
+            WatermarkPosition::Diagonal => "",
+            WatermarkPosition::Center => " transform: translate(-50%, -50%);",
+            WatermarkPosition::Top => " top: 24mm; transform: translateX(-50%);",
+            WatermarkPosition::Bottom => " top: auto; bottom: 28mm; transform: translateX(-50%);",
+        };
+        format!("<div class=\"watermark\" style=\"opacity: {};{}\">{}</div>", self.opacity, position, escape_html(&self.text))
+    }
+}
+
+// Tarayıcı motorunda alt bilgi şeridinin yüksekliği (logo 12pt, metin 9pt); PageSetup::css bu kadarını
+// alt kenar boşluğuna ekler
+pub const FOOTER_BAND_MM: f32 = 6.0;
+
+impl Footer {
+    // İçerik alanının hemen altında, @page alt boşluğuna eklenen şeritte durur; içerikle hiç örtüşmez
+    pub fn html(&self) -> String {
+        let position = match self.position {
+            FooterPosition::Left => "",
+            FooterPosition::Center => " left: 50%; transform: translateX(-50%);",
+            FooterPosition::Right => " left: auto; right: 0;",
+        };
+        let logo = self.logo.as_ref().map(|src| format!("<img src=\"{}\" alt=\"\" />", src)).unwrap_or_default();
+        let text = if self.text.is_empty() { String::new() } else { format!("<span>{}</span>", escape_html(&self.text)) };
+        format!(
+            "<div class=\"brand-footer\" style=\"height: {band}mm; bottom: -{band}mm; opacity: {};{}\">{}{}</div>",
+            self.opacity,
+            position,
+            logo,
+            text,
+            band = FOOTER_BAND_MM
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn footer(position: FooterPosition) -> Footer {
+        Footer { text: "A & B".to_string(), logo: None, opacity: 0.5, position }
+    }
+
+    // Alt bilgi içeriğin altına örtü koymaz; PageSetup::css'in ayırdığı şeride yerleşir
+    #[test]
+    fn footer_sits_in_reserved_band_below_content() {
+        let html = footer(FooterPosition::Left).html();
+        assert!(!html.contains("print-mask"));
+        assert!(html.contains(&format!("height: {band}mm; bottom: -{band}mm;", band = FOOTER_BAND_MM)), "{html}");
+        assert!(html.contains("<span>A &amp; B</span>"));
+        assert!(footer(FooterPosition::Right).html().contains("left: auto; right: 0;"));
+    }
+
+    #[test]
+    fn themes_do_not_pin_footer_to_content_bottom() {
+        for css in [include_str!("../themes/light/pdf.css"), include_str!("../themes/dark/pdf.css")] {
+            assert!(!css.contains("print-mask"));
+            let rule = css.lines().find(|line| line.trim_start().starts_with(".brand-footer { position: fixed")).unwrap();
+            assert!(!rule.contains("bottom"), "{rule}");
+        }
+    }
+}
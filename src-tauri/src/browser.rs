@@ -0,0 +1,267 @@
+use serde::Serialize;
+use std::ffi::OsString;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+// Tarayıcı yalnızca bu ortam değişkeniyle zorlanabilir; web görünümü çalıştırılacak programı seçemez
+pub const BROWSER_OVERRIDE_ENV: &str = "PIGNOTE_BROWSER";
+
+// PATH üzerinde aranan Chromium tabanlı tarayıcı adları (öncelik sırasıyla)
+const PATH_NAMES: &[&str] = &[
+    "chromium",
+    "chromium-browser",
+    "google-chrome",
+    "google-chrome-stable",
+    "microsoft-edge",
+    "microsoft-edge-stable",
+    "chrome",
+    "msedge",
+];
+
+// Tarayıcının nereden bulunduğu
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BrowserSource {
+    Override,
+    Path,
+    InstallDir,
+    Flatpak,
+    Snap,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BrowserInfo {
+    pub path: String,
+    pub source: BrowserSource,
+}
+
+// Ön yüze `kind` alanıyla ayırt edilebilen yapısal hata olarak gider
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BrowserDiscoveryError {
+    InvalidOverride { path: String },
+    NoEngineFound { searched: Vec<String> },
+}
+
+impl fmt::Display for BrowserDiscoveryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BrowserDiscoveryError::InvalidOverride { path } => {
+                write!(f, "{} ile belirtilen tarayıcı çalıştırılamıyor: {}", BROWSER_OVERRIDE_ENV, path)
+            }
+            BrowserDiscoveryError::NoEngineFound { .. } => {
+                write!(f, "PDF oluşturulamadı: Headless modda çalışacak Chrome/Edge/Chromium bulunamadı.")
+            }
+        }
+    }
+}
+
+// Aday yolları öncelik sırasıyla toplar; ortam değişkenleri (PIGNOTE_BROWSER, PATH) dışarıdan
+// verildiği için sahte bir çalıştırılabilir içeren geçici bir dizinle test edilebilir.
+pub fn discover(env_override: Option<&str>, path_var: Option<OsString>) -> Result<BrowserInfo, BrowserDiscoveryError> {
+    discover_in(env_override, path_var, system_candidates())
+}
+
+// Süreç ortamından okunan ayarlarla arama
+pub fn discover_from_env() -> Result<BrowserInfo, BrowserDiscoveryError> {
+    let env_override = std::env::var(BROWSER_OVERRIDE_ENV).ok();
+    discover(env_override.as_deref(), std::env::var_os("PATH"))
+}
+
+fn discover_in(env_override: Option<&str>, path_var: Option<OsString>, system: Vec<(BrowserSource, Vec<PathBuf>)>) -> Result<BrowserInfo, BrowserDiscoveryError> {
+    let override_path = env_override.map(str::trim).filter(|p| !p.is_empty());
+
+    if let Some(path) = override_path {
+        let candidate = PathBuf::from(path);
+        return if is_executable(&candidate) {
+            Ok(BrowserInfo { path: candidate.to_string_lossy().to_string(), source: BrowserSource::Override })
+        } else {
+            Err(BrowserDiscoveryError::InvalidOverride { path: path.to_string() })
+        };
+    }
+
+    let mut searched = Vec::new();
+    let groups = std::iter::once((BrowserSource::Path, path_candidates(path_var))).chain(system);
+    for (source, candidates) in groups {
+        for candidate in candidates {
+            if is_executable(&candidate) {
+                return Ok(BrowserInfo { path: candidate.to_string_lossy().to_string(), source });
+            }
+            searched.push(candidate.to_string_lossy().to_string());
+        }
+    }
+
+    Err(BrowserDiscoveryError::NoEngineFound { searched })
+}
+
+// PATH dışındaki sabit konumlar
+fn system_candidates() -> Vec<(BrowserSource, Vec<PathBuf>)> {
+    vec![
+        (BrowserSource::InstallDir, install_candidates()),
+        (BrowserSource::Flatpak, flatpak_candidates()),
+        (BrowserSource::Snap, snap_candidates()),
+    ]
+}
+
+fn path_candidates(path_var: Option<OsString>) -> Vec<PathBuf> {
+    let Some(path_var) = path_var else { return Vec::new() };
+    let dirs: Vec<PathBuf> = std::env::split_paths(&path_var).collect();
+    let mut candidates = Vec::new();
+    for name in PATH_NAMES {
+        for dir in &dirs {
+            if cfg!(windows) {
+                candidates.push(dir.join(format!("{}.exe", name)));
+            } else {
+                candidates.push(dir.join(name));
+            }
+        }
+    }
+    candidates
+}
+
+fn install_candidates() -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+    if cfg!(windows) {
+        let mut roots: Vec<PathBuf> = ["ProgramFiles", "ProgramFiles(x86)", "LOCALAPPDATA"]
+            .iter()
+            .filter_map(|var| std::env::var_os(var).map(PathBuf::from))
+            .collect();
+        roots.push(PathBuf::from(r"C:\Program Files"));
+        roots.push(PathBuf::from(r"C:\Program Files (x86)"));
+        for root in roots {
+            candidates.push(root.join(r"Google\Chrome\Application\chrome.exe"));
+            candidates.push(root.join(r"Microsoft\Edge\Application\msedge.exe"));
+            candidates.push(root.join(r"Chromium\Application\chrome.exe"));
+        }
+    } else if cfg!(target_os = "macos") {
+        let apps = [
+            "Google Chrome.app/Contents/MacOS/Google Chrome",
+            "Chromium.app/Contents/MacOS/Chromium",
+            "Microsoft Edge.app/Contents/MacOS/Microsoft Edge",
+        ];
+        let mut roots = vec![PathBuf::from("/Applications")];
+        if let Some(home) = dirs::home_dir() {
+            roots.push(home.join("Applications"));
+        }
+        for root in roots {
+            for app in apps {
+                candidates.push(root.join(app));
+            }
+        }
+    } else {
+        for prefix in ["/usr/bin", "/usr/local/bin"] {
+            for name in PATH_NAMES {
+                candidates.push(Path::new(prefix).join(name));
+            }
+        }
+        candidates.push(PathBuf::from("/opt/google/chrome/chrome"));
+        candidates.push(PathBuf::from("/opt/microsoft/msedge/msedge"));
+        candidates.push(PathBuf::from("/opt/chromium/chrome"));
+    }
+    candidates
+}
+
+fn flatpak_candidates() -> Vec<PathBuf> {
+    if !cfg!(target_os = "linux") {
+        return Vec::new();
+    }
+    let apps = ["org.chromium.Chromium", "com.google.Chrome", "com.microsoft.Edge"];
+    let mut roots = vec![PathBuf::from("/var/lib/flatpak/exports/bin")];
+    if let Some(data) = dirs::data_dir() {
+        roots.push(data.join("flatpak/exports/bin"));
+    }
+    roots
+        .iter()
+        .flat_map(|root| apps.iter().map(move |app| root.join(app)))
+        .collect()
+}
+
+fn snap_candidates() -> Vec<PathBuf> {
+    if !cfg!(target_os = "linux") {
+        return Vec::new();
+    }
+    vec![PathBuf::from("/snap/bin/chromium")]
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+// Ayarlar ekranında hangi tarayıcının kullanılacağını göstermek için
+#[tauri::command]
+pub async fn detect_pdf_engine() -> Result<BrowserInfo, BrowserDiscoveryError> {
+    discover_from_env()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    // Her test kendi geçici dizinini kullanır; paralel çalışan testler birbirini etkilemesin
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("pignote_browser_{}_{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn fake_executable(dir: &Path, name: &str) -> PathBuf {
+        let path = dir.join(if cfg!(windows) { format!("{}.exe", name) } else { name.to_string() });
+        fs::write(&path, "#!/bin/sh\n").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        path
+    }
+
+    #[test]
+    fn finds_chromium_on_path() {
+        let dir = temp_dir("path");
+        let chromium = fake_executable(&dir, "chromium");
+        let path_var = std::env::join_paths([&dir]).unwrap();
+
+        let found = discover(None, Some(path_var)).unwrap();
+        assert_eq!(found.source, BrowserSource::Path);
+        assert_eq!(PathBuf::from(found.path), chromium);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn empty_path_finds_no_engine() {
+        let result = discover_in(None, Some(OsString::new()), Vec::new());
+        assert!(matches!(result, Err(BrowserDiscoveryError::NoEngineFound { .. })));
+    }
+
+    #[test]
+    fn env_override_wins_over_path() {
+        let dir = temp_dir("override");
+        let chromium = fake_executable(&dir, "chromium");
+        let env = fake_executable(&dir, "from-env");
+        let path_var = std::env::join_paths([&dir]).unwrap();
+
+        let found = discover_in(env.to_str(), Some(path_var.clone()), Vec::new()).unwrap();
+        assert_eq!(found.source, BrowserSource::Override);
+        assert_eq!(PathBuf::from(found.path), env);
+        let found = discover_in(Some("  "), Some(path_var), Vec::new()).unwrap();
+        assert_eq!(PathBuf::from(found.path), chromium);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn missing_override_is_reported() {
+        let result = discover_in(Some("/nonexistent/pignote-browser"), None, Vec::new());
+        assert!(matches!(result, Err(BrowserDiscoveryError::InvalidOverride { path }) if path == "/nonexistent/pignote-browser"));
+    }
+}
@@ -0,0 +1,298 @@
+// Aç / Farklı Kaydet / çalışma alanı klasörü pencereleri. Pencereyi açan taraf DialogProvider arkasında olduğu için
+// testlerde gerçek pencere yerine sabit yol döndüren bir sahte sağlayıcı verilebilir.
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Runtime, State};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::encryption::{self, EncryptionState};
+use crate::history::HistoryState;
+use crate::search_index::SearchIndexState;
+use crate::workspace::WorkspaceState;
+
+pub struct FileFilter {
+    pub name: &'static str,
+    pub extensions: &'static [&'static str],
+}
+
+pub const NOTE_FILTERS: &[FileFilter] = &[
+    FileFilter { name: "Markdown", extensions: &["md", "markdown", "mdown", "mkd"] },
+    FileFilter { name: "Metin", extensions: &["txt"] },
+    FileFilter { name: "Tüm dosyalar", extensions: &["*"] },
+];
+
+const DEFAULT_EXTENSION: &str = "md";
+
+pub struct OpenRequest {
+    pub directory: Option<PathBuf>,
+    pub multiple: bool,
+    pub filters: &'static [FileFilter],
+}
+
+pub struct SaveRequest {
+    pub directory: Option<PathBuf>,
+    pub file_name: Option<String>,
+    pub filters: &'static [FileFilter],
+}
+
+// Kullanıcı pencereyi kapatırsa None döner
+pub trait DialogProvider: Send + Sync {
+    fn pick_files(&self, request: &OpenRequest) -> Option<Vec<PathBuf>>;
+    fn pick_save_path(&self, request: &SaveRequest) -> Option<PathBuf>;
+    fn pick_folder(&self, directory: Option<&Path>) -> Option<PathBuf>;
+}
+
+// tauri-plugin-dialog üzerinden yerel sistem pencereleri
+pub struct TauriDialogProvider<R: Runtime> {
+    app: AppHandle<R>,
+}
+
+impl<R: Runtime> TauriDialogProvider<R> {
+    pub fn new(app: AppHandle<R>) -> Self {
+        TauriDialogProvider { app }
+    }
+
+    fn builder(&self, directory: Option<&Path>, filters: &[FileFilter]) -> tauri_plugin_dialog::FileDialogBuilder<R> {
+        let mut builder = self.app.dialog().file();
+        for filter in filters {
+            builder = builder.add_filter(filter.name, filter.extensions);
+        }
+        if let Some(directory) = directory {
+            builder = builder.set_directory(directory);
+        }
+        builder
+    }
+}
+
+impl<R: Runtime> DialogProvider for TauriDialogProvider<R> {
+    // Bloklayan çağrılar: async komutlar ana iş parçacığında çalışmadığı için güvenli
+    fn pick_files(&self, request: &OpenRequest) -> Option<Vec<PathBuf>> {
+        let builder = self.builder(request.directory.as_deref(), request.filters);
+        let picked = if request.multiple {
+            builder.blocking_pick_files()?
+        } else {
+            vec![builder.blocking_pick_file()?]
+        };
+        Some(picked.into_iter().filter_map(|p| p.into_path().ok()).collect())
+    }
+
+    fn pick_save_path(&self, request: &SaveRequest) -> Option<PathBuf> {
+        let mut builder = self.builder(request.directory.as_deref(), request.filters);
+        if let Some(name) = &request.file_name {
+            builder = builder.set_file_name(name);
+        }
+        builder.blocking_save_file()?.into_path().ok()
+    }
+
+    fn pick_folder(&self, directory: Option<&Path>) -> Option<PathBuf> {
+        self.builder(directory, &[]).blocking_pick_folder()?.into_path().ok()
+    }
+}
+
+pub struct DialogState {
+    provider: Box<dyn DialogProvider>,
+    last_directory: Mutex<Option<PathBuf>>,
+    // Son klasör uygulama yeniden açıldığında da hatırlansın diye bu dosyada tutulur
+    store_path: Option<PathBuf>,
+}
+
+impl DialogState {
+    pub fn new(provider: Box<dyn DialogProvider>, store_path: Option<PathBuf>) -> Self {
+        let last_directory = store_path
+            .as_ref()
+            .and_then(|p| std::fs::read_to_string(p).ok())
+            .map(|s| PathBuf::from(s.trim()))
+            .filter(|p| p.is_dir());
+        DialogState { provider, last_directory: Mutex::new(last_directory), store_path }
+    }
+
+    fn last_directory(&self) -> Option<PathBuf> {
+        self.last_directory.lock().unwrap().clone()
+    }
+
+    fn remember(&self, picked: &Path) {
+        let Some(directory) = picked.parent() else { return };
+        *self.last_directory.lock().unwrap() = Some(directory.to_path_buf());
+        if let Some(store) = &self.store_path {
+            if let Some(parent) = store.parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+            if let Err(e) = std::fs::write(store, directory.to_string_lossy().as_bytes()) {
+                log::warn!("Son klasör kaydedilemedi: {}", e);
+            }
+        }
+    }
+
+    // Seçilen dosyalar döner; ilk dosyanın klasörü bir sonraki pencere için hatırlanır
+    pub fn pick_notes(&self, multiple: bool) -> Option<Vec<PathBuf>> {
+        let request = OpenRequest { directory: self.last_directory(), multiple, filters: NOTE_FILTERS };
+        let paths = self.provider.pick_files(&request)?;
+        if let Some(first) = paths.first() {
+            self.remember(first);
+        }
+        Some(paths)
+    }
+
+    // Kayıt yolunu seçtirip `write` ile yazar; klasör yalnızca yazım başarılıysa hatırlanır
+    pub fn save_note(&self, default_name: Option<String>, write: impl FnOnce(&Path) -> Result<(), String>) -> Result<Option<PathBuf>, String> {
+        let request = SaveRequest {
+            directory: self.last_directory(),
+            file_name: default_name.or_else(|| Some(format!("Adsız.{}", DEFAULT_EXTENSION))),
+            filters: NOTE_FILTERS,
+        };
+        let Some(mut path) = self.provider.pick_save_path(&request) else { return Ok(None) };
+        // Bazı platformlarda filtre uzantıyı eklemiyor. Pencerenin üzerine yazma sorusu uzantısız ad için
+        // sorulduğundan, uzantı eklenince var olan bir dosyaya denk gelirse sormadan ezilmez
+        if path.extension().is_none() {
+            path.set_extension(DEFAULT_EXTENSION);
+            if path.exists() {
+                return Err(format!("'{}' zaten mevcut; üzerine yazmak için dosyayı uzantısıyla birlikte seçin", path.display()));
+            }
+        }
+        write(&path)?;
+        self.remember(&path);
+        Ok(Some(path))
+    }
+}
+
+// Markdown/metin dosyası seçtirir; `multiple` ile birden fazla dosya seçilebilir
+#[tauri::command]
+pub async fn open_file_dialog(state: State<'_, DialogState>, workspace: State<'_, WorkspaceState>, multiple: Option<bool>) -> Result<Option<Vec<String>>, String> {
+    let Some(paths) = state.pick_notes(multiple.unwrap_or(false)) else { return Ok(None) };
+    // Kullanıcının kendisinin seçtiği dosyalar çalışma alanı dışında olsa da açılabilir
+    for path in &paths {
+        workspace.grant(path);
+    }
+    Ok(Some(paths.iter().map(|p| p.to_string_lossy().to_string()).collect()))
+}
+
+// Farklı Kaydet: seçilen yola içeriği yazar ve yolu döndürür
+#[tauri::command]
+pub async fn save_file_dialog(state: State<'_, DialogState>, index: State<'_, SearchIndexState>, history: State<'_, HistoryState>, encryption: State<'_, EncryptionState>, workspace: State<'_, WorkspaceState>, content: String, default_name: Option<String>) -> Result<Option<String>, String> {
+    let saved = state.save_note(default_name, |path| {
+        encryption::write_content(&encryption, &history, &index, path, &content, &Default::default())?;
+        Ok(())
+    })?;
+    let Some(path) = saved else { return Ok(None) };
+    workspace.grant(&path);
+    Ok(Some(path.to_string_lossy().to_string()))
+}
+
+// Çalışma alanı kökünü kullanıcıya seçtirir; dosya komutları bundan sonra yalnızca bu klasörün içinde çalışır
+#[tauri::command]
+pub async fn open_workspace_dialog(state: State<'_, DialogState>, workspace: State<'_, WorkspaceState>) -> Result<Option<String>, String> {
+    let current = workspace.root();
+    let Some(picked) = state.provider.pick_folder(current.as_deref()) else { return Ok(None) };
+    let root = workspace.open(&picked)?;
+    Ok(Some(root.to_string_lossy().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::safe_write;
+    use std::sync::Arc;
+
+    type Calls<T> = Arc<Mutex<Vec<T>>>;
+
+    // Gerçek pencere açmadan sabit yollar döndürür; istenen başlangıç klasörlerini kaydeder
+    #[derive(Default)]
+    struct MockDialogProvider {
+        files: Vec<PathBuf>,
+        save_path: Option<PathBuf>,
+        open_requests: Calls<(Option<PathBuf>, bool)>,
+        save_requests: Calls<(Option<PathBuf>, Option<String>)>,
+    }
+
+    impl DialogProvider for MockDialogProvider {
+        fn pick_files(&self, request: &OpenRequest) -> Option<Vec<PathBuf>> {
+            self.open_requests.lock().unwrap().push((request.directory.clone(), request.multiple));
+            if self.files.is_empty() {
+                None
+            } else {
+                Some(self.files.clone())
+            }
+        }
+
+        fn pick_save_path(&self, request: &SaveRequest) -> Option<PathBuf> {
+            self.save_requests.lock().unwrap().push((request.directory.clone(), request.file_name.clone()));
+            self.save_path.clone()
+        }
+
+        fn pick_folder(&self, _directory: Option<&Path>) -> Option<PathBuf> {
+            None
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("pignote_dialogs_{}_{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn open_returns_all_picked_files_and_remembers_directory() {
+        let dir = temp_dir("open");
+        let notes = dir.join("notlar");
+        std::fs::create_dir_all(&notes).unwrap();
+        let files = vec![notes.join("a.md"), notes.join("b.md")];
+        let store = dir.join("last_dir");
+        let provider = MockDialogProvider { files: files.clone(), ..Default::default() };
+        let requests = provider.open_requests.clone();
+        let state = DialogState::new(Box::new(provider), Some(store.clone()));
+
+        assert_eq!(state.pick_notes(true), Some(files.clone()));
+        assert_eq!(state.pick_notes(false), Some(files));
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0], (None, true));
+        assert_eq!(requests[1], (Some(notes.clone()), false));
+
+        // Yeniden açılışta kayıtlı klasörden başlanır
+        let reopened = DialogState::new(Box::new(MockDialogProvider::default()), Some(store));
+        assert_eq!(reopened.last_directory(), Some(notes));
+        assert_eq!(reopened.pick_notes(true), None);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn save_writes_content_with_default_extension() {
+        let dir = temp_dir("save");
+        let provider = MockDialogProvider { save_path: Some(dir.join("yeni")), ..Default::default() };
+        let requests = provider.save_requests.clone();
+        let state = DialogState::new(Box::new(provider), None);
+
+        let saved = state
+            .save_note(None, |path| safe_write::write_atomic(path, "# Başlık".as_bytes(), &Default::default()).map(|_| ()).map_err(|e| e.to_string()))
+            .unwrap();
+        let path = dir.join("yeni.md");
+        assert_eq!(saved, Some(path.clone()));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "# Başlık");
+        assert_eq!(state.last_directory(), Some(dir.clone()));
+        assert_eq!(requests.lock().unwrap()[0], (None, Some("Adsız.md".to_string())));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn save_refuses_to_overwrite_file_matched_by_added_extension() {
+        let dir = temp_dir("save_existing");
+        let existing = dir.join("yeni.md");
+        std::fs::write(&existing, "eski").unwrap();
+        let provider = MockDialogProvider { save_path: Some(dir.join("yeni")), ..Default::default() };
+        let state = DialogState::new(Box::new(provider), None);
+
+        let result = state.save_note(None, |_| panic!("var olan dosyanın üzerine yazılmamalı"));
+        assert!(result.is_err());
+        assert_eq!(std::fs::read_to_string(&existing).unwrap(), "eski");
+        assert_eq!(state.last_directory(), None);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn cancelled_save_writes_nothing() {
+        let state = DialogState::new(Box::new(MockDialogProvider::default()), None);
+        let saved = state.save_note(Some("not.md".to_string()), |_| panic!("pencere kapatıldığında yazılmamalı")).unwrap();
+        assert_eq!(saved, None);
+        assert_eq!(state.last_directory(), None);
+    }
+}
@@ -0,0 +1,787 @@
+// Markdown AST'sinden gerçek WordprocessingML üreten DOCX yazıcısı.
+// Word dışındaki uygulamalar (LibreOffice, Google Docs) altChunk'ı okumadığı için
+// içerik w:p / w:r / w:tbl olarak yazılır; stiller styles.xml, listeler numbering.xml içindedir.
+use comrak::nodes::{AstNode, ListType, NodeValue, TableAlignment};
+use comrak::{parse_document, Arena};
+use regex::Regex;
+use std::io::Write as _;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::frontmatter::{self, NoteMetadata};
+use crate::images::{self, ImageFormat};
+use crate::markdown;
+use crate::tags;
+use crate::toc::{self, TocOptions};
+use crate::wikilinks::{self, LinkContext};
+use crate::workspace::ReadScope;
+
+// A4, 1 inç kenar boşluğu (twip)
+const PAGE_WIDTH: u32 = 11906;
+const PAGE_HEIGHT: u32 = 16838;
+const PAGE_MARGIN: u32 = 1440;
+const CONTENT_WIDTH: u32 = PAGE_WIDTH - 2 * PAGE_MARGIN;
+const EMU_PER_PX: u64 = 9525;
+const EMU_PER_TWIP: u64 = 635;
+const LIST_INDENT: u32 = 720;
+
+const NS_W: &str = "http://schemas.openxmlformats.org/wordprocessingml/2006/main";
+const NS_R: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+const NS_WP: &str = "http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing";
+const REL_HYPERLINK: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink";
+const REL_IMAGE: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships/image";
+
+pub struct DocxOptions<'a> {
+    pub dark: bool,
+    // Görseller notun klasörüne göre ve yalnızca çalışma alanından okunur
+    pub scope: &'a ReadScope,
+    pub links: Option<&'a LinkContext>,
+    // docProps/core.xml için başlık, yazar, tarih ve etiketler
+    pub metadata: Option<&'a NoteMetadata>,
+    pub toc: TocOptions,
+    // Export işinin iptal bayrağı
+    pub cancel: Option<&'a AtomicBool>,
+}
+
+// Markdown içeriğini DOCX paketine yazar
+pub fn write_docx(markdown: &str, options: &DocxOptions, output_path: &Path) -> Result<(), String> {
+    let arena = Arena::new();
+    let comrak_options = markdown::options();
+    let root = parse_document(&arena, frontmatter::body(markdown), &comrak_options);
+    wikilinks::resolve_in_document(root, options.links);
+    let headings = toc::expand_in_document(&arena, root, &options.toc);
+
+    let mut writer = DocxWriter::new(options);
+    writer.heading_ids = headings.into_iter().map(|heading| heading.id).collect::<Vec<_>>().into_iter();
+    writer.blocks(root, &BlockContext::default())?;
+    writer.finish(output_path)
+}
+
+// Önizleme HTML'i gelirse yapı çıkarılamaz; blok etiketlerine göre paragraflara bölünüp
+// Markdown olarak yorumlanmaması için kaçışlanmış düz metne çevrilir
+pub fn html_to_plain_markdown(html: &str) -> String {
+    let break_re = Regex::new(r"(?i)<br\s*/?>").unwrap();
+    let block_re = Regex::new(r"(?i)</(p|div|h[1-6]|li|tr|blockquote|pre|table|ul|ol)>").unwrap();
+    let tag_re = Regex::new(r"<[^>]*>").unwrap();
+    let text = break_re.replace_all(html, "\n");
+    let text = block_re.replace_all(&text, "\n\n");
+    let text = tag_re.replace_all(&text, "");
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+
+    text.split("\n\n")
+        .map(|block| {
+            block
+                .lines()
+                .map(|line| {
+                    let mut escaped = String::new();
+                    for c in line.trim().chars() {
+                        if c.is_ascii_punctuation() {
+                            escaped.push('\\');
+                        }
+                        escaped.push(c);
+                    }
+                    escaped
+                })
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join("  \n")
+        })
+        .filter(|block| !block.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+// Word yer imi adları en fazla 40 karakter olabilir; link hedefi de aynı kısaltmayı kullanır
+fn bookmark_name(id: &str) -> String {
+    id.chars().take(40).collect()
+}
+
+fn xml_escape(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            // XML 1.0'da geçersiz kontrol karakterleri atlanır
+            '\t' | '\n' | '\r' => out.push(c),
+            c if (c as u32) < 0x20 => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct RunFormat {
+    bold: bool,
+    italic: bool,
+    strike: bool,
+    underline: bool,
+    code: bool,
+    tag: bool,
+    hyperlink: bool,
+    superscript: bool,
+    subscript: bool,
+}
+
+impl RunFormat {
+    fn properties(&self) -> String {
+        let mut props = String::new();
+        if self.code {
+            props.push_str("<w:rStyle w:val=\"CodeChar\"/>");
+        } else if self.tag {
+            props.push_str("<w:rStyle w:val=\"TagChar\"/>");
+        } else if self.hyperlink {
+            props.push_str("<w:rStyle w:val=\"Hyperlink\"/>");
+        }
+        if self.bold {
+            props.push_str("<w:b/>");
+        }
+        if self.italic {
+            props.push_str("<w:i/>");
+        }
+        if self.strike {
+            props.push_str("<w:strike/>");
+        }
+        if self.underline {
+            props.push_str("<w:u w:val=\"single\"/>");
+        }
+        if self.superscript {
+            props.push_str("<w:vertAlign w:val=\"superscript\"/>");
+        } else if self.subscript {
+            props.push_str("<w:vertAlign w:val=\"subscript\"/>");
+        }
+        if props.is_empty() { props } else { format!("<w:rPr>{}</w:rPr>", props) }
+    }
+}
+
+fn text_run(text: &str, format: &RunFormat) -> String {
+    format!("<w:r>{}<w:t xml:space=\"preserve\">{}</w:t></w:r>", format.properties(), xml_escape(text))
+}
+
+#[derive(Clone, Default)]
+struct BlockContext {
+    quote_depth: u32,
+    list_level: u32,
+    indent: u32,
+    tight: bool,
+}
+
+struct Relationship {
+    id: String,
+    kind: &'static str,
+    target: String,
+    external: bool,
+}
+
+struct ListInstance {
+    ordered: bool,
+    level: u32,
+    start: usize,
+}
+
+struct DocxWriter<'a> {
+    options: &'a DocxOptions<'a>,
+    body: String,
+    relationships: Vec<Relationship>,
+    media: Vec<(String, Vec<u8>)>,
+    lists: Vec<ListInstance>,
+    // Liste öğesinin ilk paragrafı numaralandırmayı ve görev kutusunu alır
+    pending_numbering: Option<(usize, u32)>,
+    pending_prefix: Option<String>,
+    drawing_id: u32,
+    // Başlıklar bu kimliklerle yer imi alır; "#kimlik" linkleri yer imine gider
+    heading_ids: std::vec::IntoIter<String>,
+    bookmark_id: u32,
+}
+
+impl<'a> DocxWriter<'a> {
+    fn new(options: &'a DocxOptions<'a>) -> Self {
+        DocxWriter {
+            options,
+            body: String::new(),
+            relationships: Vec::new(),
+            media: Vec::new(),
+            lists: Vec::new(),
+            pending_numbering: None,
+            pending_prefix: None,
+            drawing_id: 0,
+            heading_ids: Vec::new().into_iter(),
+            bookmark_id: 0,
+        }
+    }
+
+    fn add_relationship(&mut self, kind: &'static str, target: String, external: bool) -> String {
+        // rId1-3 styles/numbering/settings için ayrılmıştır
+        let id = format!("rId{}", self.relationships.len() + 4);
+        self.relationships.push(Relationship { id: id.clone(), kind, target, external });
+        id
+    }
+
+    fn check_cancel(&self) -> Result<(), String> {
+        if self.options.cancel.is_some_and(|cancel| cancel.load(Ordering::SeqCst)) {
+            return Err("İşlem iptal edildi".to_string());
+        }
+        Ok(())
+    }
+
+    fn blocks<'n>(&mut self, node: &'n AstNode<'n>, ctx: &BlockContext) -> Result<(), String> {
+        for child in node.children() {
+            self.block(child, ctx)?;
+        }
+        Ok(())
+    }
+
+    fn block<'n>(&mut self, node: &'n AstNode<'n>, ctx: &BlockContext) -> Result<(), String> {
+        self.check_cancel()?;
+        let value = node.data.borrow().value.clone();
+        match value {
+            NodeValue::Paragraph => {
+                let runs = self.inlines(node, RunFormat::default());
+                let spacing = if ctx.tight { "<w:spacing w:after=\"0\"/>" } else { "" };
+                self.paragraph(ctx, None, spacing, &runs);
+            }
+            NodeValue::Heading(heading) => {
+                let mut runs = self.inlines(node, RunFormat::default());
+                if let Some(id) = self.heading_ids.next() {
+                    let bookmark = self.bookmark_id;
+                    self.bookmark_id += 1;
+                    runs = format!(
+                        "<w:bookmarkStart w:id=\"{}\" w:name=\"{}\"/>{}<w:bookmarkEnd w:id=\"{}\"/>",
+                        bookmark,
+                        xml_escape(&bookmark_name(&id)),
+                        runs,
+                        bookmark
+                    );
+                }
+                let style = format!("Heading{}", heading.level.clamp(1, 6));
+                self.paragraph(ctx, Some(&style), "", &runs);
+            }
+            NodeValue::BlockQuote | NodeValue::MultilineBlockQuote(_) | NodeValue::Alert(_) => {
+                let inner = BlockContext { quote_depth: ctx.quote_depth + 1, ..ctx.clone() };
+                self.blocks(node, &inner)?;
+            }
+            NodeValue::List(list) => {
+                self.lists.push(ListInstance {
+                    ordered: list.list_type == ListType::Ordered,
+                    level: ctx.list_level,
+                    start: list.start,
+                });
+                let num_id = self.lists.len();
+                for item in node.children() {
+                    if let NodeValue::TaskItem(mark) = item.data.borrow().value {
+                        self.pending_prefix = Some(if mark.is_some() { "☒ " } else { "☐ " }.to_string());
+                    }
+                    self.pending_numbering = Some((num_id, ctx.list_level));
+                    let inner = BlockContext {
+                        list_level: ctx.list_level + 1,
+                        indent: LIST_INDENT * (ctx.list_level + 1),
+                        tight: list.tight,
+                        ..ctx.clone()
+                    };
+                    self.blocks(item, &inner)?;
+                    // İçeriği olmayan öğe de numarasını göstermeli
+                    if self.pending_numbering.is_some() {
+                        self.paragraph(&inner, None, "", "");
+                    }
+                }
+            }
+            NodeValue::CodeBlock(code) => {
+                let mut runs = String::new();
+                for (i, line) in code.literal.trim_end_matches('\n').split('\n').enumerate() {
+                    if i > 0 {
+                        runs.push_str("<w:r><w:br/></w:r>");
+                    }
+                    runs.push_str(&text_run(&line.replace('\t', "    "), &RunFormat::default()));
+                }
+                self.paragraph(ctx, Some("CodeBlock"), "", &runs);
+            }
+            NodeValue::Table(table) => self.table(node, &table.alignments, ctx)?,
+            NodeValue::ThematicBreak => {
+                let border = "<w:pBdr><w:bottom w:val=\"single\" w:sz=\"6\" w:space=\"1\" w:color=\"auto\"/></w:pBdr>";
+                self.paragraph(ctx, None, border, "");
+            }
+            NodeValue::FootnoteDefinition(definition) => {
+                self.pending_prefix = Some(format!("[{}] ", definition.name));
+                self.blocks(node, ctx)?;
+            }
+            NodeValue::HtmlBlock(_) | NodeValue::FrontMatter(_) => {}
+            _ => self.blocks(node, ctx)?,
+        }
+        Ok(())
+    }
+
+    fn paragraph(&mut self, ctx: &BlockContext, style: Option<&str>, extra_properties: &str, runs: &str) {
+        let mut properties = String::new();
+        let style = style.or(if ctx.quote_depth > 0 { Some("Quote") } else { None });
+        if let Some(style) = style {
+            properties.push_str(&format!("<w:pStyle w:val=\"{}\"/>", style));
+        } else if self.pending_numbering.is_some() {
+            properties.push_str("<w:pStyle w:val=\"ListParagraph\"/>");
+        }
+        if let Some((num_id, level)) = self.pending_numbering.take() {
+            properties.push_str(&format!("<w:numPr><w:ilvl w:val=\"{}\"/><w:numId w:val=\"{}\"/></w:numPr>", level, num_id));
+        } else {
+            let indent = ctx.indent + ctx.quote_depth.saturating_sub(1) * 360 + if ctx.quote_depth > 0 { 360 } else { 0 };
+            if indent > 0 {
+                properties.push_str(&format!("<w:ind w:left=\"{}\"/>", indent));
+            }
+        }
+        properties.push_str(extra_properties);
+
+        self.body.push_str("<w:p>");
+        if !properties.is_empty() {
+            self.body.push_str(&format!("<w:pPr>{}</w:pPr>", properties));
+        }
+        if let Some(prefix) = self.pending_prefix.take() {
+            self.body.push_str(&text_run(&prefix, &RunFormat::default()));
+        }
+        self.body.push_str(runs);
+        self.body.push_str("</w:p>");
+    }
+
+    fn inlines<'n>(&mut self, node: &'n AstNode<'n>, format: RunFormat) -> String {
+        let mut runs = String::new();
+        for child in node.children() {
+            let value = child.data.borrow().value.clone();
+            match value {
+                NodeValue::Text(text) => {
+                    for (part, is_tag) in tags::split_text(&text) {
+                        runs.push_str(&text_run(part, &RunFormat { tag: is_tag, ..format }));
+                    }
+                }
+                NodeValue::SoftBreak => runs.push_str(&text_run(" ", &format)),
+                NodeValue::LineBreak => runs.push_str("<w:r><w:br/></w:r>"),
+                NodeValue::Code(code) => runs.push_str(&text_run(&code.literal, &RunFormat { code: true, ..format })),
+                NodeValue::Math(math) => runs.push_str(&text_run(&math.literal, &RunFormat { code: true, ..format })),
+                NodeValue::EscapedTag(tag) => runs.push_str(&text_run(&tag, &format)),
+                NodeValue::HtmlInline(html) => {
+                    if html.to_ascii_lowercase().starts_with("<br") {
+                        runs.push_str("<w:r><w:br/></w:r>");
+                    }
+                }
+                NodeValue::Emph => runs.push_str(&self.inlines(child, RunFormat { italic: true, ..format })),
+                NodeValue::Strong => runs.push_str(&self.inlines(child, RunFormat { bold: true, ..format })),
+                NodeValue::Strikethrough => runs.push_str(&self.inlines(child, RunFormat { strike: true, ..format })),
+                NodeValue::Underline => runs.push_str(&self.inlines(child, RunFormat { underline: true, ..format })),
+                NodeValue::Superscript => runs.push_str(&self.inlines(child, RunFormat { superscript: true, ..format })),
+                NodeValue::Subscript => runs.push_str(&self.inlines(child, RunFormat { subscript: true, ..format })),
+                NodeValue::Link(link) => runs.push_str(&self.hyperlink(child, &link.url, format)),
+                NodeValue::WikiLink(link) => runs.push_str(&self.hyperlink(child, &link.url, format)),
+                NodeValue::Image(link) => {
+                    let alt = plain_text(child);
+                    runs.push_str(&self.image(&link.url, &alt, format));
+                }
+                NodeValue::FootnoteReference(reference) => {
+                    runs.push_str(&text_run(&format!("[{}]", reference.name), &RunFormat { superscript: true, ..format }));
+                }
+                _ => runs.push_str(&self.inlines(child, format)),
+            }
+        }
+        runs
+    }
+
+    fn hyperlink<'n>(&mut self, node: &'n AstNode<'n>, url: &str, format: RunFormat) -> String {
+        let inner = self.inlines(node, RunFormat { hyperlink: true, ..format });
+        if let Some(anchor) = url.strip_prefix('#') {
+            return format!("<w:hyperlink w:anchor=\"{}\">{}</w:hyperlink>", xml_escape(&bookmark_name(anchor)), inner);
+        }
+        let id = self.add_relationship(REL_HYPERLINK, url.to_string(), true);
+        format!("<w:hyperlink r:id=\"{}\" w:history=\"1\">{}</w:hyperlink>", id, inner)
+    }
+
+    fn image(&mut self, url: &str, alt: &str, format: RunFormat) -> String {
+        let loaded = images::load_image_bytes(url, self.options.scope)
+            .and_then(|bytes| Some((ImageFormat::detect(&bytes)?, images::dimensions(&bytes)?, bytes)));
+        let Some((image_format, (width, height), bytes)) = loaded else {
+            // Gömülemeyen görselin yerine alternatif metin
+            return if alt.is_empty() { String::new() } else { text_run(&format!("[{}]", alt), &RunFormat { italic: true, ..format }) };
+        };
+
+        let name = format!("image{}.{}", self.media.len() + 1, image_format.extension());
+        self.media.push((name.clone(), bytes));
+        let id = self.add_relationship(REL_IMAGE, format!("media/{}", name), false);
+        self.drawing_id += 1;
+
+        // Sayfa genişliğine ve en fazla 16cm yüksekliğe sığdır
+        let max_cx = CONTENT_WIDTH as u64 * EMU_PER_TWIP;
+        let max_cy = 5_760_000u64;
+        let (mut cx, mut cy) = (width.max(1) as u64 * EMU_PER_PX, height.max(1) as u64 * EMU_PER_PX);
+        if cx > max_cx {
+            cy = cy * max_cx / cx;
+            cx = max_cx;
+        }
+        if cy > max_cy {
+            cx = cx * max_cy / cy;
+            cy = max_cy;
+        }
+
+        format!(
+            concat!(
+                "<w:r><w:drawing><wp:inline distT=\"0\" distB=\"0\" distL=\"0\" distR=\"0\">",
+                "<wp:extent cx=\"{cx}\" cy=\"{cy}\"/><wp:docPr id=\"{id_num}\" name=\"Picture {id_num}\" descr=\"{alt}\"/>",
+                "<a:graphic xmlns:a=\"http://schemas.openxmlformats.org/drawingml/2006/main\">",
+                "<a:graphicData uri=\"http://schemas.openxmlformats.org/drawingml/2006/picture\">",
+                "<pic:pic xmlns:pic=\"http://schemas.openxmlformats.org/drawingml/2006/picture\">",
+                "<pic:nvPicPr><pic:cNvPr id=\"{id_num}\" name=\"{name}\"/><pic:cNvPicPr/></pic:nvPicPr>",
+                "<pic:blipFill><a:blip r:embed=\"{rel}\"/><a:stretch><a:fillRect/></a:stretch></pic:blipFill>",
+                "<pic:spPr><a:xfrm><a:off x=\"0\" y=\"0\"/><a:ext cx=\"{cx}\" cy=\"{cy}\"/></a:xfrm>",
+                "<a:prstGeom prst=\"rect\"><a:avLst/></a:prstGeom></pic:spPr>",
+                "</pic:pic></a:graphicData></a:graphic></wp:inline></w:drawing></w:r>"
+            ),
+            cx = cx,
+            cy = cy,
+            id_num = self.drawing_id,
+            alt = xml_escape(alt),
+            name = name,
+            rel = id,
+        )
+    }
+
+    fn table<'n>(&mut self, node: &'n AstNode<'n>, alignments: &[TableAlignment], ctx: &BlockContext) -> Result<(), String> {
+        let columns = alignments.len().max(1) as u32;
+        let available = CONTENT_WIDTH.saturating_sub(ctx.indent);
+        let column_width = available / columns;
+        let (border, header_fill) = if self.options.dark { ("334155", "111827") } else { ("E5E7EB", "F3F4F6") };
+
+        let mut xml = String::from("<w:tbl><w:tblPr><w:tblStyle w:val=\"TableGrid\"/>");
+        xml.push_str(&format!("<w:tblW w:w=\"{}\" w:type=\"dxa\"/>", available));
+        if ctx.indent > 0 {
+            xml.push_str(&format!("<w:tblInd w:w=\"{}\" w:type=\"dxa\"/>", ctx.indent));
+        }
+        xml.push_str(&format!(
+            "<w:tblBorders><w:top w:val=\"single\" w:sz=\"4\" w:color=\"{b}\"/><w:left w:val=\"single\" w:sz=\"4\" w:color=\"{b}\"/><w:bottom w:val=\"single\" w:sz=\"4\" w:color=\"{b}\"/><w:right w:val=\"single\" w:sz=\"4\" w:color=\"{b}\"/><w:insideH w:val=\"single\" w:sz=\"4\" w:color=\"{b}\"/><w:insideV w:val=\"single\" w:sz=\"4\" w:color=\"{b}\"/></w:tblBorders>",
+            b = border
+        ));
+        xml.push_str("<w:tblLayout w:type=\"fixed\"/></w:tblPr><w:tblGrid>");
+        for _ in 0..columns {
+            xml.push_str(&format!("<w:gridCol w:w=\"{}\"/>", column_width));
+        }
+        xml.push_str("</w:tblGrid>");
+
+        for row in node.children() {
+            self.check_cancel()?;
+            let header = matches!(row.data.borrow().value, NodeValue::TableRow(true));
+            xml.push_str("<w:tr>");
+            if header {
+                xml.push_str("<w:trPr><w:tblHeader/></w:trPr>");
+            }
+            let mut cells = 0;
+            for (i, cell) in row.children().enumerate() {
+                cells += 1;
+                let runs = self.inlines(cell, RunFormat { bold: header, ..RunFormat::default() });
+                xml.push_str(&format!("<w:tc><w:tcPr><w:tcW w:w=\"{}\" w:type=\"dxa\"/>", column_width));
+                if header {
+                    xml.push_str(&format!("<w:shd w:val=\"clear\" w:color=\"auto\" w:fill=\"{}\"/>", header_fill));
+                }
+                xml.push_str("</w:tcPr><w:p><w:pPr><w:spacing w:after=\"0\"/>");
+                match alignments.get(i) {
+                    Some(TableAlignment::Center) => xml.push_str("<w:jc w:val=\"center\"/>"),
+                    Some(TableAlignment::Right) => xml.push_str("<w:jc w:val=\"right\"/>"),
+                    _ => {}
+                }
+                xml.push_str("</w:pPr>");
+                xml.push_str(&runs);
+                xml.push_str("</w:p></w:tc>");
+            }
+            // Eksik hücreler de w:p içermeli, yoksa Word dosyayı bozuk sayar
+            for _ in cells..columns {
+                xml.push_str(&format!("<w:tc><w:tcPr><w:tcW w:w=\"{}\" w:type=\"dxa\"/></w:tcPr><w:p/></w:tc>", column_width));
+            }
+            xml.push_str("</w:tr>");
+        }
+        xml.push_str("</w:tbl>");
+        self.body.push_str(&xml);
+        // Art arda iki tablo birleşmesin ve tablodan sonra boşluk kalsın
+        self.body.push_str("<w:p/>");
+        Ok(())
+    }
+
+    fn document_xml(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n");
+        xml.push_str(&format!("<w:document xmlns:w=\"{}\" xmlns:r=\"{}\" xmlns:wp=\"{}\">", NS_W, NS_R, NS_WP));
+        if self.options.dark {
+            xml.push_str("<w:background w:color=\"0F172A\"/>");
+        }
+        xml.push_str("<w:body>");
+        xml.push_str(&self.body);
+        xml.push_str(&format!(
+            "<w:sectPr><w:pgSz w:w=\"{}\" w:h=\"{}\"/><w:pgMar w:top=\"{m}\" w:right=\"{m}\" w:bottom=\"{m}\" w:left=\"{m}\" w:header=\"708\" w:footer=\"708\" w:gutter=\"0\"/></w:sectPr>",
+            PAGE_WIDTH,
+            PAGE_HEIGHT,
+            m = PAGE_MARGIN
+        ));
+        xml.push_str("</w:body></w:document>");
+        xml
+    }
+
+    fn styles_xml(&self) -> String {
+        let palette = if self.options.dark {
+            ("D1D5DB", "E5E7EB", "CBD5E1", "0B1220", "F59E0B", "F472B6", "3B0A24", "F9A8D4")
+        } else {
+            ("111827", "111827", "6B7280", "F3F4F6", "111827", "EC4899", "FCE7F3", "BE185D")
+        };
+        let (text, heading, muted, code_fill, code_text, link, tag_fill, tag_text) = palette;
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n");
+        xml.push_str(&format!("<w:styles xmlns:w=\"{}\">", NS_W));
+        xml.push_str(&format!(
+            concat!(
+                "<w:docDefaults><w:rPrDefault><w:rPr><w:rFonts w:ascii=\"Calibri\" w:hAnsi=\"Calibri\" w:eastAsia=\"Calibri\" w:cs=\"Calibri\"/>",
+                "<w:color w:val=\"{}\"/><w:sz w:val=\"22\"/><w:szCs w:val=\"22\"/><w:lang w:val=\"tr-TR\"/></w:rPr></w:rPrDefault>",
+                "<w:pPrDefault><w:pPr><w:spacing w:after=\"160\" w:line=\"276\" w:lineRule=\"auto\"/></w:pPr></w:pPrDefault></w:docDefaults>"
+            ),
+            text
+        ));
+        xml.push_str("<w:style w:type=\"paragraph\" w:default=\"1\" w:styleId=\"Normal\"><w:name w:val=\"Normal\"/><w:qFormat/></w:style>");
+
+        for (level, size) in [(1, 36), (2, 32), (3, 28), (4, 24), (5, 22), (6, 22)] {
+            let border = if level <= 2 {
+                "<w:pBdr><w:bottom w:val=\"single\" w:sz=\"6\" w:space=\"4\" w:color=\"E5E7EB\"/></w:pBdr>"
+            } else {
+                ""
+            };
+            xml.push_str(&format!(
+                concat!(
+                    "<w:style w:type=\"paragraph\" w:styleId=\"Heading{l}\"><w:name w:val=\"heading {l}\"/>",
+                    "<w:basedOn w:val=\"Normal\"/><w:next w:val=\"Normal\"/><w:uiPriority w:val=\"9\"/><w:qFormat/>",
+                    "<w:pPr><w:keepNext/><w:keepLines/>{border}<w:spacing w:before=\"360\" w:after=\"120\"/><w:outlineLvl w:val=\"{o}\"/></w:pPr>",
+                    "<w:rPr><w:b/><w:bCs/><w:color w:val=\"{c}\"/><w:sz w:val=\"{s}\"/><w:szCs w:val=\"{s}\"/></w:rPr></w:style>"
+                ),
+                l = level,
+                o = level - 1,
+                border = border,
+                c = heading,
+                s = size
+            ));
+        }
+
+        xml.push_str(&format!(
+            concat!(
+                "<w:style w:type=\"paragraph\" w:styleId=\"Quote\"><w:name w:val=\"Quote\"/><w:basedOn w:val=\"Normal\"/><w:qFormat/>",
+                "<w:pPr><w:pBdr><w:left w:val=\"single\" w:sz=\"24\" w:space=\"8\" w:color=\"EC4899\"/></w:pBdr><w:ind w:left=\"360\"/></w:pPr>",
+                "<w:rPr><w:color w:val=\"{}\"/></w:rPr></w:style>"
+            ),
+            muted
+        ));
+        xml.push_str(&format!(
+            concat!(
+                "<w:style w:type=\"paragraph\" w:customStyle=\"1\" w:styleId=\"CodeBlock\"><w:name w:val=\"Code Block\"/><w:basedOn w:val=\"Normal\"/>",
+                "<w:pPr><w:shd w:val=\"clear\" w:color=\"auto\" w:fill=\"{f}\"/><w:spacing w:after=\"160\" w:line=\"240\" w:lineRule=\"auto\"/></w:pPr>",
+                "<w:rPr><w:rFonts w:ascii=\"Consolas\" w:hAnsi=\"Consolas\" w:cs=\"Consolas\"/><w:sz w:val=\"19\"/><w:szCs w:val=\"19\"/></w:rPr></w:style>",
+                "<w:style w:type=\"character\" w:customStyle=\"1\" w:styleId=\"CodeChar\"><w:name w:val=\"Code Char\"/>",
+                "<w:rPr><w:rFonts w:ascii=\"Consolas\" w:hAnsi=\"Consolas\" w:cs=\"Consolas\"/><w:color w:val=\"{c}\"/><w:sz w:val=\"20\"/>",
+                "<w:shd w:val=\"clear\" w:color=\"auto\" w:fill=\"{f}\"/></w:rPr></w:style>"
+            ),
+            f = code_fill,
+            c = code_text
+        ));
+        xml.push_str(&format!(
+            "<w:style w:type=\"character\" w:styleId=\"Hyperlink\"><w:name w:val=\"Hyperlink\"/><w:rPr><w:color w:val=\"{}\"/><w:u w:val=\"single\"/></w:rPr></w:style>",
+            link
+        ));
+        xml.push_str(&format!(
+            concat!(
+                "<w:style w:type=\"character\" w:customStyle=\"1\" w:styleId=\"TagChar\"><w:name w:val=\"Tag\"/>",
+                "<w:rPr><w:color w:val=\"{}\"/><w:sz w:val=\"20\"/><w:shd w:val=\"clear\" w:color=\"auto\" w:fill=\"{}\"/></w:rPr></w:style>"
+            ),
+            tag_text, tag_fill
+        ));
+        xml.push_str("<w:style w:type=\"paragraph\" w:styleId=\"ListParagraph\"><w:name w:val=\"List Paragraph\"/><w:basedOn w:val=\"Normal\"/><w:qFormat/><w:pPr><w:contextualSpacing/></w:pPr></w:style>");
+        xml.push_str("<w:style w:type=\"table\" w:default=\"1\" w:styleId=\"TableNormal\"><w:name w:val=\"Normal Table\"/><w:tblPr><w:tblInd w:w=\"0\" w:type=\"dxa\"/><w:tblCellMar><w:top w:w=\"0\" w:type=\"dxa\"/><w:left w:w=\"108\" w:type=\"dxa\"/><w:bottom w:w=\"0\" w:type=\"dxa\"/><w:right w:w=\"108\" w:type=\"dxa\"/></w:tblCellMar></w:tblPr></w:style>");
+        xml.push_str("<w:style w:type=\"table\" w:styleId=\"TableGrid\"><w:name w:val=\"Table Grid\"/><w:basedOn w:val=\"TableNormal\"/><w:tblPr><w:tblCellMar><w:top w:w=\"80\" w:type=\"dxa\"/><w:left w:w=\"115\" w:type=\"dxa\"/><w:bottom w:w=\"80\" w:type=\"dxa\"/><w:right w:w=\"115\" w:type=\"dxa\"/></w:tblCellMar></w:tblPr></w:style>");
+        xml.push_str("</w:styles>");
+        xml
+    }
+
+    fn numbering_xml(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n");
+        xml.push_str(&format!("<w:numbering xmlns:w=\"{}\">", NS_W));
+        // 0: madde işaretli, 1: numaralı
+        for (abstract_id, ordered) in [(0, false), (1, true)] {
+            xml.push_str(&format!("<w:abstractNum w:abstractNumId=\"{}\"><w:multiLevelType w:val=\"hybridMultilevel\"/>", abstract_id));
+            for level in 0..9u32 {
+                let (format, text) = if ordered {
+                    ("decimal", format!("%{}.", level + 1))
+                } else {
+                    ("bullet", ["•", "◦", "▪"][level as usize % 3].to_string())
+                };
+                xml.push_str(&format!(
+                    "<w:lvl w:ilvl=\"{l}\"><w:start w:val=\"1\"/><w:numFmt w:val=\"{f}\"/><w:lvlText w:val=\"{t}\"/><w:lvlJc w:val=\"left\"/><w:pPr><w:ind w:left=\"{i}\" w:hanging=\"360\"/></w:pPr></w:lvl>",
+                    l = level,
+                    f = format,
+                    t = text,
+                    i = LIST_INDENT * (level + 1)
+                ));
+            }
+            xml.push_str("</w:abstractNum>");
+        }
+        // Her liste ayrı bir numaralandırma örneği: numaralı listeler kendi başlangıcından başlar
+        for (i, list) in self.lists.iter().enumerate() {
+            xml.push_str(&format!("<w:num w:numId=\"{}\"><w:abstractNumId w:val=\"{}\"/>", i + 1, if list.ordered { 1 } else { 0 }));
+            if list.ordered {
+                xml.push_str(&format!(
+                    "<w:lvlOverride w:ilvl=\"{}\"><w:startOverride w:val=\"{}\"/></w:lvlOverride>",
+                    list.level, list.start
+                ));
+            }
+            xml.push_str("</w:num>");
+        }
+        xml.push_str("</w:numbering>");
+        xml
+    }
+
+    fn finish(self, output_path: &Path) -> Result<(), String> {
+        let file = std::fs::File::create(output_path).map_err(|e| e.to_string())?;
+        let mut zip = zip::ZipWriter::new(file);
+        let opts = zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+        let mut content_types = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<Types xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\">",
+            "<Default Extension=\"rels\" ContentType=\"application/vnd.openxmlformats-package.relationships+xml\"/>",
+            "<Default Extension=\"xml\" ContentType=\"application/xml\"/>"
+        ));
+        for format in [ImageFormat::Png, ImageFormat::Jpeg, ImageFormat::Gif] {
+            content_types.push_str(&format!("<Default Extension=\"{}\" ContentType=\"{}\"/>", format.extension(), format.mime()));
+        }
+        content_types.push_str(concat!(
+            "<Override PartName=\"/word/document.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml\"/>",
+            "<Override PartName=\"/word/styles.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml\"/>",
+            "<Override PartName=\"/word/numbering.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.wordprocessingml.numbering+xml\"/>",
+            "<Override PartName=\"/word/settings.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.wordprocessingml.settings+xml\"/>",
+            "<Override PartName=\"/docProps/core.xml\" ContentType=\"application/vnd.openxmlformats-package.core-properties+xml\"/>",
+            "<Override PartName=\"/docProps/app.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.extended-properties+xml\"/>",
+            "</Types>"
+        ));
+        zip.start_file("[Content_Types].xml", opts).map_err(|e| e.to_string())?;
+        zip.write_all(content_types.as_bytes()).map_err(|e| e.to_string())?;
+
+        zip.add_directory("_rels/", opts).map_err(|e| e.to_string())?;
+        zip.start_file("_rels/.rels", opts).map_err(|e| e.to_string())?;
+        zip.write_all(br#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/>
+  <Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties" Target="docProps/core.xml"/>
+  <Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/extended-properties" Target="docProps/app.xml"/>
+</Relationships>"#).map_err(|e| e.to_string())?;
+
+        zip.add_directory("docProps/", opts).map_err(|e| e.to_string())?;
+        zip.start_file("docProps/app.xml", opts).map_err(|e| e.to_string())?;
+        zip.write_all(br#"<?xml version="1.0" encoding="UTF-8"?>
+<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/extended-properties" xmlns:vt="http://schemas.openxmlformats.org/officeDocument/2006/docPropsVTypes">
+  <Application>PigNote</Application>
+  <DocSecurity>0</DocSecurity>
+  <ScaleCrop>false</ScaleCrop>
+  <Company></Company>
+  <LinksUpToDate>false</LinksUpToDate>
+  <SharedDoc>false</SharedDoc>
+  <HyperlinksChanged>false</HyperlinksChanged>
+  <AppVersion>16.0000</AppVersion>
+</Properties>"#).map_err(|e| e.to_string())?;
+
+        let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        let metadata = self.options.metadata.cloned().unwrap_or_default();
+        let created = metadata.date_time().map(|d| d.format("%Y-%m-%dT%H:%M:%SZ").to_string()).unwrap_or_else(|| now.clone());
+        let mut properties = String::new();
+        if let Some(title) = &metadata.title {
+            properties.push_str(&format!("  <dc:title>{}</dc:title>\n", xml_escape(title)));
+        }
+        if let Some(subject) = &metadata.subject {
+            properties.push_str(&format!("  <dc:subject>{}</dc:subject>\n", xml_escape(subject)));
+        }
+        if !metadata.tags.is_empty() {
+            properties.push_str(&format!("  <cp:keywords>{}</cp:keywords>\n", xml_escape(&metadata.tags.join(", "))));
+        }
+        let core_xml = format!(
+            concat!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+                "<cp:coreProperties xmlns:cp=\"http://schemas.openxmlformats.org/package/2006/metadata/core-properties\" ",
+                "xmlns:dc=\"http://purl.org/dc/elements/1.1/\" xmlns:dcterms=\"http://purl.org/dc/terms/\" ",
+                "xmlns:dcmitype=\"http://purl.org/dc/dcmitype/\" xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\">\n",
+                "{properties}",
+                "  <dcterms:created xsi:type=\"dcterms:W3CDTF\">{created}</dcterms:created>\n",
+                "  <dcterms:modified xsi:type=\"dcterms:W3CDTF\">{now}</dcterms:modified>\n",
+                "  <dc:creator>{creator}</dc:creator>\n  <cp:lastModifiedBy>PigNote</cp:lastModifiedBy>\n</cp:coreProperties>"
+            ),
+            properties = properties,
+            created = created,
+            now = now,
+            creator = xml_escape(metadata.author.as_deref().unwrap_or("PigNote"))
+        );
+        zip.start_file("docProps/core.xml", opts).map_err(|e| e.to_string())?;
+        zip.write_all(core_xml.as_bytes()).map_err(|e| e.to_string())?;
+
+        let mut document_rels = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">",
+            "<Relationship Id=\"rId1\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles\" Target=\"styles.xml\"/>",
+            "<Relationship Id=\"rId2\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/numbering\" Target=\"numbering.xml\"/>",
+            "<Relationship Id=\"rId3\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/settings\" Target=\"settings.xml\"/>"
+        ));
+        for rel in &self.relationships {
+            let mode = if rel.external { " TargetMode=\"External\"" } else { "" };
+            document_rels.push_str(&format!(
+                "<Relationship Id=\"{}\" Type=\"{}\" Target=\"{}\"{}/>",
+                rel.id,
+                rel.kind,
+                xml_escape(&rel.target),
+                mode
+            ));
+        }
+        document_rels.push_str("</Relationships>");
+
+        zip.add_directory("word/", opts).map_err(|e| e.to_string())?;
+        zip.add_directory("word/_rels/", opts).map_err(|e| e.to_string())?;
+        zip.start_file("word/_rels/document.xml.rels", opts).map_err(|e| e.to_string())?;
+        zip.write_all(document_rels.as_bytes()).map_err(|e| e.to_string())?;
+
+        zip.start_file("word/document.xml", opts).map_err(|e| e.to_string())?;
+        zip.write_all(self.document_xml().as_bytes()).map_err(|e| e.to_string())?;
+        zip.start_file("word/styles.xml", opts).map_err(|e| e.to_string())?;
+        zip.write_all(self.styles_xml().as_bytes()).map_err(|e| e.to_string())?;
+        zip.start_file("word/numbering.xml", opts).map_err(|e| e.to_string())?;
+        zip.write_all(self.numbering_xml().as_bytes()).map_err(|e| e.to_string())?;
+
+        // Koyu temada sayfa arka planının Word'de görünmesi için displayBackgroundShape gerekir
+        let settings = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<w:settings xmlns:w=\"{}\">{}<w:defaultTabStop w:val=\"708\"/></w:settings>",
+            NS_W,
+            if self.options.dark { "<w:displayBackgroundShape/>" } else { "" }
+        );
+        zip.start_file("word/settings.xml", opts).map_err(|e| e.to_string())?;
+        zip.write_all(settings.as_bytes()).map_err(|e| e.to_string())?;
+
+        if !self.media.is_empty() {
+            zip.add_directory("word/media/", opts).map_err(|e| e.to_string())?;
+        }
+        for (name, bytes) in &self.media {
+            zip.start_file(format!("word/media/{}", name), opts).map_err(|e| e.to_string())?;
+            zip.write_all(bytes).map_err(|e| e.to_string())?;
+        }
+        zip.finish().map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
+
+fn plain_text<'n>(node: &'n AstNode<'n>) -> String {
+    let mut text = String::new();
+    for descendant in node.descendants() {
+        match &descendant.data.borrow().value {
+            NodeValue::Text(t) => text.push_str(t),
+            NodeValue::Code(code) => text.push_str(&code.literal),
+            NodeValue::SoftBreak | NodeValue::LineBreak => text.push(' '),
+            _ => {}
+        }
+    }
+    text
+}
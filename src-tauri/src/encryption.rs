@@ -0,0 +1,495 @@
+// Parolayla şifrelenen notlar ("not.md.enc"). Anahtar parola ve dosyaya özgü tuzdan Argon2id ile türetilir,
+// içerik XChaCha20-Poly1305 ile şifrelenip doğrulanır. Parola yalnızca bellekte tutulur ve bir süre
+// kullanılmazsa silinir (otomatik kilit); şifreli notlar arama dizinine ve sürüm geçmişine girmez.
+use argon2::{Algorithm, Argon2, Params};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, KeyInit, OsRng, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+use zeroize::Zeroizing;
+
+use crate::history::{self, HistoryState};
+use crate::safe_write::{self, WriteError, WriteOptions, WriteResult};
+use crate::search;
+use crate::search_index::SearchIndexState;
+use crate::workspace::WorkspaceState;
+
+pub const ENCRYPTED_EXTENSION: &str = "enc";
+pub const LOCKED_EVENT: &str = "notes-locked";
+
+const MAGIC: &[u8] = b"PIGNOTE-ENC";
+const FORMAT_VERSION: u8 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+// Sihirli dizi, sürüm, üç KDF parametresi, tuz ve nonce; başlığın tamamı ek doğrulanan veri olarak kullanılır
+const HEADER_LEN: usize = MAGIC.len() + 1 + 12 + SALT_LEN + NONCE_LEN;
+// Argon2id: 64 MiB bellek, 3 tur. Değerler dosyaya yazıldığı için sonradan değiştirilebilir.
+const KDF_MEMORY_KIB: u32 = 64 * 1024;
+const KDF_ITERATIONS: u32 = 3;
+const KDF_LANES: u32 = 1;
+// Kurcalanmış bir başlık anahtar türetmede aşırı bellek ya da süre istemesin
+const KDF_MAX_MEMORY_KIB: u32 = 1024 * 1024;
+const KDF_MAX_ITERATIONS: u32 = 64;
+const DEFAULT_TIMEOUT_MINUTES: u64 = 10;
+const AUTO_LOCK_CHECK: Duration = Duration::from_secs(15);
+
+// Ön yüz kilitli notu ve yanlış parolayı `kind` alanından ayırt edip parola sorabilir
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CryptoError {
+    Locked { message: String },
+    WrongPassphrase { message: String },
+    Invalid { path: String, message: String },
+}
+
+impl fmt::Display for CryptoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CryptoError::Locked { message } | CryptoError::WrongPassphrase { message } | CryptoError::Invalid { message, .. } => {
+                write!(f, "{}", message)
+            }
+        }
+    }
+}
+
+impl From<CryptoError> for String {
+    fn from(e: CryptoError) -> String {
+        e.to_string()
+    }
+}
+
+fn locked() -> CryptoError {
+    CryptoError::Locked { message: "Şifreli notlar kilitli; önce parolayla kilidi açın".to_string() }
+}
+
+fn invalid(path: &Path, message: &str) -> CryptoError {
+    CryptoError::Invalid { path: path.to_string_lossy().to_string(), message: message.to_string() }
+}
+
+pub fn is_encrypted(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e.eq_ignore_ascii_case(ENCRYPTED_EXTENSION))
+}
+
+// Anahtar türetme girdisi: dosyadaki parametreler ve tuz
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct KdfParams {
+    memory_kib: u32,
+    iterations: u32,
+    lanes: u32,
+    salt: [u8; SALT_LEN],
+}
+
+impl KdfParams {
+    fn generate() -> Self {
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        KdfParams { memory_kib: KDF_MEMORY_KIB, iterations: KDF_ITERATIONS, lanes: KDF_LANES, salt }
+    }
+
+    fn derive(&self, passphrase: &str) -> Result<Zeroizing<[u8; 32]>, String> {
+        let params = Params::new(self.memory_kib, self.iterations, self.lanes, Some(32)).map_err(|e| format!("Geçersiz anahtar parametreleri: {}", e))?;
+        let mut key = Zeroizing::new([0u8; 32]);
+        Argon2::new(Algorithm::Argon2id, argon2::Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), &self.salt, key.as_mut())
+            .map_err(|e| format!("Anahtar türetilemedi: {}", e))?;
+        Ok(key)
+    }
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+}
+
+struct Header {
+    kdf: KdfParams,
+    nonce: [u8; NONCE_LEN],
+}
+
+impl Header {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN);
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(FORMAT_VERSION);
+        for value in [self.kdf.memory_kib, self.kdf.iterations, self.kdf.lanes] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes.extend_from_slice(&self.kdf.salt);
+        bytes.extend_from_slice(&self.nonce);
+        bytes
+    }
+
+    fn parse(path: &Path, bytes: &[u8]) -> Result<Self, CryptoError> {
+        if bytes.len() < HEADER_LEN || !bytes.starts_with(MAGIC) {
+            return Err(invalid(path, "Dosya bir PigNote şifreli notu değil"));
+        }
+        let version = bytes[MAGIC.len()];
+        if version != FORMAT_VERSION {
+            return Err(invalid(path, &format!("Desteklenmeyen şifreleme sürümü: {}", version)));
+        }
+        let params = MAGIC.len() + 1;
+        let salt = params + 12;
+        let nonce = salt + SALT_LEN;
+        let kdf = KdfParams {
+            memory_kib: u32_at(bytes, params),
+            iterations: u32_at(bytes, params + 4),
+            lanes: u32_at(bytes, params + 8),
+            salt: bytes[salt..nonce].try_into().unwrap(),
+        };
+        if kdf.memory_kib > KDF_MAX_MEMORY_KIB || kdf.iterations > KDF_MAX_ITERATIONS || kdf.lanes > 16 {
+            return Err(invalid(path, "Şifreli notun anahtar parametreleri izin verilen sınırların dışında"));
+        }
+        Ok(Header { kdf, nonce: bytes[nonce..HEADER_LEN].try_into().unwrap() })
+    }
+}
+
+struct Session {
+    passphrase: Zeroizing<String>,
+    // Yazarken kullanılan parametreler; her kayıtta anahtar yeniden türetilmesin diye oturum boyunca aynı
+    write_params: KdfParams,
+    // Okunan notların tuzlarına göre türetilmiş anahtarlar
+    keys: HashMap<KdfParams, Zeroizing<[u8; 32]>>,
+    timeout: Duration,
+    last_used: Instant,
+}
+
+impl Session {
+    fn key(&mut self, params: &KdfParams) -> Result<&Zeroizing<[u8; 32]>, String> {
+        if !self.keys.contains_key(params) {
+            let key = params.derive(&self.passphrase)?;
+            self.keys.insert(params.clone(), key);
+        }
+        Ok(&self.keys[params])
+    }
+
+    fn seal(&mut self, content: &[u8]) -> Result<Vec<u8>, String> {
+        let mut nonce = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce);
+        let header = Header { kdf: self.write_params.clone(), nonce };
+        let mut sealed = header.to_bytes();
+        let cipher = XChaCha20Poly1305::new(self.key(&header.kdf)?.as_ref().into());
+        let ciphertext = cipher
+            .encrypt(XNonce::from_slice(&nonce), Payload { msg: content, aad: &sealed })
+            .map_err(|_| "Not şifrelenemedi".to_string())?;
+        sealed.extend_from_slice(&ciphertext);
+        Ok(sealed)
+    }
+
+    fn open(&mut self, path: &Path, bytes: &[u8]) -> Result<Zeroizing<Vec<u8>>, CryptoError> {
+        let header = Header::parse(path, bytes)?;
+        let key = self.key(&header.kdf).map_err(|message| CryptoError::Invalid { path: path.to_string_lossy().to_string(), message })?;
+        let cipher = XChaCha20Poly1305::new(key.as_ref().into());
+        // Doğrulama etiketi tutmazsa parola yanlıştır ya da dosya bozulmuştur; ikisi ayırt edilemez
+        cipher
+            .decrypt(XNonce::from_slice(&header.nonce), Payload { msg: &bytes[HEADER_LEN..], aad: &bytes[..HEADER_LEN] })
+            .map(Zeroizing::new)
+            .map_err(|_| CryptoError::WrongPassphrase { message: "Parola yanlış ya da şifreli not bozulmuş".to_string() })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptionStatus {
+    pub unlocked: bool,
+    // Kilitlenmeye kalan süre (saniye)
+    pub locks_in: Option<u64>,
+}
+
+#[derive(Default)]
+pub struct EncryptionState {
+    session: Mutex<Option<Session>>,
+}
+
+impl EncryptionState {
+    // Süresi dolan oturum kilitlenir; kullanılan oturumun süresi yeniden başlar
+    fn with_session<T>(&self, f: impl FnOnce(&mut Session) -> Result<T, CryptoError>) -> Result<T, CryptoError> {
+        let mut guard = self.session.lock().unwrap();
+        if guard.as_ref().is_some_and(|s| s.last_used.elapsed() >= s.timeout) {
+            *guard = None;
+        }
+        let session = guard.as_mut().ok_or_else(locked)?;
+        session.last_used = Instant::now();
+        f(session)
+    }
+
+    pub fn encrypt(&self, content: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        self.with_session(|session| session.seal(content).map_err(|message| CryptoError::Invalid { path: String::new(), message }))
+    }
+
+    pub fn decrypt(&self, path: &Path, bytes: &[u8]) -> Result<String, CryptoError> {
+        let plain = self.with_session(|session| session.open(path, bytes))?;
+        String::from_utf8(plain.to_vec()).map_err(|_| invalid(path, "Şifreli not geçerli UTF-8 metin içermiyor"))
+    }
+
+    // Doğrulama dosyası verilirse parola önce onunla denenir; yanlışsa oturum açılmaz
+    fn unlock(&self, passphrase: String, verify: Option<(&Path, &[u8])>, timeout: Duration) -> Result<(), CryptoError> {
+        let passphrase = Zeroizing::new(passphrase);
+        if passphrase.is_empty() {
+            return Err(CryptoError::WrongPassphrase { message: "Parola boş olamaz".to_string() });
+        }
+        let mut session = Session { passphrase, write_params: KdfParams::generate(), keys: HashMap::new(), timeout, last_used: Instant::now() };
+        if let Some((path, bytes)) = verify {
+            session.open(path, bytes)?;
+        }
+        *self.session.lock().unwrap() = Some(session);
+        Ok(())
+    }
+
+    // Oturum açıktıysa true
+    pub fn lock(&self) -> bool {
+        self.session.lock().unwrap().take().is_some()
+    }
+
+    pub fn status(&self) -> EncryptionStatus {
+        let guard = self.session.lock().unwrap();
+        match guard.as_ref() {
+            Some(session) if session.last_used.elapsed() < session.timeout => {
+                EncryptionStatus { unlocked: true, locks_in: Some((session.timeout - session.last_used.elapsed()).as_secs()) }
+            }
+            _ => EncryptionStatus { unlocked: false, locks_in: None },
+        }
+    }
+
+    fn expire(&self) -> bool {
+        let mut guard = self.session.lock().unwrap();
+        if guard.as_ref().is_some_and(|s| s.last_used.elapsed() >= s.timeout) {
+            *guard = None;
+            return true;
+        }
+        false
+    }
+
+    // Parola, not açılmasa da süresi dolunca bellekten silinir; ön yüz açık şifreli notu kapatabilsin diye olay gönderilir
+    pub fn spawn_auto_lock(app: AppHandle) {
+        std::thread::spawn(move || loop {
+            std::thread::sleep(AUTO_LOCK_CHECK);
+            if app.state::<EncryptionState>().expire() {
+                log::info!("Şifreli notlar otomatik olarak kilitlendi");
+                if let Err(e) = app.emit(LOCKED_EVENT, ()) {
+                    log::warn!("Kilit olayı gönderilemedi: {}", e);
+                }
+            }
+        });
+    }
+}
+
+// Editörden gelen içeriği yazan komutlar (kaydet, Farklı Kaydet) buradan geçer: şifreli not yalnızca şifreli
+// hâliyle diske yazılır, arama dizinine ve sürüm geçmişine girmez; parola yoksa kilit hatası döner
+pub fn write_content(encryption: &EncryptionState, history: &HistoryState, index: &SearchIndexState, path: &Path, content: &str, options: &WriteOptions) -> Result<WriteResult, WriteError> {
+    if is_encrypted(path) {
+        let sealed = encryption.encrypt(content.as_bytes()).map_err(|e| WriteError::Locked { path: path.to_string_lossy().to_string(), message: e.to_string() })?;
+        return safe_write::write_atomic(path, &sealed, options);
+    }
+    history::write_note(history, index, path, content.as_bytes(), options)
+}
+
+fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(ENCRYPTED_EXTENSION);
+    path.with_file_name(name)
+}
+
+// Parola doğrulaması için çalışma alanındaki ilk şifreli not; çöp kutusu gibi yok sayılan klasörler ve
+// sembolik bağlar atlanır
+fn find_encrypted_note(root: &Path) -> Option<PathBuf> {
+    let ignore = search::build_ignore_set(&[]).ok()?;
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else { continue };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if ignore.is_match(path.strip_prefix(root).unwrap_or(&path)) {
+                continue;
+            }
+            let Ok(file_type) = entry.file_type() else { continue };
+            if file_type.is_dir() {
+                stack.push(path);
+            } else if file_type.is_file() && is_encrypted(&path) {
+                return Some(path);
+            }
+        }
+    }
+    None
+}
+
+// Parolayı belleğe alır; `path` verilirse parola o notla, verilmezse çalışma alanındaki bir şifreli notla
+// doğrulanır. Çalışma alanında hiç şifreli not yoksa parola yeni notlar için belirlenmiş olur.
+#[tauri::command]
+pub async fn unlock_notes(workspace: State<'_, WorkspaceState>, encryption: State<'_, EncryptionState>, passphrase: String, path: Option<String>, timeout_minutes: Option<u64>) -> Result<EncryptionStatus, CryptoError> {
+    let timeout = Duration::from_secs(timeout_minutes.unwrap_or(DEFAULT_TIMEOUT_MINUTES).max(1) * 60);
+    let path = match path {
+        Some(path) => Some(workspace.check(&path).map_err(|e| invalid(Path::new(&path), &e.to_string()))?),
+        None => workspace.root().and_then(|root| find_encrypted_note(&root)),
+    };
+    let verify = match path {
+        Some(path) => {
+            let bytes = fs::read(&path).map_err(|e| invalid(&path, &e.to_string()))?;
+            Some((path, bytes))
+        }
+        None => None,
+    };
+    encryption.unlock(passphrase, verify.as_ref().map(|(path, bytes)| (path.as_path(), bytes.as_slice())), timeout)?;
+    Ok(encryption.status())
+}
+
+#[tauri::command]
+pub async fn lock_notes(app: AppHandle, encryption: State<'_, EncryptionState>) -> Result<(), String> {
+    if encryption.lock() {
+        let _ = app.emit(LOCKED_EVENT, ());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn encryption_status(encryption: State<'_, EncryptionState>) -> Result<EncryptionStatus, String> {
+    Ok(encryption.status())
+}
+
+// Notu "not.md.enc" olarak şifreler ve düz metin hâlini siler; düz metin sürüm geçmişi de temizlenir
+#[tauri::command]
+pub async fn encrypt_note(index: State<'_, SearchIndexState>, workspace: State<'_, WorkspaceState>, history: State<'_, HistoryState>, encryption: State<'_, EncryptionState>, path: String) -> Result<String, String> {
+    let path = workspace.check_entry(&path)?;
+    if is_encrypted(&path) || !search::is_note_file(&path) {
+        return Err("Yalnızca .md, .markdown ve .txt notları şifrelenebilir".to_string());
+    }
+    let target = workspace.check_entry(encrypted_path(&path))?;
+    if target.exists() {
+        return Err("Bu isimde şifreli bir not zaten mevcut".to_string());
+    }
+    let content = Zeroizing::new(fs::read(&path).map_err(|e| e.to_string())?);
+    let sealed = encryption.encrypt(&content)?;
+    safe_write::write_atomic(&target, &sealed, &WriteOptions::default())?;
+    fs::remove_file(&path).map_err(|e| format!("Şifreli not oluşturuldu ancak düz metin silinemedi: {}", e))?;
+    // Kaydederken alınan yedek de düz metindir
+    let backup = safe_write::backup_path(&path);
+    if let Err(e) = fs::remove_file(&backup) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            return Err(format!("Şifreli not oluşturuldu ancak düz metin yedeği silinemedi ({}): {}", backup.display(), e));
+        }
+    }
+    index.path_removed(&path);
+    history.forget(&path);
+    Ok(target.to_string_lossy().to_string())
+}
+
+// Şifrelemeyi kaldırır: "not.md.enc" yeniden "not.md" olur
+#[tauri::command]
+pub async fn decrypt_note(index: State<'_, SearchIndexState>, workspace: State<'_, WorkspaceState>, history: State<'_, HistoryState>, encryption: State<'_, EncryptionState>, path: String) -> Result<String, String> {
+    let path = workspace.check_entry(&path)?;
+    if !is_encrypted(&path) {
+        return Err("Not şifreli değil".to_string());
+    }
+    let target = workspace.check_entry(path.with_extension(""))?;
+    if target.exists() {
+        return Err("Bu isimde bir not zaten mevcut".to_string());
+    }
+    let bytes = fs::read(&path).map_err(|e| e.to_string())?;
+    let content = encryption.decrypt(&path, &bytes)?;
+    history::write_note(&history, &index, &target, content.as_bytes(), &WriteOptions::default())?;
+    fs::remove_file(&path).map_err(|e| e.to_string())?;
+    Ok(target.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Testlerde Argon2'nin bellek ve süre maliyeti düşük tutulur; biçim aynıdır
+    fn cheap_params() -> KdfParams {
+        KdfParams { memory_kib: 8, iterations: 1, lanes: 1, ..KdfParams::generate() }
+    }
+
+    fn session(passphrase: &str) -> Session {
+        Session {
+            passphrase: Zeroizing::new(passphrase.to_string()),
+            write_params: cheap_params(),
+            keys: HashMap::new(),
+            timeout: Duration::from_secs(60),
+            last_used: Instant::now(),
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("pignote_encryption_{}_{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn wrong_passphrase<T>(result: Result<T, CryptoError>) -> bool {
+        matches!(result, Err(CryptoError::WrongPassphrase { .. }))
+    }
+
+    #[test]
+    fn seal_and_open_round_trip() {
+        let path = Path::new("not.md.enc");
+        let sealed = session("parola").seal("gizli not 🐷".as_bytes()).unwrap();
+        assert!(sealed.starts_with(MAGIC));
+        assert!(!sealed.windows(4).any(|w| w == b"not "));
+        // Yeni bir oturum (yeni yazma tuzu) dosyadaki tuzla aynı anahtarı türetir
+        assert_eq!(session("parola").open(path, &sealed).unwrap().as_slice(), "gizli not 🐷".as_bytes());
+    }
+
+    #[test]
+    fn wrong_passphrase_is_rejected() {
+        let sealed = session("parola").seal(b"icerik").unwrap();
+        assert!(wrong_passphrase(session("Parola").open(Path::new("not.md.enc"), &sealed)));
+    }
+
+    #[test]
+    fn tampering_is_detected() {
+        let path = Path::new("not.md.enc");
+        let sealed = session("parola").seal(b"icerik").unwrap();
+        let salt = MAGIC.len() + 1 + 12;
+        let nonce = salt + SALT_LEN;
+        for offset in [salt, nonce, HEADER_LEN, sealed.len() - 1] {
+            let mut tampered = sealed.clone();
+            tampered[offset] ^= 1;
+            assert!(wrong_passphrase(session("parola").open(path, &tampered)), "{offset}");
+        }
+
+        let mut tampered = sealed.clone();
+        tampered[0] ^= 1;
+        assert!(matches!(session("parola").open(path, &tampered), Err(CryptoError::Invalid { .. })));
+        let mut tampered = sealed.clone();
+        tampered[MAGIC.len()] = FORMAT_VERSION + 1;
+        assert!(matches!(session("parola").open(path, &tampered), Err(CryptoError::Invalid { .. })));
+        assert!(matches!(session("parola").open(path, &sealed[..HEADER_LEN - 1]), Err(CryptoError::Invalid { .. })));
+        // Sınırların dışındaki anahtar parametreleri türetmeye geçmeden reddedilir
+        let mut tampered = sealed.clone();
+        tampered[MAGIC.len() + 1..MAGIC.len() + 5].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(session("parola").open(path, &tampered), Err(CryptoError::Invalid { .. })));
+    }
+
+    #[test]
+    fn unlock_fails_when_verification_note_does_not_open() {
+        let path = Path::new("not.md.enc");
+        let sealed = session("parola").seal(b"icerik").unwrap();
+        let state = EncryptionState::default();
+        assert!(wrong_passphrase(state.unlock("yanlis".to_string(), Some((path, &sealed)), Duration::from_secs(60))));
+        assert!(!state.status().unlocked);
+        assert!(state.unlock("parola".to_string(), Some((path, &sealed)), Duration::from_secs(60)).is_ok());
+        assert!(state.status().unlocked);
+    }
+
+    #[test]
+    fn finds_encrypted_note_outside_ignored_folders() {
+        let dir = temp_dir("find");
+        fs::create_dir_all(dir.join(".pignote-trash")).unwrap();
+        fs::create_dir_all(dir.join("alt")).unwrap();
+        fs::write(dir.join("a.md"), "a").unwrap();
+        fs::write(dir.join(".pignote-trash/eski.md.enc"), "x").unwrap();
+        assert_eq!(find_encrypted_note(&dir), None);
+
+        fs::write(dir.join("alt/b.md.enc"), "x").unwrap();
+        assert_eq!(find_encrypted_note(&dir), Some(dir.join("alt/b.md.enc")));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
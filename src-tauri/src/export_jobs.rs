@@ -0,0 +1,279 @@
+// Export işleri: her export bir iş kimliği alır ve arka planda çalışır. Aşamalar "export-progress",
+// sonuç "export-finished" olayıyla bildirilir. İşler tek tek iptal edilebilir; aynı anda çalışan iş
+// sayısı sınırlıdır, fazlası sırada bekler.
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::browser::BrowserDiscoveryError;
+use crate::export_path::ExportResult;
+
+pub const EXPORT_PROGRESS_EVENT: &str = "export-progress";
+pub const EXPORT_FINISHED_EVENT: &str = "export-finished";
+
+const DEFAULT_PARALLEL_LIMIT: usize = 2;
+const MAX_PARALLEL_LIMIT: usize = 8;
+// Tarayıcı süreci beklenirken iptal isteğine bakılma sıklığı
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+const CANCELLED: &str = "İşlem iptal edildi";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Pdf,
+    Html,
+    Docx,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportStage {
+    // Boş yer bekleniyor
+    Queued,
+    // Markdown → HTML / Word yapıları
+    Convert,
+    InlineImages,
+    // Tarayıcı ya da yerleşik motorla PDF, HTML/DOCX için dosya yazımı
+    Render,
+    // PDF kırpma, geçici dosyaların silinmesi
+    PostProcess,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportProgress {
+    pub job_id: u64,
+    pub format: ExportFormat,
+    pub stage: ExportStage,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportFinished {
+    pub job_id: u64,
+    pub format: ExportFormat,
+    pub result: Option<ExportResult>,
+    pub error: Option<String>,
+    // Tarayıcı bulunamadıysa ön yüz `kind` alanına göre yönlendirme yapabilsin
+    pub browser_error: Option<BrowserDiscoveryError>,
+    pub cancelled: bool,
+}
+
+// İş hatası: gösterilecek mesaj ve varsa yapısal ayrıntısı
+#[derive(Debug, Clone)]
+pub struct ExportError {
+    pub message: String,
+    pub browser: Option<BrowserDiscoveryError>,
+}
+
+impl From<String> for ExportError {
+    fn from(message: String) -> Self {
+        ExportError { message, browser: None }
+    }
+}
+
+impl From<BrowserDiscoveryError> for ExportError {
+    fn from(error: BrowserDiscoveryError) -> Self {
+        ExportError { message: error.to_string(), browser: Some(error) }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportJobInfo {
+    pub job_id: u64,
+    pub format: ExportFormat,
+    pub stage: ExportStage,
+}
+
+pub struct Job {
+    pub id: u64,
+    pub format: ExportFormat,
+    cancelled: AtomicBool,
+    stage: Mutex<ExportStage>,
+    app: AppHandle,
+}
+
+impl Job {
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    // Uzun süren dizgi işlemleri (yerleşik PDF, DOCX) bu bayrağa bakar
+    pub fn cancel_flag(&self) -> &AtomicBool {
+        &self.cancelled
+    }
+
+    // İptal edildiyse hata döner; uzun adımların arasında çağrılır
+    pub fn check(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            Err(CANCELLED.to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn stage(&self, stage: ExportStage) -> Result<(), String> {
+        self.check()?;
+        *self.stage.lock().unwrap() = stage;
+        let progress = ExportProgress { job_id: self.id, format: self.format, stage };
+        if let Err(e) = self.app.emit(EXPORT_PROGRESS_EVENT, progress) {
+            log::warn!("Export ilerlemesi gönderilemedi: {}", e);
+        }
+        Ok(())
+    }
+
+    // Süreç beklenirken iptal gelirse öldürülür; diğer işlerin süreçlerine dokunulmaz
+    pub fn run(&self, command: &mut Command) -> Result<ExitStatus, String> {
+        self.check()?;
+        let mut child = command.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null()).spawn().map_err(|e| e.to_string())?;
+        loop {
+            if self.is_cancelled() {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(CANCELLED.to_string());
+            }
+            if let Some(status) = child.try_wait().map_err(|e| e.to_string())? {
+                return Ok(status);
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+struct Slots {
+    running: usize,
+    limit: usize,
+}
+
+pub struct ExportJobs {
+    next_id: AtomicU64,
+    jobs: Mutex<HashMap<u64, Arc<Job>>>,
+    slots: Mutex<Slots>,
+    slot_freed: Condvar,
+}
+
+impl Default for ExportJobs {
+    fn default() -> Self {
+        ExportJobs {
+            next_id: AtomicU64::new(1),
+            jobs: Mutex::new(HashMap::new()),
+            slots: Mutex::new(Slots { running: 0, limit: DEFAULT_PARALLEL_LIMIT }),
+            slot_freed: Condvar::new(),
+        }
+    }
+}
+
+impl ExportJobs {
+    // İş kimliği hemen döner; iş boş yer açılınca ayrı bir iş parçacığında çalışır
+    pub fn spawn<F, E>(&self, app: AppHandle, format: ExportFormat, work: F) -> u64
+    where
+        F: FnOnce(&Job) -> Result<ExportResult, E> + Send + 'static,
+        E: Into<ExportError>,
+    {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let job = Arc::new(Job { id, format, cancelled: AtomicBool::new(false), stage: Mutex::new(ExportStage::Queued), app: app.clone() });
+        self.jobs.lock().unwrap().insert(id, job.clone());
+        let _ = job.stage(ExportStage::Queued);
+
+        std::thread::spawn(move || {
+            let jobs = app.state::<ExportJobs>();
+            let outcome = if jobs.acquire(&job) {
+                // Bir işteki panik yeri boşaltmadan iş parçacığını sonlandırmasın
+                let outcome = panic::catch_unwind(AssertUnwindSafe(|| work(&job).map_err(Into::into)))
+                    .unwrap_or_else(|_| Err(ExportError::from("Export sırasında beklenmeyen bir hata oluştu".to_string())));
+                jobs.release();
+                outcome
+            } else {
+                Err(ExportError::from(CANCELLED.to_string()))
+            };
+            jobs.jobs.lock().unwrap().remove(&job.id);
+
+            let cancelled = job.is_cancelled() && outcome.is_err();
+            let finished = match outcome {
+                Ok(result) => ExportFinished { job_id: job.id, format, result: Some(result), error: None, browser_error: None, cancelled },
+                Err(e) => ExportFinished { job_id: job.id, format, result: None, error: Some(e.message), browser_error: e.browser, cancelled },
+            };
+            if let Err(e) = app.emit(EXPORT_FINISHED_EVENT, finished) {
+                log::warn!("Export sonucu gönderilemedi: {}", e);
+            }
+        });
+        id
+    }
+
+    // Sırada beklerken iptal edilen iş için false
+    fn acquire(&self, job: &Job) -> bool {
+        let mut slots = self.slots.lock().unwrap();
+        loop {
+            if job.is_cancelled() {
+                return false;
+            }
+            if slots.running < slots.limit {
+                slots.running += 1;
+                return true;
+            }
+            slots = self.slot_freed.wait_timeout(slots, POLL_INTERVAL).unwrap().0;
+        }
+    }
+
+    fn release(&self) {
+        self.slots.lock().unwrap().running -= 1;
+        self.slot_freed.notify_all();
+    }
+
+    // İş bulunamazsa (bitmiş ya da hiç yok) false
+    pub fn cancel(&self, id: u64) -> bool {
+        match self.jobs.lock().unwrap().get(&id) {
+            Some(job) => {
+                job.cancelled.store(true, Ordering::SeqCst);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn list(&self) -> Vec<ExportJobInfo> {
+        let mut jobs: Vec<ExportJobInfo> = self
+            .jobs
+            .lock()
+            .unwrap()
+            .values()
+            .map(|job| ExportJobInfo { job_id: job.id, format: job.format, stage: *job.stage.lock().unwrap() })
+            .collect();
+        jobs.sort_by_key(|job| job.job_id);
+        jobs
+    }
+
+    pub fn set_limit(&self, limit: usize) -> usize {
+        let limit = limit.clamp(1, MAX_PARALLEL_LIMIT);
+        self.slots.lock().unwrap().limit = limit;
+        self.slot_freed.notify_all();
+        limit
+    }
+}
+
+// Yalnızca verilen iş iptal edilir; diğer işler çalışmaya devam eder
+#[tauri::command]
+pub async fn cancel_export(jobs: State<'_, ExportJobs>, job_id: u64) -> Result<(), String> {
+    if !jobs.cancel(job_id) {
+        log::info!("İptal edilecek export işi bulunamadı: {}", job_id);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_export_jobs(jobs: State<'_, ExportJobs>) -> Result<Vec<ExportJobInfo>, String> {
+    Ok(jobs.list())
+}
+
+// Aynı anda çalışabilecek export sayısı (1-8); uygulanan değer döner
+#[tauri::command]
+pub async fn set_export_limit(jobs: State<'_, ExportJobs>, limit: usize) -> Result<usize, String> {
+    Ok(jobs.set_limit(limit))
+}
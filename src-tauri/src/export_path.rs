@@ -0,0 +1,205 @@
+// Export hedef yolunun çözümlenmesi: açık yol > hedef klasör > Masaüstü > Belgeler > ev dizini
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::workspace;
+
+// Hedefte aynı isimde dosya varsa ne yapılacağı
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverwritePolicy {
+    Overwrite,
+    // "notlar.pdf" varsa "notlar (1).pdf", "notlar (2).pdf"...
+    #[default]
+    AutoSuffix,
+    Fail,
+}
+
+// Ön yüzden gelen isteğe bağlı hedef ayarları
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportDestination {
+    // Tam dosya yolu; verilirse filename ve output_dir yok sayılır
+    pub output_path: Option<String>,
+    pub output_dir: Option<String>,
+    #[serde(default)]
+    pub overwrite: OverwritePolicy,
+}
+
+// Dosyanın hangi kaynaktan seçilen klasöre yazıldığı
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DestinationSource {
+    OutputPath,
+    OutputDir,
+    Desktop,
+    Documents,
+    Home,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportResult {
+    pub path: String,
+    pub directory: String,
+    pub file_name: String,
+    pub source: DestinationSource,
+    // Var olan bir dosyanın üzerine yazıldı mı
+    pub overwritten: bool,
+    // Çakışma nedeniyle isme " (n)" eklendi mi
+    pub renamed: bool,
+    // Dosya yazıldı ama kullanıcının bilmesi gereken sorunlar (ör. okunamayan ön bilgi)
+    pub warnings: Vec<String>,
+}
+
+impl ExportResult {
+    pub fn new(resolved: &ResolvedPath) -> ExportResult {
+        ExportResult {
+            path: resolved.path.to_string_lossy().to_string(),
+            directory: resolved.path.parent().map(|p| p.to_string_lossy().to_string()).unwrap_or_default(),
+            file_name: resolved.path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+            source: resolved.source,
+            overwritten: resolved.overwritten,
+            renamed: resolved.renamed,
+            warnings: Vec::new(),
+        }
+    }
+
+    pub fn with_warnings(mut self, warnings: Vec<String>) -> ExportResult {
+        self.warnings = warnings;
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ResolvedPath {
+    pub path: PathBuf,
+    pub source: DestinationSource,
+    pub overwritten: bool,
+    pub renamed: bool,
+}
+
+// Hedef yolu çözer ve gerekirse klasörü oluşturur. `extension` noktasız verilir ("pdf").
+pub fn resolve(filename: &str, extension: &str, destination: Option<&ExportDestination>) -> Result<ResolvedPath, String> {
+    let destination = destination.cloned().unwrap_or_default();
+
+    let (path, source) = if let Some(output_path) = non_empty(destination.output_path.as_deref()) {
+        let mut path = PathBuf::from(output_path);
+        if path.extension().is_none() {
+            path.set_extension(extension);
+        }
+        (path, DestinationSource::OutputPath)
+    } else {
+        let (dir, source) = match non_empty(destination.output_dir.as_deref()) {
+            Some(dir) => (PathBuf::from(dir), DestinationSource::OutputDir),
+            None => default_directory()?,
+        };
+        (dir.join(file_name(filename, extension)?), source)
+    };
+
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Hedef klasör oluşturulamadı: {}", e))?;
+    }
+
+    if !path.exists() {
+        return Ok(ResolvedPath { path, source, overwritten: false, renamed: false });
+    }
+    if path.is_dir() {
+        return Err(format!("Hedef yol bir klasör: {}", path.to_string_lossy()));
+    }
+    match destination.overwrite {
+        OverwritePolicy::Overwrite => Ok(ResolvedPath { path, source, overwritten: true, renamed: false }),
+        OverwritePolicy::Fail => Err(format!("Bu isimde bir dosya zaten mevcut: {}", path.to_string_lossy())),
+        OverwritePolicy::AutoSuffix => Ok(ResolvedPath { path: next_free_path(&path), source, overwritten: false, renamed: true }),
+    }
+}
+
+// Masaüstü olmayan (ör. başsız Linux) kurulumlarda Belgeler'e, o da yoksa ev dizinine düşer
+fn default_directory() -> Result<(PathBuf, DestinationSource), String> {
+    let candidates = [
+        (dirs::desktop_dir(), DestinationSource::Desktop),
+        (dirs::document_dir(), DestinationSource::Documents),
+        (dirs::home_dir(), DestinationSource::Home),
+    ];
+    candidates
+        .into_iter()
+        .find_map(|(dir, source)| dir.filter(|d| d.is_dir()).map(|d| (d, source)))
+        .ok_or_else(|| "Export için uygun bir klasör bulunamadı (Masaüstü, Belgeler veya ev dizini)".to_string())
+}
+
+// Ad web görünümünden gelir; "../x" ya da mutlak bir yol hedef klasörün dışına yazdıramaz
+fn file_name(filename: &str, extension: &str) -> Result<String, String> {
+    let trimmed = filename.trim();
+    let base = if trimmed.is_empty() { "export" } else { trimmed };
+    workspace::check_name(base)?;
+    let suffix = format!(".{}", extension);
+    if base.to_lowercase().ends_with(&suffix) {
+        Ok(base.to_string())
+    } else {
+        Ok(format!("{}{}", base, suffix))
+    }
+}
+
+fn next_free_path(path: &Path) -> PathBuf {
+    let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let extension = path.extension().map(|e| e.to_string_lossy().to_string());
+    let parent = path.parent().map(Path::to_path_buf).unwrap_or_default();
+    (1..)
+        .map(|n| {
+            let name = match &extension {
+                Some(ext) => format!("{} ({}).{}", stem, n, ext),
+                None => format!("{} ({})", stem, n),
+            };
+            parent.join(name)
+        })
+        .find(|candidate| !candidate.exists())
+        .unwrap()
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("pignote_export_path_{}_{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn destination(dir: &Path) -> ExportDestination {
+        ExportDestination { output_dir: Some(dir.to_string_lossy().to_string()), ..Default::default() }
+    }
+
+    #[test]
+    fn file_name_adds_extension_once() {
+        assert_eq!(file_name("notlar", "pdf").unwrap(), "notlar.pdf");
+        assert_eq!(file_name("notlar.PDF", "pdf").unwrap(), "notlar.PDF");
+        assert_eq!(file_name("  ", "pdf").unwrap(), "export.pdf");
+    }
+
+    #[test]
+    fn file_name_must_be_a_single_component() {
+        for name in ["..", ".", "../../x", "alt/x", "alt\\x", "/etc/passwd", "./x"] {
+            assert!(file_name(name, "pdf").is_err(), "{name}");
+        }
+    }
+
+    #[test]
+    fn resolve_stays_in_output_dir() {
+        let dir = temp_dir("resolve");
+        let target = dir.join("hedef");
+        let destination = destination(&target);
+        assert!(resolve("../kacis", "pdf", Some(&destination)).is_err());
+        assert!(resolve(&dir.join("mutlak").to_string_lossy(), "pdf", Some(&destination)).is_err());
+        assert!(!dir.join("kacis.pdf").exists());
+
+        let resolved = resolve("not", "pdf", Some(&destination)).unwrap();
+        assert_eq!(resolved.path, target.join("not.pdf"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
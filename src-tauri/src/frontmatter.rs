@@ -0,0 +1,151 @@
+// Notun başındaki "---" satırları arasındaki YAML bloğu (Jekyll/Obsidian biçimi). Export'ta gövdeden
+// çıkarılır; başlık, yazar, tarih ve etiketler belge özelliklerine yazılır.
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+use std::fs::File;
+use std::io::Read as _;
+use std::ops::Range;
+use std::path::Path;
+
+use crate::search;
+
+pub const TAG_KEYS: &[&str] = &["tags", "tag"];
+const AUTHOR_KEYS: &[&str] = &["author", "authors"];
+const SUBJECT_KEYS: &[&str] = &["subject", "description", "summary"];
+// "status" bu değerlerden biriyse PDF'e filigran olarak basılır; "published" gibi durumlar basılmaz
+const MARKING_STATUSES: &[&str] = &["draft", "confidential", "taslak", "gizli"];
+// Klasör listelerken yalnızca dosyanın başı okunur
+const READ_LIMIT: u64 = 64 * 1024;
+
+pub struct FrontMatter {
+    // İçerikteki bayt aralıkları: YAML metni ve ardından gelen gövdenin başı
+    pub yaml: Range<usize>,
+    pub body_start: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteMetadata {
+    pub title: Option<String>,
+    // Liste verilmişse virgülle birleştirilir
+    pub author: Option<String>,
+    // YAML'daki gibi metin olarak ("2024-05-01")
+    pub date: Option<String>,
+    pub tags: Vec<String>,
+    // "subject", "description" ya da "summary"; PDF'te konu (Subject) olarak yazılır
+    pub subject: Option<String>,
+    // "watermark" anahtarı ya da işaret niteliğindeki "status" değeri (büyük harfle)
+    pub watermark: Option<String>,
+}
+
+impl NoteMetadata {
+    pub fn from_mapping(mapping: &Mapping) -> Self {
+        let text = |key: &str| mapping.get(key).and_then(scalar).map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+        let author = AUTHOR_KEYS.iter().find_map(|key| mapping.get(*key)).and_then(|value| match value {
+            Value::Sequence(items) => Some(items.iter().filter_map(scalar).collect::<Vec<_>>().join(", ")).filter(|s| !s.is_empty()),
+            other => scalar(other),
+        });
+        let watermark = text("watermark").or_else(|| {
+            text("status").filter(|status| MARKING_STATUSES.contains(&status.to_lowercase().as_str())).map(|status| status.to_uppercase())
+        });
+        let subject = SUBJECT_KEYS.iter().find_map(|key| text(key));
+        NoteMetadata { title: text("title"), author, date: text("date"), tags: tag_list(mapping), subject, watermark }
+    }
+
+    // YYYY-MM-DD ile başlayan tarihler W3CDTF/ISO zaman damgasına çevrilir
+    pub fn date_time(&self) -> Option<chrono::NaiveDateTime> {
+        let date = self.date.as_deref()?;
+        chrono::NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M:%S")
+            .or_else(|_| chrono::NaiveDateTime::parse_from_str(date, "%Y-%m-%dT%H:%M:%S"))
+            .ok()
+            .or_else(|| chrono::NaiveDate::parse_from_str(date.get(..10)?, "%Y-%m-%d").ok()?.and_hms_opt(0, 0, 0))
+    }
+}
+
+fn is_delimiter(line: &str, closing: bool) -> bool {
+    let line = line.trim_end();
+    line == "---" || (closing && line == "...")
+}
+
+// Kapanış satırı yoksa blok yok sayılır (ör. yatay çizgiyle başlayan not)
+pub fn split(content: &str) -> Option<FrontMatter> {
+    let start = if content.starts_with('\u{feff}') { '\u{feff}'.len_utf8() } else { 0 };
+    let mut lines = content[start..].split_inclusive('\n');
+    let first = lines.next()?;
+    if !is_delimiter(first, false) {
+        return None;
+    }
+    let yaml_start = start + first.len();
+    let mut offset = yaml_start;
+    for line in lines {
+        if is_delimiter(line, true) {
+            return Some(FrontMatter { yaml: yaml_start..offset, body_start: offset + line.len() });
+        }
+        offset += line.len();
+    }
+    None
+}
+
+// Ön bilgisi olmayan notlar olduğu gibi döner
+pub fn body(content: &str) -> &str {
+    match split(content) {
+        Some(front) => &content[front.body_start..],
+        None => content,
+    }
+}
+
+// Ön bilgi yoksa ya da YAML geçersizse None
+pub fn metadata(content: &str) -> Option<NoteMetadata> {
+    // Listeleme her notta buradan geçer; bozuk ön bilgi uyarı yağdırmadan yok sayılır
+    try_metadata(content).map_err(|e| log::debug!("Ön bilgi (front matter) okunamadı: {}", e)).ok()?
+}
+
+// Export gibi kullanıcıya sonucu gösteren yerler için: geçersiz YAML hatası çağırana döner
+pub fn try_metadata(content: &str) -> Result<Option<NoteMetadata>, serde_yaml::Error> {
+    let Some(front) = split(content) else {
+        return Ok(None);
+    };
+    Ok(parse(&content[front.yaml])?.map(|mapping| NoteMetadata::from_mapping(&mapping)))
+}
+
+// Not dosyası değilse, okunamıyorsa ya da ön bilgisi yoksa None
+pub fn read_metadata(path: &Path) -> Option<NoteMetadata> {
+    if !search::is_note_file(path) {
+        return None;
+    }
+    let mut head = Vec::new();
+    File::open(path).ok()?.take(READ_LIMIT).read_to_end(&mut head).ok()?;
+    metadata(&String::from_utf8_lossy(&head))
+}
+
+// Eşleme olmayan kök için None; geçersiz YAML hatası çağırana bırakılır
+pub fn parse(yaml: &str) -> Result<Option<Mapping>, serde_yaml::Error> {
+    match serde_yaml::from_str::<Value>(yaml)? {
+        Value::Mapping(mapping) => Ok(Some(mapping)),
+        _ => Ok(None),
+    }
+}
+
+// "tags: [a, b]", "tags: a, b" ya da alt alta "- a" biçimleri; "#" öneki atılır
+fn tag_list(mapping: &Mapping) -> Vec<String> {
+    let Some(value) = TAG_KEYS.iter().find_map(|key| mapping.get(*key)) else { return Vec::new() };
+    let items: Vec<String> = match value {
+        Value::Sequence(items) => items.iter().filter_map(scalar).collect(),
+        Value::String(text) => text.split([',', ' ']).map(str::to_string).collect(),
+        other => scalar(other).into_iter().collect(),
+    };
+    items
+        .into_iter()
+        .map(|item| item.trim().trim_start_matches('#').to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(flag) => Some(flag.to_string()),
+        _ => None,
+    }
+}
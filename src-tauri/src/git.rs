@@ -0,0 +1,404 @@
+// Not klasörleri için yerel git işlemleri: dosya durumu, hazırla/geri al, commit, dosya geçmişi ve
+// HEAD'e göre fark. libgit2 ağ özellikleri olmadan derlenir; push/pull/fetch yoktur.
+use git2::{Delta, DiffOptions, ErrorCode, IndexAddOption, Patch, Repository, Sort, Status, StatusOptions};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+use crate::workspace::WorkspaceState;
+
+const DEFAULT_LOG_LIMIT: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Change {
+    Added,
+    Modified,
+    Deleted,
+    Renamed,
+    Typechange,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileStatus {
+    // Mutlak yol (gezgindeki FileInfo.path ile eşleştirmek için) ve depo köküne göre "/" ile ayrılmış yol
+    pub path: String,
+    pub relative_path: String,
+    // Hazırlanmış (index) ve hazırlanmamış (çalışma kopyası) değişiklik
+    pub staged: Option<Change>,
+    pub unstaged: Option<Change>,
+    pub untracked: bool,
+    pub conflicted: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitStatus {
+    pub repository: String,
+    // Ayrık HEAD'de None
+    pub branch: Option<String>,
+    pub files: Vec<FileStatus>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitInfo {
+    pub id: String,
+    pub short_id: String,
+    pub summary: String,
+    pub message: String,
+    pub author: String,
+    pub email: String,
+    // RFC 3339
+    pub time: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffLine {
+    // '+', '-' ya da ' '
+    pub origin: char,
+    pub content: String,
+    pub old_line: Option<u32>,
+    pub new_line: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffHunk {
+    pub header: String,
+    pub old_start: u32,
+    pub old_lines: u32,
+    pub new_start: u32,
+    pub new_lines: u32,
+    pub lines: Vec<DiffLine>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileDiff {
+    pub path: String,
+    // Değişiklik yoksa None
+    pub change: Option<Change>,
+    pub binary: bool,
+    pub additions: usize,
+    pub deletions: usize,
+    pub hunks: Vec<DiffHunk>,
+}
+
+pub(crate) fn git_error(e: git2::Error) -> String {
+    format!("Git hatası: {}", e.message())
+}
+
+struct Repo {
+    repo: Repository,
+    // Kanonik çalışma kopyası kökü
+    workdir: PathBuf,
+}
+
+impl Repo {
+    // Yolun içinde bulunduğu depo; depo yoksa None
+    fn discover(path: &Path) -> Result<Option<Self>, String> {
+        let start = if path.is_dir() { path } else { path.parent().unwrap_or(path) };
+        let repo = match Repository::discover(start) {
+            Ok(repo) => repo,
+            Err(e) if e.code() == ErrorCode::NotFound => return Ok(None),
+            Err(e) => return Err(git_error(e)),
+        };
+        let workdir = repo.workdir().ok_or("Çalışma kopyası olmayan (bare) depolar desteklenmiyor")?;
+        let workdir = dunce::canonicalize(workdir).map_err(|e| e.to_string())?;
+        Ok(Some(Repo { repo, workdir }))
+    }
+
+    fn open(path: &Path) -> Result<Self, String> {
+        Repo::discover(path)?.ok_or_else(|| "Klasör bir git deposunun içinde değil".to_string())
+    }
+
+    // libgit2 yolları depo köküne göre ve "/" ile ister
+    fn relative(&self, path: &Path) -> Result<String, String> {
+        let relative = path.strip_prefix(&self.workdir).map_err(|_| format!("'{}' bu deponun dışında", path.display()))?;
+        Ok(relative.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/"))
+    }
+
+    fn absolute(&self, relative: &str) -> String {
+        let mut path = self.workdir.clone();
+        path.extend(relative.split('/'));
+        path.to_string_lossy().to_string()
+    }
+
+    fn branch(&self) -> Option<String> {
+        match self.repo.head() {
+            Ok(head) if head.is_branch() => head.shorthand().map(str::to_string),
+            Ok(_) => None,
+            // Henüz commit'i olmayan dal: HEAD yine de dalı gösterir
+            Err(_) => self
+                .repo
+                .find_reference("HEAD")
+                .ok()?
+                .symbolic_target()
+                .map(|target| target.trim_start_matches("refs/heads/").to_string()),
+        }
+    }
+
+    fn head_commit(&self) -> Result<Option<git2::Commit<'_>>, String> {
+        match self.repo.head() {
+            Ok(head) => head.peel_to_commit().map(Some).map_err(git_error),
+            Err(e) if matches!(e.code(), ErrorCode::UnbornBranch | ErrorCode::NotFound) => Ok(None),
+            Err(e) => Err(git_error(e)),
+        }
+    }
+
+    fn relative_paths(&self, workspace: &WorkspaceState, paths: &[String]) -> Result<Vec<String>, String> {
+        if paths.is_empty() {
+            return Err("Dosya seçilmedi".to_string());
+        }
+        paths.iter().map(|path| self.relative(&workspace.check(path)?)).collect()
+    }
+}
+
+fn staged_change(status: Status) -> Option<Change> {
+    if status.is_index_new() {
+        Some(Change::Added)
+    } else if status.is_index_modified() {
+        Some(Change::Modified)
+    } else if status.is_index_deleted() {
+        Some(Change::Deleted)
+    } else if status.is_index_renamed() {
+        Some(Change::Renamed)
+    } else if status.is_index_typechange() {
+        Some(Change::Typechange)
+    } else {
+        None
+    }
+}
+
+fn unstaged_change(status: Status) -> Option<Change> {
+    if status.is_wt_modified() {
+        Some(Change::Modified)
+    } else if status.is_wt_deleted() {
+        Some(Change::Deleted)
+    } else if status.is_wt_renamed() {
+        Some(Change::Renamed)
+    } else if status.is_wt_typechange() {
+        Some(Change::Typechange)
+    } else {
+        None
+    }
+}
+
+fn delta_change(delta: Delta) -> Option<Change> {
+    match delta {
+        Delta::Added | Delta::Untracked => Some(Change::Added),
+        Delta::Modified => Some(Change::Modified),
+        Delta::Deleted => Some(Change::Deleted),
+        Delta::Renamed | Delta::Copied => Some(Change::Renamed),
+        Delta::Typechange => Some(Change::Typechange),
+        _ => None,
+    }
+}
+
+// Yamanın satırları ön yüze gönderilecek biçime çevrilir; eklenen/silinen satırlar sayılır
+pub(crate) fn collect_hunks(patch: &Patch, result: &mut FileDiff) -> Result<(), String> {
+    for hunk_index in 0..patch.num_hunks() {
+        let (hunk, line_count) = patch.hunk(hunk_index).map_err(git_error)?;
+        let mut lines = Vec::with_capacity(line_count);
+        for line_index in 0..line_count {
+            let line = patch.line_in_hunk(hunk_index, line_index).map_err(git_error)?;
+            let origin = match line.origin() {
+                '+' => {
+                    result.additions += 1;
+                    '+'
+                }
+                '-' => {
+                    result.deletions += 1;
+                    '-'
+                }
+                ' ' => ' ',
+                // "\ No newline at end of file" gibi işaretler
+                _ => continue,
+            };
+            let content = String::from_utf8_lossy(line.content());
+            lines.push(DiffLine {
+                origin,
+                content: content.trim_end_matches(['\n', '\r']).to_string(),
+                old_line: line.old_lineno(),
+                new_line: line.new_lineno(),
+            });
+        }
+        result.hunks.push(DiffHunk {
+            header: String::from_utf8_lossy(hunk.header()).trim_end().to_string(),
+            old_start: hunk.old_start(),
+            old_lines: hunk.old_lines(),
+            new_start: hunk.new_start(),
+            new_lines: hunk.new_lines(),
+            lines,
+        });
+    }
+    Ok(())
+}
+
+fn commit_info(commit: &git2::Commit) -> CommitInfo {
+    let id = commit.id().to_string();
+    let author = commit.author();
+    let seconds = commit.time().seconds();
+    CommitInfo {
+        short_id: id.chars().take(7).collect(),
+        id,
+        summary: commit.summary().unwrap_or_default().to_string(),
+        message: commit.message().unwrap_or_default().trim_end().to_string(),
+        author: author.name().unwrap_or_default().to_string(),
+        email: author.email().unwrap_or_default().to_string(),
+        time: chrono::DateTime::from_timestamp(seconds, 0).map(|t| t.to_rfc3339()),
+    }
+}
+
+// Klasördeki (alt klasörler dahil) değişmiş dosyalar; klasör bir depoda değilse None
+#[tauri::command]
+pub async fn git_status(workspace: State<'_, WorkspaceState>, root: String) -> Result<Option<GitStatus>, String> {
+    let root = workspace.check(&root)?;
+    let Some(repo) = Repo::discover(&root)? else { return Ok(None) };
+    let scope = repo.relative(&root)?;
+
+    let mut options = StatusOptions::new();
+    options.include_untracked(true).recurse_untracked_dirs(true).renames_head_to_index(true);
+    if !scope.is_empty() {
+        options.pathspec(format!("{}/", scope));
+    }
+    let statuses = repo.repo.statuses(Some(&mut options)).map_err(git_error)?;
+    let files = statuses
+        .iter()
+        .filter_map(|entry| {
+            let relative = entry.path()?.to_string();
+            let status = entry.status();
+            Some(FileStatus {
+                path: repo.absolute(&relative),
+                relative_path: relative,
+                staged: staged_change(status),
+                unstaged: unstaged_change(status),
+                untracked: status.is_wt_new(),
+                conflicted: status.is_conflicted(),
+            })
+        })
+        .collect();
+    Ok(Some(GitStatus { repository: repo.workdir.to_string_lossy().to_string(), branch: repo.branch(), files }))
+}
+
+// Dosyaları (ya da klasörleri) commit'e hazırlar; silinmiş dosyaların silinmesi de hazırlanır
+#[tauri::command]
+pub async fn git_stage(workspace: State<'_, WorkspaceState>, root: String, paths: Vec<String>) -> Result<(), String> {
+    let repo = Repo::open(&workspace.check(&root)?)?;
+    let relative = repo.relative_paths(&workspace, &paths)?;
+    let mut index = repo.repo.index().map_err(git_error)?;
+    index.add_all(relative.iter(), IndexAddOption::DEFAULT, None).map_err(git_error)?;
+    index.update_all(relative.iter(), None).map_err(git_error)?;
+    index.write().map_err(git_error)
+}
+
+// Hazırlığı geri alır; çalışma kopyasındaki değişikliklere dokunmaz
+#[tauri::command]
+pub async fn git_unstage(workspace: State<'_, WorkspaceState>, root: String, paths: Vec<String>) -> Result<(), String> {
+    let repo = Repo::open(&workspace.check(&root)?)?;
+    let relative = repo.relative_paths(&workspace, &paths)?;
+    let head = repo.head_commit()?;
+    match head {
+        Some(head) => repo.repo.reset_default(Some(head.as_object()), relative.iter()).map_err(git_error),
+        // İlk commit'ten önce: dosyalar index'ten çıkarılır
+        None => {
+            let mut index = repo.repo.index().map_err(git_error)?;
+            index.remove_all(relative.iter(), None).map_err(git_error)?;
+            index.write().map_err(git_error)
+        }
+    }
+}
+
+// Hazırlanmış değişiklikleri commit'ler; yazar bilgisi git yapılandırmasından (user.name/user.email) alınır
+#[tauri::command]
+pub async fn git_commit(workspace: State<'_, WorkspaceState>, root: String, message: String) -> Result<CommitInfo, String> {
+    let repo = Repo::open(&workspace.check(&root)?)?;
+    let message = git2::message_prettify(message.trim(), None).map_err(git_error)?;
+    if message.trim().is_empty() {
+        return Err("Commit mesajı boş olamaz".to_string());
+    }
+    let mut index = repo.repo.index().map_err(git_error)?;
+    if index.has_conflicts() {
+        return Err("Çakışmalar çözülmeden commit yapılamaz".to_string());
+    }
+    let tree_id = index.write_tree().map_err(git_error)?;
+    let parent = repo.head_commit()?;
+    let unchanged = match &parent {
+        Some(parent) => parent.tree_id() == tree_id,
+        None => index.is_empty(),
+    };
+    if unchanged {
+        return Err("Commit'lenecek hazırlanmış değişiklik yok".to_string());
+    }
+    let signature = repo
+        .repo
+        .signature()
+        .map_err(|_| "Git kimliği ayarlı değil: 'git config user.name' ve 'git config user.email' ile ayarlayın".to_string())?;
+    let tree = repo.repo.find_tree(tree_id).map_err(git_error)?;
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    let id = repo.repo.commit(Some("HEAD"), &signature, &signature, &message, &tree, &parents).map_err(git_error)?;
+    let commit = repo.repo.find_commit(id).map_err(git_error)?;
+    Ok(commit_info(&commit))
+}
+
+// Dosyayı değiştiren commit'ler, en yeniden eskiye; birleştirmelerde ilk ebeveyne göre bakılır
+#[tauri::command]
+pub async fn git_file_log(workspace: State<'_, WorkspaceState>, path: String, limit: Option<usize>) -> Result<Vec<CommitInfo>, String> {
+    let path = workspace.check(&path)?;
+    let repo = Repo::open(&path)?;
+    let relative = repo.relative(&path)?;
+    let Some(_) = repo.head_commit()? else { return Ok(Vec::new()) };
+
+    let mut walk = repo.repo.revwalk().map_err(git_error)?;
+    walk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME).map_err(git_error)?;
+    walk.push_head().map_err(git_error)?;
+    let blob_at = |commit: &git2::Commit| commit.tree().ok()?.get_path(Path::new(&relative)).ok().map(|entry| entry.id());
+
+    let limit = limit.unwrap_or(DEFAULT_LOG_LIMIT);
+    let mut commits = Vec::new();
+    for id in walk {
+        let commit = repo.repo.find_commit(id.map_err(git_error)?).map_err(git_error)?;
+        let before = commit.parent(0).ok().and_then(|parent| blob_at(&parent));
+        if blob_at(&commit) != before {
+            commits.push(commit_info(&commit));
+            if commits.len() >= limit {
+                break;
+            }
+        }
+    }
+    Ok(commits)
+}
+
+// Çalışma kopyasının HEAD'e göre farkı (hazırlanmış ve hazırlanmamış değişiklikler birlikte)
+#[tauri::command]
+pub async fn git_diff(workspace: State<'_, WorkspaceState>, path: String, context_lines: Option<u32>) -> Result<FileDiff, String> {
+    let path = workspace.check(&path)?;
+    let repo = Repo::open(&path)?;
+    let relative = repo.relative(&path)?;
+    let head_tree = match repo.head_commit()? {
+        Some(commit) => Some(commit.tree().map_err(git_error)?),
+        None => None,
+    };
+
+    let mut options = DiffOptions::new();
+    options
+        .pathspec(&relative)
+        .disable_pathspec_match(true)
+        .include_untracked(true)
+        .show_untracked_content(true)
+        .context_lines(context_lines.unwrap_or(3));
+    let diff = repo.repo.diff_tree_to_workdir_with_index(head_tree.as_ref(), Some(&mut options)).map_err(git_error)?;
+
+    let mut result = FileDiff { path: path.to_string_lossy().to_string(), change: None, binary: false, additions: 0, deletions: 0, hunks: Vec::new() };
+    let Some(delta) = diff.deltas().next() else { return Ok(result) };
+    result.change = delta_change(delta.status());
+    result.binary = delta.flags().is_binary();
+    let Some(patch) = Patch::from_diff(&diff, 0).map_err(git_error)? else { return Ok(result) };
+    result.binary |= patch.delta().flags().is_binary();
+
+    collect_hunks(&patch, &mut result)?;
+    Ok(result)
+}
@@ -0,0 +1,364 @@
+// Notların yerel sürüm geçmişi: write_file her kayıtta içeriğin bir kopyasını uygulama veri klasörüne
+// alır. Her not için ayrı bir klasörde içerik özetiyle adlandırılmış kopyalar ve bir sürüm listesi
+// tutulur; aynı içerik ikinci kez saklanmaz, eski sürümler saatlik/günlük seyreltilir.
+use git2::{DiffOptions, Patch};
+use globset::GlobSet;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::State;
+
+use crate::git::{self, Change, FileDiff};
+use crate::safe_write::{self, WriteError, WriteOptions, WriteResult};
+use crate::search;
+use crate::search_index::SearchIndexState;
+use crate::workspace::WorkspaceState;
+
+const MANIFEST: &str = "versions.json";
+const MINUTE: u64 = 60 * 1000;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+// (yaş sınırı, aralık): o yaştan genç sürümlerden her aralıkta yalnızca en yenisi kalır (0 = hepsi).
+// Son sınırdan eski sürümler silinir; notun en son sürümü her zaman korunur.
+const RETENTION: &[(u64, u64)] = &[(10 * MINUTE, 0), (DAY, HOUR), (30 * DAY, DAY)];
+// Gömülü görselli dev notlar geçmişi şişirmesin
+const MAX_SNAPSHOT_SIZE: usize = 5 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Version {
+    // Kayıt zamanı (Unix ms); not içinde sürüm kimliği olarak da kullanılır
+    pub id: u64,
+    // İçeriğin SHA-256 özeti
+    pub hash: String,
+    pub size: u64,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct Manifest {
+    path: String,
+    // Eskiden yeniye
+    versions: Vec<Version>,
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or_default()
+}
+
+// Yeniden eskiye bakılır; her aralıkta ilk görülen (en yeni) sürüm kalır
+fn prune(versions: &mut Vec<Version>, now: u64) {
+    let newest = versions.last().map(|v| v.id);
+    let mut buckets = HashSet::new();
+    let mut kept: Vec<Version> = versions
+        .drain(..)
+        .rev()
+        .filter(|version| {
+            if Some(version.id) == newest {
+                return true;
+            }
+            let age = now.saturating_sub(version.id);
+            match RETENTION.iter().find(|(limit, _)| age < *limit) {
+                Some((_, 0)) => true,
+                Some((_, step)) => buckets.insert((*step, version.id / step)),
+                None => false,
+            }
+        })
+        .collect();
+    kept.reverse();
+    *versions = kept;
+}
+
+pub struct HistoryState {
+    // Uygulama veri klasörü bulunamazsa geçmiş tutulmaz
+    dir: Option<PathBuf>,
+    lock: Mutex<()>,
+}
+
+impl HistoryState {
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        HistoryState { dir, lock: Mutex::new(()) }
+    }
+
+    // Not klasörü kanonik yolun özetiyle adlandırılır
+    fn note_dir(&self, path: &Path) -> Option<PathBuf> {
+        let key = safe_write::hash_bytes(path.to_string_lossy().as_bytes());
+        self.dir.as_ref().map(|dir| dir.join(key))
+    }
+
+    fn load(note_dir: &Path) -> Manifest {
+        let Ok(bytes) = fs::read(note_dir.join(MANIFEST)) else { return Manifest::default() };
+        serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+            log::warn!("Sürüm listesi okunamadı ({}): {}", note_dir.display(), e);
+            Manifest::default()
+        })
+    }
+
+    fn save(note_dir: &Path, manifest: &Manifest) -> Result<(), String> {
+        let json = serde_json::to_vec(manifest).map_err(|e| e.to_string())?;
+        safe_write::write_atomic(&note_dir.join(MANIFEST), &json, &WriteOptions::default())?;
+        Ok(())
+    }
+
+    // Kayıt hatası notun kaydedilmesini engellemez; yalnızca günlüğe yazılır
+    pub fn record(&self, path: &Path, content: &[u8]) {
+        if let Err(e) = self.try_record(path, content) {
+            log::warn!("Sürüm kaydedilemedi ({}): {}", path.display(), e);
+        }
+    }
+
+    // Diskteki içerik (ör. başka bir uygulamanın değişikliği ya da geçmişi olmayan notun ilk hâli)
+    // üzerine yazılmadan önce saklanır; son sürümle aynıysa bir şey yapılmaz
+    pub fn record_current(&self, path: &Path) {
+        if !search::is_note_file(path) {
+            return;
+        }
+        if let Ok(content) = fs::read(path) {
+            self.record(path, &content);
+        }
+    }
+
+    fn try_record(&self, path: &Path, content: &[u8]) -> Result<(), String> {
+        if !search::is_note_file(path) || content.len() > MAX_SNAPSHOT_SIZE {
+            return Ok(());
+        }
+        let Some(note_dir) = self.note_dir(path) else { return Ok(()) };
+        let _guard = self.lock.lock().unwrap();
+        let mut manifest = Self::load(&note_dir);
+        let hash = safe_write::hash_bytes(content);
+        if manifest.versions.last().is_some_and(|last| last.hash == hash) {
+            return Ok(());
+        }
+
+        fs::create_dir_all(&note_dir).map_err(|e| e.to_string())?;
+        let blob = note_dir.join(&hash);
+        if !blob.exists() {
+            safe_write::write_atomic(&blob, content, &WriteOptions::default())?;
+        }
+        // Aynı milisaniyedeki iki kayıt da ayrı kimlik alsın
+        let now = now_millis();
+        let id = manifest.versions.last().map_or(now, |last| now.max(last.id + 1));
+        manifest.path = path.to_string_lossy().to_string();
+        manifest.versions.push(Version { id, hash, size: content.len() as u64 });
+        prune(&mut manifest.versions, now);
+        Self::save(&note_dir, &manifest)?;
+        Self::remove_unused(&note_dir, &manifest);
+        Ok(())
+    }
+
+    // Seyreltmede hiçbir sürümün kullanmadığı kopyalar silinir
+    fn remove_unused(note_dir: &Path, manifest: &Manifest) {
+        let used: HashSet<&str> = manifest.versions.iter().map(|v| v.hash.as_str()).collect();
+        let Ok(entries) = fs::read_dir(note_dir) else { return };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name != MANIFEST && !name.starts_with('.') && !used.contains(name.as_str()) {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+
+    // Yeniden eskiye
+    pub fn list(&self, path: &Path) -> Vec<Version> {
+        let Some(note_dir) = self.note_dir(path) else { return Vec::new() };
+        let _guard = self.lock.lock().unwrap();
+        let mut versions = Self::load(&note_dir).versions;
+        versions.reverse();
+        versions
+    }
+
+    pub fn read(&self, path: &Path, id: u64) -> Result<Vec<u8>, String> {
+        let note_dir = self.note_dir(path).ok_or("Sürüm geçmişi kullanılamıyor")?;
+        let _guard = self.lock.lock().unwrap();
+        let manifest = Self::load(&note_dir);
+        let version = manifest.versions.iter().find(|v| v.id == id).ok_or("Sürüm bulunamadı")?;
+        fs::read(note_dir.join(&version.hash)).map_err(|e| format!("Sürüm okunamadı: {}", e))
+    }
+
+    // Not şifrelendiğinde düz metin sürümleri diskte kalmasın
+    pub fn forget(&self, path: &Path) {
+        let Some(note_dir) = self.note_dir(path) else { return };
+        let _guard = self.lock.lock().unwrap();
+        if let Err(e) = fs::remove_dir_all(&note_dir) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                log::warn!("Sürüm geçmişi silinemedi ({}): {}", path.display(), e);
+            }
+        }
+    }
+
+    // Yeniden adlandırılan dosya ya da klasörün altındaki notların geçmişi yeni yollarına taşınır. Diskteki
+    // değişiklikten sonra çağrılır; klasörler yolun özetiyle adlandırıldığı için tüm geçmiş taranmaz, yalnızca
+    // taşınan notların klasörlerine dokunulur.
+    pub fn path_renamed(&self, from: &Path, to: &Path) {
+        if self.dir.is_none() {
+            return;
+        }
+        let renamed: Vec<(PathBuf, PathBuf)> = if to.is_dir() {
+            search::note_files(to, to, &GlobSet::empty())
+                .into_iter()
+                .filter_map(|new_path| Some((from.join(new_path.strip_prefix(to).ok()?), new_path)))
+                .collect()
+        } else {
+            vec![(from.to_path_buf(), to.to_path_buf())]
+        };
+        let _guard = self.lock.lock().unwrap();
+        for (old_path, new_path) in renamed {
+            self.move_history(&old_path, &new_path);
+        }
+    }
+
+    fn move_history(&self, old_path: &Path, new_path: &Path) {
+        let (Some(old_dir), Some(new_dir)) = (self.note_dir(old_path), self.note_dir(new_path)) else { return };
+        if !old_dir.exists() {
+            return;
+        }
+        // Yeni yolda daha önce silinmiş bir notun geçmişi varsa o korunur
+        if new_dir.exists() {
+            log::warn!("Sürüm geçmişi taşınamadı, hedefte geçmiş var: {}", new_path.display());
+            return;
+        }
+        let mut manifest = Self::load(&old_dir);
+        manifest.path = new_path.to_string_lossy().to_string();
+        let moved = fs::rename(&old_dir, &new_dir).map_err(|e| e.to_string()).and_then(|_| Self::save(&new_dir, &manifest));
+        if let Err(e) = moved {
+            log::warn!("Sürüm geçmişi taşınamadı ({}): {}", new_path.display(), e);
+        }
+    }
+}
+
+// Notun kayıtlı sürümleri, yeniden eskiye
+#[tauri::command]
+pub async fn list_versions(workspace: State<'_, WorkspaceState>, history: State<'_, HistoryState>, path: String) -> Result<Vec<Version>, String> {
+    let path = workspace.check(&path)?;
+    Ok(history.list(&path))
+}
+
+// Önizleme için bir sürümün içeriği
+#[tauri::command]
+pub async fn read_version(workspace: State<'_, WorkspaceState>, history: State<'_, HistoryState>, path: String, id: u64) -> Result<String, String> {
+    let path = workspace.check(&path)?;
+    let content = history.read(&path, id)?;
+    Ok(String::from_utf8_lossy(&content).to_string())
+}
+
+// İki sürüm arasındaki fark; `to` verilmezse dosyanın diskteki hâliyle karşılaştırılır
+#[tauri::command]
+pub async fn diff_versions(workspace: State<'_, WorkspaceState>, history: State<'_, HistoryState>, path: String, from: u64, to: Option<u64>, context_lines: Option<u32>) -> Result<FileDiff, String> {
+    let path = workspace.check(&path)?;
+    let old = history.read(&path, from)?;
+    let new = match to {
+        Some(id) => history.read(&path, id)?,
+        None => match fs::read(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.to_string()),
+        },
+    };
+
+    let mut options = DiffOptions::new();
+    options.context_lines(context_lines.unwrap_or(3));
+    let patch = Patch::from_buffers(&old, Some(&path), &new, Some(&path), Some(&mut options)).map_err(git::git_error)?;
+    let mut result = FileDiff {
+        path: path.to_string_lossy().to_string(),
+        change: if old == new { None } else { Some(Change::Modified) },
+        binary: patch.delta().flags().is_binary(),
+        additions: 0,
+        deletions: 0,
+        hunks: Vec::new(),
+    };
+    git::collect_hunks(&patch, &mut result)?;
+    Ok(result)
+}
+
+// Not içeriğini diske yazan her komut buradan geçer: diskteki içerik önce geçmişe alınır, yazılan içerik
+// arama dizinine ve geçmişe işlenir. Toplu değişiklikler (etiket yeniden adlandırma) de böylece geri alınabilir.
+pub fn write_note(history: &HistoryState, index: &SearchIndexState, path: &Path, content: &[u8], options: &WriteOptions) -> Result<WriteResult, WriteError> {
+    history.record_current(path);
+    let result = safe_write::write_atomic(path, content, options)?;
+    index.file_written(path, &String::from_utf8_lossy(content), &result.fingerprint);
+    history.record(path, content);
+    Ok(result)
+}
+
+// Sürümü geri yükler; diskteki içerik önce geçmişe alınır, böylece geri yükleme de geri alınabilir
+#[tauri::command]
+pub async fn restore_version(index: State<'_, SearchIndexState>, workspace: State<'_, WorkspaceState>, history: State<'_, HistoryState>, path: String, id: u64, options: Option<WriteOptions>) -> Result<WriteResult, WriteError> {
+    let path = workspace.check(&path)?;
+    let content = history.read(&path, id).map_err(|message| WriteError::Io { path: path.to_string_lossy().to_string(), message })?;
+    write_note(&history, &index, &path, &content, &options.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("pignote_history_{}_{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    // Verilen yaşlardaki (ms) sürümler seyreltilir, kalanların yaşları döner
+    fn kept_ages(ages: &[u64]) -> Vec<u64> {
+        // Saat ve gün sınırına denk gelen bir "şimdi": aralıklar tahmin edilebilir olsun
+        let now = 1000 * DAY;
+        let mut versions: Vec<Version> = ages.iter().rev().map(|age| Version { id: now - age, hash: age.to_string(), size: 0 }).collect();
+        prune(&mut versions, now);
+        versions.iter().rev().map(|v| now - v.id).collect()
+    }
+
+    #[test]
+    fn prune_keeps_everything_younger_than_ten_minutes() {
+        let ages = [0, MINUTE, 10 * MINUTE - 2, 10 * MINUTE - 1];
+        assert_eq!(kept_ages(&ages), ages);
+    }
+
+    #[test]
+    fn prune_keeps_newest_per_hour_below_a_day() {
+        let ages = [0, 10 * MINUTE, 20 * MINUTE, 2 * HOUR + 10 * MINUTE, 2 * HOUR + 20 * MINUTE, DAY - 1];
+        assert_eq!(kept_ages(&ages), [0, 10 * MINUTE, 2 * HOUR + 10 * MINUTE, DAY - 1]);
+    }
+
+    #[test]
+    fn prune_keeps_newest_per_day_below_thirty_days() {
+        let ages = [0, DAY, DAY + HOUR, DAY + 2 * HOUR, 30 * DAY - 1, 30 * DAY, 40 * DAY];
+        assert_eq!(kept_ages(&ages), [0, DAY, DAY + HOUR, 30 * DAY - 1]);
+    }
+
+    #[test]
+    fn prune_never_drops_the_latest_version() {
+        assert_eq!(kept_ages(&[40 * DAY]), [40 * DAY]);
+        assert_eq!(kept_ages(&[40 * DAY, 50 * DAY]), [40 * DAY]);
+    }
+
+    #[test]
+    fn rename_moves_history_of_file_and_folder_contents() {
+        let dir = temp_dir("rename");
+        let history = HistoryState::new(Some(dir.join("history")));
+        let notes = dir.join("notes");
+        fs::create_dir_all(notes.join("eski/alt")).unwrap();
+        for (path, content) in [("a.md", "a"), ("eski/b.md", "b"), ("eski/alt/c.md", "c")] {
+            fs::write(notes.join(path), content).unwrap();
+            history.record(&notes.join(path), content.as_bytes());
+        }
+
+        fs::rename(notes.join("a.md"), notes.join("z.md")).unwrap();
+        history.path_renamed(&notes.join("a.md"), &notes.join("z.md"));
+        assert!(history.list(&notes.join("a.md")).is_empty());
+        assert_eq!(history.list(&notes.join("z.md")).len(), 1);
+
+        fs::rename(notes.join("eski"), notes.join("yeni")).unwrap();
+        history.path_renamed(&notes.join("eski"), &notes.join("yeni"));
+        for path in ["b.md", "alt/c.md"] {
+            assert!(history.list(&notes.join("eski").join(path)).is_empty());
+            let versions = history.list(&notes.join("yeni").join(path));
+            assert_eq!(versions.len(), 1, "{path}");
+            let content = history.read(&notes.join("yeni").join(path), versions[0].id).unwrap();
+            assert_eq!(content, fs::read(notes.join("yeni").join(path)).unwrap());
+        }
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,119 @@
+// Export motorlarının (yerleşik PDF, DOCX) ortak görsel yardımcıları
+use base64::{engine::general_purpose, Engine as _};
+use std::path::Path;
+
+use crate::workspace::ReadScope;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    Gif,
+}
+
+impl ImageFormat {
+    pub fn detect(bytes: &[u8]) -> Option<ImageFormat> {
+        if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(ImageFormat::Png)
+        } else if bytes.starts_with(&[0xff, 0xd8]) {
+            Some(ImageFormat::Jpeg)
+        } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+            Some(ImageFormat::Gif)
+        } else {
+            None
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Jpeg => "jpeg",
+            ImageFormat::Gif => "gif",
+        }
+    }
+
+    pub fn mime(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "image/png",
+            ImageFormat::Jpeg => "image/jpeg",
+            ImageFormat::Gif => "image/gif",
+        }
+    }
+}
+
+// Markdown'daki görsel adresini okur: data: URI, file:///, mutlak yol veya notun klasörüne göre göreli yol.
+// Uzak (http/https) görseller indirilmez; çalışma alanı dışındaki dosyalar okunmaz.
+pub fn load_image_bytes(src: &str, scope: &ReadScope) -> Option<Vec<u8>> {
+    if let Some(data) = src.strip_prefix("data:") {
+        let (_, payload) = data.split_once(";base64,")?;
+        return general_purpose::STANDARD.decode(payload.trim()).ok();
+    }
+    if src.starts_with("http://") || src.starts_with("https://") {
+        return None;
+    }
+    let local = local_path(src);
+    match scope.check(Path::new(&local)) {
+        Ok(path) => std::fs::read(path).ok(),
+        Err(e) => {
+            log::warn!("Görsel export'a eklenmedi: {}", e);
+            None
+        }
+    }
+}
+
+// "file:///C:/a.png" → "C:/a.png", "file:///home/a.png" → "/home/a.png"; diğer adresler olduğu gibi
+pub fn local_path(src: &str) -> String {
+    match src.strip_prefix("file:///") {
+        Some(rest) if Path::new(rest).is_absolute() => rest.to_string(),
+        Some(rest) => format!("/{}", rest),
+        None => src.to_string(),
+    }
+}
+
+// Piksel cinsinden genişlik/yükseklik
+pub fn dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    match ImageFormat::detect(bytes)? {
+        ImageFormat::Png => {
+            // IHDR her zaman ilk chunk: 8 bayt imza + 8 bayt chunk başlığı
+            let header = bytes.get(16..24)?;
+            let width = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+            let height = u32::from_be_bytes([header[4], header[5], header[6], header[7]]);
+            Some((width, height))
+        }
+        ImageFormat::Jpeg => jpeg_info(bytes).map(|(width, height, _)| (width, height)),
+        ImageFormat::Gif => {
+            let header = bytes.get(6..10)?;
+            let width = u16::from_le_bytes([header[0], header[1]]) as u32;
+            let height = u16::from_le_bytes([header[2], header[3]]) as u32;
+            Some((width, height))
+        }
+    }
+}
+
+// JPEG SOF segmentinden genişlik, yükseklik ve renk bileşeni sayısı
+pub fn jpeg_info(bytes: &[u8]) -> Option<(u32, u32, u8)> {
+    let mut i = 2;
+    while i + 9 < bytes.len() {
+        if bytes[i] != 0xff {
+            i += 1;
+            continue;
+        }
+        let marker = bytes[i + 1];
+        if marker == 0xff {
+            i += 1;
+            continue;
+        }
+        if (0xd0..=0xd9).contains(&marker) || marker == 0x01 {
+            i += 2;
+            continue;
+        }
+        let length = u16::from_be_bytes([bytes[i + 2], bytes[i + 3]]) as usize;
+        if matches!(marker, 0xc0..=0xc3 | 0xc5..=0xc7 | 0xc9..=0xcb | 0xcd..=0xcf) {
+            let height = u16::from_be_bytes([bytes[i + 5], bytes[i + 6]]) as u32;
+            let width = u16::from_be_bytes([bytes[i + 7], bytes[i + 8]]) as u32;
+            return Some((width, height, bytes[i + 9]));
+        }
+        i += 2 + length;
+    }
+    None
+}
@@ -1,21 +1,48 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
-use std::process::{Child, Command, Stdio};
+use std::process::Command;
 use regex::Regex;
 use base64::{engine::general_purpose, Engine as _};
-use once_cell::sync::Lazy;
-use std::sync::{atomic::{AtomicBool, Ordering}, Mutex};
-use lopdf::{Document as LoDocument, Object as LoObject};
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct FileInfo {
-    pub name: String,
-    pub path: String,
-    pub is_directory: bool,
-    pub size: Option<u64>,
-    pub modified: Option<String>,
-}
+use tauri::Manager;
+
+mod branding;
+mod browser;
+mod dialogs;
+mod docx;
+mod encryption;
+mod export_jobs;
+mod export_path;
+mod frontmatter;
+mod git;
+mod history;
+mod images;
+mod listing;
+mod markdown;
+mod page_setup;
+mod pdf_meta;
+mod pdf_render;
+mod recycle;
+mod safe_write;
+mod search;
+mod search_index;
+mod tags;
+mod themes;
+mod toc;
+mod watcher;
+mod wikilinks;
+mod workspace;
+
+use branding::{BrandingOptions, Footer, Watermark};
+use export_jobs::{ExportError, ExportFormat, ExportJobs, ExportStage, Job};
+use export_path::{ExportDestination, ExportResult};
+use markdown::{markdown_to_html, markdown_to_html_with_headings};
+use page_setup::PageSetup;
+use safe_write::{WriteError, WriteOptions, WriteResult};
+use themes::{Page, Template, Theme, ThemeState};
+use toc::TocOptions;
+use wikilinks::{LinkBase, LinkContext};
+use workspace::{AccessError, ReadScope, WorkspaceState};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SaveFileRequest {
@@ -32,82 +59,53 @@ pub struct ExportRequest {
     pub base_dir: Option<String>,
 }
 
-// Basit dosya okuma
-#[tauri::command]
-async fn read_file(path: String) -> Result<String, String> {
-    fs::read_to_string(&path).map_err(|e| e.to_string())
+// PDF motoru: Auto önce Chrome/Edge dener, bulamazsa yerleşik motora düşer
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PdfEngine {
+    #[default]
+    Auto,
+    Chrome,
+    Builtin,
 }
 
-// Basit dosya yazma
-#[tauri::command]
-async fn write_file(path: String, content: String) -> Result<(), String> {
-    fs::write(&path, content).map_err(|e| e.to_string())
-}
-
-// Klasör listeme
-#[tauri::command]
-async fn list_directory(path: String) -> Result<Vec<FileInfo>, String> {
-    let mut files = Vec::new();
-    
-    let entries = fs::read_dir(&path).map_err(|e| e.to_string())?;
-    
-    for entry in entries {
-        let entry = entry.map_err(|e| e.to_string())?;
-        let metadata = entry.metadata().map_err(|e| e.to_string())?;
-        
-        let file_info = FileInfo {
-            name: entry.file_name().to_string_lossy().to_string(),
-            path: entry.path().to_string_lossy().to_string(),
-            is_directory: metadata.is_dir(),
-            size: if metadata.is_file() { Some(metadata.len()) } else { None },
-            modified: None, // Basit tutalım
-        };
-        
-        files.push(file_info);
-    }
-    
-    Ok(files)
-}
-
-// Dosya silme
+// Basit dosya okuma
 #[tauri::command]
-async fn delete_file(path: String) -> Result<(), String> {
-    if Path::new(&path).is_dir() {
-        fs::remove_dir_all(&path).map_err(|e| e.to_string())
-    } else {
-        fs::remove_file(&path).map_err(|e| e.to_string())
+async fn read_file(workspace: tauri::State<'_, WorkspaceState>, encryption: tauri::State<'_, encryption::EncryptionState>, path: String) -> Result<String, String> {
+    let path = workspace.check(&path)?;
+    if encryption::is_encrypted(&path) {
+        let bytes = fs::read(&path).map_err(|e| e.to_string())?;
+        return Ok(encryption.decrypt(&path, &bytes)?);
     }
+    fs::read_to_string(&path).map_err(|e| e.to_string())
 }
 
-// Placeholder dialog fonksiyonları
-#[tauri::command]
-async fn open_file_dialog() -> Result<Option<String>, String> {
-    // Şimdilik None döndür - dialog impl daha sonra
-    Ok(None)
-}
-
+// Atomik dosya yazma: geçici dosya + yeniden adlandırma, isteğe bağlı .bak ve çakışma kontrolü
 #[tauri::command]
-async fn save_file_dialog(_content: String) -> Result<Option<String>, String> {
-    // Şimdilik None döndür - dialog impl daha sonra
-    Ok(None)
+async fn write_file(index: tauri::State<'_, search_index::SearchIndexState>, workspace: tauri::State<'_, WorkspaceState>, history: tauri::State<'_, history::HistoryState>, encryption: tauri::State<'_, encryption::EncryptionState>, path: String, content: String, options: Option<WriteOptions>) -> Result<WriteResult, WriteError> {
+    let path = workspace.check(&path)?;
+    encryption::write_content(&encryption, &history, &index, &path, &content, &options.unwrap_or_default())
 }
 
 #[tauri::command]
-async fn create_directory(path: String) -> Result<(), String> {
+async fn create_directory(workspace: tauri::State<'_, WorkspaceState>, path: String) -> Result<(), String> {
+    let path = workspace.check(&path)?;
     fs::create_dir_all(&path).map_err(|e| e.to_string())
 }
 
 // Dosya yeniden adlandırma
 #[tauri::command]
-async fn rename_file(old_path: String, new_name: String) -> Result<String, String> {
-    let path = Path::new(&old_path);
+async fn rename_file(index: tauri::State<'_, search_index::SearchIndexState>, history: tauri::State<'_, history::HistoryState>, workspace: tauri::State<'_, WorkspaceState>, old_path: String, new_name: String) -> Result<String, String> {
+    let old_path = workspace.check_entry(&old_path)?;
+    let path = old_path.as_path();
     let parent = path.parent().ok_or("Geçersiz dosya yolu")?;
-    let new_path = parent.join(&new_name);
     
-    // Yeni dosya adının geçerli olup olmadığını kontrol et
-    if new_name.is_empty() || new_name.contains(['<', '>', ':', '"', '|', '?', '*']) {
+    // Yeni dosya adının geçerli olup olmadığını kontrol et; "../x" ya da "a/b" ile klasör dışına çıkılamaz
+    workspace::check_name(&new_name)?;
+    if new_name.contains(['<', '>', ':', '"', '|', '?', '*']) {
         return Err("Geçersiz dosya adı".to_string());
     }
+    let new_path = workspace.check_entry(parent.join(&new_name))?;
     
     // Yeni dosya zaten var mı kontrol et
     if new_path.exists() {
@@ -116,693 +114,290 @@ async fn rename_file(old_path: String, new_name: String) -> Result<String, Strin
     
     // Dosyayı yeniden adlandır
     fs::rename(path, &new_path).map_err(|e| e.to_string())?;
+    index.path_renamed(path, &new_path);
+    history.path_renamed(path, &new_path);
     
     Ok(new_path.to_string_lossy().to_string())
 }
 
-// Export işlevselliği
+// [[wiki]] bağlantıları için çalışma alanı taranır; bağlantı yoksa gerek yok
+fn link_context(content: &str, root: Option<&str>, base_dir: Option<&str>, base: LinkBase) -> Option<LinkContext> {
+    if !content.contains("[[") {
+        return None;
+    }
+    LinkContext::new(root, base_dir, base)
+}
+
+fn output_dir(resolved: &export_path::ResolvedPath) -> LinkBase {
+    LinkBase::Relative(resolved.path.parent().map(Path::to_path_buf).unwrap_or_default())
+}
+
+// Export'un okuyacağı ve yazacağı yollar, çalışma alanı denetiminden geçmiş kanonik hâlleriyle
+struct ExportPaths {
+    scope: ReadScope,
+    base_dir: Option<String>,
+    root: Option<String>,
+    destination: Option<ExportDestination>,
+}
+
+// Hedef dosya ya da klasör çalışma alanının içinde olmalı ya da kullanıcı Farklı Kaydet ile seçmiş olmalı;
+// hedef verilmezse varsayılan klasöre (Masaüstü > Belgeler > ev dizini) yazılır
+fn confine_export(workspace: &WorkspaceState, base_dir: Option<&str>, root: Option<&str>, destination: Option<ExportDestination>) -> Result<ExportPaths, AccessError> {
+    let scope = workspace.read_scope(base_dir)?;
+    let base_dir = scope.base_dir().map(|dir| dir.to_string_lossy().to_string());
+    let root = match root {
+        Some(root) => Some(workspace.check(root)?),
+        None => workspace.root(),
+    };
+    let checked = |path: Option<String>| -> Result<Option<String>, AccessError> {
+        match path.as_deref().map(str::trim).filter(|p| !p.is_empty()) {
+            Some(path) => Ok(Some(workspace.check(path)?.to_string_lossy().to_string())),
+            None => Ok(None),
+        }
+    };
+    let destination = match destination {
+        Some(destination) => Some(ExportDestination {
+            output_path: checked(destination.output_path)?,
+            output_dir: checked(destination.output_dir)?,
+            overwrite: destination.overwrite,
+        }),
+        None => None,
+    };
+    Ok(ExportPaths { scope, base_dir, root: root.map(|root| root.to_string_lossy().to_string()), destination })
+}
+
+// Export işlevselliği: komutlar iş kimliğini hemen döndürür, sonuç "export-finished" olayıyla gelir
 #[tauri::command]
-async fn export_to_pdf(content: String, filename: String, dark: Option<bool>, base_dir: Option<String>) -> Result<String, String> {
+#[allow(clippy::too_many_arguments)]
+async fn export_to_pdf(app: tauri::AppHandle, jobs: tauri::State<'_, ExportJobs>, themes: tauri::State<'_, ThemeState>, workspace: tauri::State<'_, WorkspaceState>, content: String, filename: String, theme: Option<String>, base_dir: Option<String>, root: Option<String>, engine: Option<PdfEngine>, destination: Option<ExportDestination>, branding: Option<BrandingOptions>, toc: Option<TocOptions>, page_setup: Option<PageSetup>) -> Result<u64, String> {
+    // Tema, yol izinleri, logo ve sayfa düzeni hataları iş başlamadan bildirilsin
+    let theme = themes.load(theme.as_deref())?;
+    let paths = confine_export(&workspace, base_dir.as_deref(), root.as_deref(), destination)?;
+    let branding = branding.unwrap_or_default();
+    let footer = branding.footer(&paths.scope)?;
+    let page_setup = page_setup.unwrap_or_default();
+    page_setup.validate()?;
+    Ok(jobs.spawn(app, ExportFormat::Pdf, move |job| pdf_export(job, content, filename, theme, paths, engine, branding, footer, toc.unwrap_or_default(), page_setup)))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn pdf_export(job: &Job, content: String, filename: String, theme: Theme, paths: ExportPaths, engine: Option<PdfEngine>, branding: BrandingOptions, footer: Option<Footer>, toc: TocOptions, page_setup: PageSetup) -> Result<ExportResult, ExportError> {
+    let ExportPaths { scope, base_dir, root, destination } = paths;
+    let is_html = content.trim_start().starts_with("<");
+
+    // Uygun Chromium tabanlı tarayıcı yürütülebilirini bul (PIGNOTE_BROWSER > PATH > kurulum dizinleri > Flatpak/Snap)
+    let browser = match engine.unwrap_or_default() {
+        PdfEngine::Builtin => None,
+        PdfEngine::Chrome => Some(browser::discover_from_env()?),
+        PdfEngine::Auto => match browser::discover_from_env() {
+            Ok(found) => Some(found),
+            // HTML içeriği yerleşik motorla dizilemez; tarayıcı hatası olduğu gibi dönsün
+            Err(e) if is_html => return Err(e.into()),
+            Err(_) => None,
+        },
+    };
+
+    let (metadata, warnings) = export_metadata(&content);
+    let watermark = branding.watermark(metadata.as_ref());
+    let Some(browser) = browser else {
+        if is_html {
+            return Err("Yerleşik PDF motoru yalnızca Markdown içeriğini destekler".to_string().into());
+        }
+        job.stage(ExportStage::Convert)?;
+        let resolved = export_path::resolve(&filename, "pdf", destination.as_ref())?;
+        let links = link_context(&content, root.as_deref(), base_dir.as_deref(), output_dir(&resolved));
+        let options = pdf_render::PdfRenderOptions { dark: theme.dark, scope: &scope, layout: pdf_render::PageLayout::from(&page_setup), links: links.as_ref(), metadata: metadata.as_ref(), watermark: watermark.as_ref(), footer: footer.as_ref(), toc, cancel: Some(job.cancel_flag()) };
+        pdf_render::render_markdown_to_pdf(&content, &options, &resolved.path, |step| {
+            job.stage(match step {
+                pdf_render::RenderStep::LoadImages => ExportStage::InlineImages,
+                pdf_render::RenderStep::Layout => ExportStage::Render,
+                pdf_render::RenderStep::Write => ExportStage::PostProcess,
+            })
+        })?;
+        return Ok(ExportResult::new(&resolved).with_warnings(warnings));
+    };
+    log::info!("PDF export için tarayıcı: {} ({:?})", browser.path, browser.source);
+
     // HTML içeriği oluştur
-    let is_dark = dark.unwrap_or(false);
-    // İptal kontrolü
-    if CANCEL_REQUESTED.load(Ordering::SeqCst) { return Err("İşlem iptal edildi".to_string()); }
-    let html_body_raw = if content.trim_start().starts_with("<") { content } else { markdown_to_html(&content)? };
-    // İptal kontrolü
-    if CANCEL_REQUESTED.load(Ordering::SeqCst) { return Err("İşlem iptal edildi".to_string()); }
+    job.stage(ExportStage::Convert)?;
+    // Tarayıcı sayfayı geçici klasörden açtığı için bağlantılar mutlak yazılır
+    let links = link_context(&content, root.as_deref(), base_dir.as_deref(), LinkBase::Absolute);
+    let (html_body_raw, headings) = if is_html { (content, Vec::new()) } else { markdown_to_html_with_headings(&content, links.as_ref(), &toc)? };
+    job.stage(ExportStage::InlineImages)?;
     // Görsellerin PDF'te görünmesi için <img src="..."> yollarını düzelt: file:/// mutlak yol veya base64 inline
-    let html_body = inline_or_fix_images_cancellable(&html_body_raw, base_dir.as_deref())?;
-    // PigNote marka ikonu (PDF'e gömülü base64)
-    let brand_img_b64: String = general_purpose::STANDARD.encode(include_bytes!("../icons/icon.png"));
-    let brand_data_uri = format!("data:image/png;base64,{}", brand_img_b64);
-    let html_content = if is_dark { format!(
-        r#"
-        <!DOCTYPE html>
-        <html>
-        <head>
-            <meta charset="UTF-8">
-            <title>{}</title>
-            <style>
-                :root {{
-                    --accent-500: #ec4899;
-                }}
-                 @media print {{
-                    @page {{ margin: 12mm; }}
-                     body {{ 
-                        font-family: Inter, -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif;
-                        line-height: 1.6;
-                        margin: 12mm;
-                        background: #0f172a;
-                        color: #d1d5db;
-                        font-size: 11pt;
-                        font-weight: 400;
-                    }}
-                    /* Linklerin yazdırmada URL'lerini ekleyen UA kuralını geçersiz kıl */
-                    a[href]::after, a[href]::before {{ content: none !important; }}
-                     h1, h2, h3, h4, h5, h6 {{ page-break-after: avoid; break-after: avoid-page; }}
-                     h1 + *, h2 + *, h3 + *, h4 + *, h5 + * , h6 + * {{ break-before: avoid-page; }}
-                     p, ul, ol, pre, table {{ orphans: 3; widows: 3; }}
-                    h1, h2, h3, h4, h5, h6 {{ color: #e5e7eb; margin-top: 18pt; margin-bottom: 10pt; font-weight: 700; }}
-                    h1 {{ font-size: 18pt; border-bottom: 1pt solid #374151; padding-bottom: 5pt; }}
-                    h2 {{ font-size: 16pt; border-bottom: 1pt solid #374151; padding-bottom: 3pt; }}
-                    code {{ background: #0b1220; padding: 1pt 2pt; border-radius: 2pt; font-family: 'Courier New', monospace; color: #f59e0b; }}
-                    pre {{ background: #0b1220; padding: 10pt; border-radius: 3pt; overflow-x: auto; border: 1pt solid #2a2a2a; color: #cbd5e1; page-break-inside: avoid; break-inside: avoid; }}
-                    blockquote {{ border-left: 3pt solid var(--accent-500); margin: 0; padding-left: 15pt; color: #cbd5e1; background: #111827; }}
-                    img {{ display: block; margin: 6pt auto; width: auto; height: auto; max-width: 17cm; max-height: 16cm; object-fit: contain; page-break-inside: avoid; break-inside: avoid; break-before: avoid-page; page-break-after: auto; }}
-                    ul, ol {{ padding-left: 15pt; }}
-                    li {{ margin-bottom: 3pt; }}
-                    p {{ margin-bottom: 10pt; }}
-                     table {{ border-collapse: collapse; width: 100%; margin: 15pt 0; font-size: 10pt; page-break-inside: avoid; break-inside: avoid; }}
-                    th, td {{ 
-                        border: 1pt solid #2a2a2a; 
-                        padding: 6pt; 
-                        text-align: left; 
-                        vertical-align: top;
-                        word-wrap: break-word;
-                    }}
-                    th {{ 
-                        background-color: #111827; 
-                        font-weight: 600;
-                        color: #e5e7eb;
-                    }}
-                    thead {{ 
-                        background-color: #111827;
-                    }}
-                    tbody tr:nth-child(even) {{ background-color: #0f172a; }}
-                     tbody tr:hover {{ background-color: #0f172a; }}
-                    .watermark {{ position: fixed; top: 50%; left: 50%; transform: translate(-50%, -50%) rotate(-30deg); font-size: 64pt; font-weight: 700; color: rgba(255,255,255,0.06); z-index: 9999; white-space: nowrap; pointer-events: none; }}
-                    .brand-footer {{ position: fixed; bottom: 12mm; left: 12mm; font-size: 9pt; color: #9ca3af; display: flex; align-items: center; gap: 6pt; z-index: 10001; }}
-                    .brand-footer img {{ width: 12pt; height: 12pt; display: inline-block; }}
-                    /* Alt yazıların tamamını maskeleyen örtü */
-                    .print-mask-bottom {{ position: fixed; left: 0; right: 0; bottom: 0; height: 12mm; background: #0f172a; z-index: 10000; }}
-                }}
-                 body {{ 
-                    font-family: Inter, -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif;
-                    line-height: 1.6;
-                    background: #0f172a;
-                    color: #d1d5db;
-                    max-width: 860px;
-                    margin: 32px auto;
-                    font-weight: 400;
-                }}
-                 .watermark {{ display: none; }}
-                 .brand-footer {{ display: none; }}
-                 .print-mask-bottom {{ display: none; }}
-                h1, h2, h3, h4, h5, h6 {{ color: #e5e7eb; margin-top: 24px; margin-bottom: 12px; font-weight: 700; }}
-                h1 {{ font-size: 24px; border-bottom: 2px solid #374151; padding-bottom: 10px; }}
-                h2 {{ font-size: 20px; border-bottom: 1px solid #374151; padding-bottom: 6px; }}
-                code {{ background: #0b1220; padding: 2px 4px; border-radius: 3px; font-family: 'Courier New', monospace; color: #f59e0b; }}
-                pre {{ background: #0b1220; padding: 15px; border-radius: 5px; overflow-x: auto; border: 1px solid #2a2a2a; color: #cbd5e1; }}
-                blockquote {{ border-left: 4px solid var(--accent-500); margin: 0; padding-left: 20px; color: #cbd5e1; background: #111827; }}
-                img {{ 
-                    max-width: 100%; 
-                    height: auto; 
-                    max-height: 70vh; 
-                    object-fit: contain; 
-                    display: block; 
-                    margin: 12px auto; 
-                }}
-                ul, ol {{ padding-left: 20px; }}
-                li {{ margin-bottom: 5px; }}
-                p {{ margin-bottom: 15px; }}
-                table {{ 
-                    border-collapse: collapse; 
-                    width: 100%; 
-                    margin: 20px 0; 
-                    font-size: 14px;
-                }}
-                th, td {{ 
-                    border: 1px solid #2a2a2a; 
-                    padding: 8px; 
-                    text-align: left; 
-                    vertical-align: top;
-                    word-wrap: break-word;
-                }}
-                th {{ 
-                    background-color: #111827; 
-                    font-weight: 600;
-                    color: #e5e7eb;
-                }}
-                thead {{ 
-                    background-color: #111827;
-                }}
-                tbody tr:nth-child(even) {{ background-color: #0f172a; }}
-                tbody tr:hover {{ background-color: #0f172a; }}
-            </style>
-        </head>
-        <body>
-            <div class="print-container">{}</div>
-             <div class="watermark">PigNote</div>
-             <div class="brand-footer"><img src="{}" alt="PigNote" /><span>PigNote</span></div>
-             <div class="print-mask-bottom"></div>
-        </body>
-        </html>
-        "#,
-        filename,
-        html_body,
-        brand_data_uri
-    )} else { format!(
-        r#"
-        <!DOCTYPE html>
-        <html>
-        <head>
-            <meta charset="UTF-8">
-            <title>{}</title>
-            <style>
-                :root {{ --accent-500: #ec4899; }}
-                 @media print {{
-                    @page {{ margin: 12mm; }}
-                    body {{ 
-                        font-family: Inter, -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; 
-                        line-height: 1.6; 
-                        color: #111827; 
-                        margin: 12mm; 
-                        font-size: 11pt; 
-                        font-weight: 400; 
-                        background: #ffffff;
-                    }}
-                    /* Linklerin yazdırmada URL'lerini ekleyen UA kuralını geçersiz kıl */
-                    a[href]::after, a[href]::before {{ content: none !important; }}
-                     h1, h2, h3, h4, h5, h6 {{ page-break-after: avoid; break-after: avoid-page; }}
-                     h1 + *, h2 + *, h3 + *, h4 + *, h5 + * , h6 + * {{ break-before: avoid-page; }}
-                     p, ul, ol, pre, table {{ orphans: 3; widows: 3; }}
-                    img {{
-                        display: block !important;
-                        margin: 6pt auto !important;
-                        width: auto !important;
-                        height: auto !important;
-                        max-width: 17cm !important;
-                        max-height: 16cm !important;
-                        object-fit: contain !important;
-                        page-break-inside: avoid !important;
-                        break-inside: avoid !important;
-                        break-before: avoid-page !important;
-                        page-break-after: auto !important;
-                    }}
-                    table {{ 
-                        border-collapse: collapse; 
-                        width: 100%; 
-                        margin: 15pt 0; 
-                        font-size: 10pt;
-                        page-break-inside: avoid; break-inside: avoid;
-                    }}
-                    th, td {{ 
-                        border: 1pt solid #e5e7eb; 
-                        padding: 6pt; 
-                        text-align: left; 
-                        vertical-align: top;
-                        word-wrap: break-word;
-                    }}
-                    th {{ 
-                        background-color: #f3f4f6; 
-                        font-weight: 600;
-                        color: #111827;
-                    }}
-                    pre {{ page-break-inside: avoid; break-inside: avoid; }}
-                    .watermark {{ position: fixed; top: 50%; left: 50%; transform: translate(-50%, -50%) rotate(-30deg); font-size: 64pt; font-weight: 700; color: rgba(0,0,0,0.06); z-index: 9999; white-space: nowrap; pointer-events: none; }}
-                    .brand-footer {{ position: fixed; bottom: 12mm; left: 12mm; font-size: 9pt; color: #6b7280; display: flex; align-items: center; gap: 6pt; z-index: 10001; }}
-                    .brand-footer img {{ width: 12pt; height: 12pt; display: inline-block; }}
-                    .print-mask-bottom {{ position: fixed; left: 0; right: 0; bottom: 0; height: 12mm; background: #ffffff; z-index: 10000; }}
-                }}
-                body {{ font-family: Inter, -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; line-height: 1.6; color: #374151; max-width: 860px; margin: 32px auto; font-weight: 400; }}
-                .watermark {{ display: none; }}
-                .brand-footer {{ display: none; }}
-                .print-mask-bottom {{ display: none; }}
-                h1, h2, h3, h4, h5, h6 {{ color: #111827; margin-top: 24px; margin-bottom: 12px; font-weight: 700; }}
-                h1 {{ font-size: 24px; border-bottom: 2px solid #e5e7eb; padding-bottom: 10px; }}
-                h2 {{ font-size: 20px; border-bottom: 1px solid #e5e7eb; padding-bottom: 6px; }}
-                code {{ background: #f3f4f6; padding: 2px 4px; border-radius: 3px; font-family: 'Courier New', monospace; }}
-                pre {{ background: #f3f4f6; padding: 15px; border-radius: 5px; overflow-x: auto; border-left: 4px solid var(--accent-500); }}
-                blockquote {{ border-left: 4px solid var(--accent-500); margin: 0; padding-left: 20px; color: #6b7280; background: #fafafa; }}
-                img {{ display:block; margin:12px auto; width:auto; height:auto; max-width: 860px; max-height: 70vh; object-fit: contain; }}
-                table {{ border-collapse: collapse; width: 100%; margin: 20px 0; font-size: 14px; }}
-                th, td {{ border: 1px solid #e5e7eb; padding: 8px; text-align: left; vertical-align: top; word-wrap: break-word; }}
-                th {{ background-color: #f3f4f6; font-weight: 600; color: #111827; }}
-            </style>
-        </head>
-        <body><div class="print-container">{}</div><div class="watermark">PigNote</div><div class="brand-footer"><img src="{}" alt="PigNote" /><span>PigNote</span></div><div class="print-mask-bottom"></div></body>
-        </html>
-        "#,
-        filename,
-        html_body,
-        brand_data_uri
-    )};
+    let mut html_body = inline_or_fix_images_cancellable(job, &html_body_raw, &scope)?;
+    html_body.push_str(&pdf_meta::destination_anchors(&headings));
+    let page = Page {
+        filename: &filename,
+        metadata: metadata.as_ref(),
+        body: &html_body,
+        watermark: &watermark.as_ref().map(Watermark::html).unwrap_or_default(),
+        brand_footer: &footer.as_ref().map(Footer::html).unwrap_or_default(),
+        brand_icon: footer.as_ref().and_then(|footer| footer.logo.as_deref()).unwrap_or_default(),
+        page_css: &page_setup.css(if footer.is_some() { branding::FOOTER_BAND_MM } else { 0.0 }),
+    };
+    let html_content = theme.render(Template::Pdf, &page);
     
-    // Masaüstü yoluna kaydet
-    let desktop_dir = dirs::desktop_dir().ok_or("Masaüstü klasörü bulunamadı")?;
-    let output_path = desktop_dir.join(format!("{}.pdf", filename));
+    // Hedef yolu çöz (varsayılan: Masaüstü > Belgeler > ev dizini)
+    let resolved = export_path::resolve(&filename, "pdf", destination.as_ref())?;
+    let output_path = resolved.path.clone();
     
     // Geçici HTML dosyasını sistemin temp klasöründe oluştur
     let temp_dir = std::env::temp_dir();
-    let temp_html_path = temp_dir.join(format!("{}_pignote_export.html", filename));
+    let temp_stem = output_path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    // Aynı adla paralel export'lar birbirinin dosyasını ezmesin
+    let temp_html_path = temp_dir.join(format!("{}_pignote_export_{}_{}.html", temp_stem, std::process::id(), job.id));
     fs::write(&temp_html_path, &html_content).map_err(|e| e.to_string())?;
     
-    // Uygun Chromium tabanlı tarayıcı yürütülebilirini bul
-    let candidates = [
-        r"C:\\Program Files\\Google\\Chrome\\Application\\chrome.exe",
-        r"C:\\Program Files (x86)\\Google\\Chrome\\Application\\chrome.exe",
-        r"C:\\Program Files\\Microsoft\\Edge\\Application\\msedge.exe",
-        r"C:\\Program Files (x86)\\Microsoft\\Edge\\Application\\msedge.exe",
-    ];
-    let browser = candidates
-        .iter()
-        .find(|p| Path::new(p).exists())
-        .ok_or_else(|| "PDF oluşturulamadı: Headless modda çalışacak Chrome/Edge bulunamadı.".to_string())?;
-
     // URL'yi file:/// formatına çevir
     let url = format!(
         "file:///{}",
         temp_html_path
             .to_string_lossy()
             .replace('\\', "/")
+            .trim_start_matches('/')
     );
 
-    // İptal mekanizması için global child referansı ve flag kullan
-    CANCEL_REQUESTED.store(false, Ordering::SeqCst);
-    {
-        let mut guard = CURRENT_EXPORT_PROCESS.lock().unwrap();
-        // Her ihtimale karşı önceki bir süreç kalmışsa öldür
-        if let Some(prev) = guard.as_mut() {
-            let _ = prev.kill();
-        }
-        *guard = None;
-    }
-
-    // Headless export sürecini başlat
-    let child_result: Result<Child, std::io::Error> = Command::new(browser)
-        .arg("--headless")
-        .arg("--disable-gpu")
-        .arg("--disable-features=PrintingPDFHeaderFooter")
-        .arg("--no-pdf-header-footer")
-        .arg("--print-to-pdf-no-header")
-        .arg(format!("--print-to-pdf={}", output_path.to_string_lossy()))
-        .arg(url)
-        .stdin(Stdio::null())
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .spawn();
-
-    let child = child_result.map_err(|e| e.to_string())?;
-
-    {
-        let mut guard = CURRENT_EXPORT_PROCESS.lock().unwrap();
-        *guard = Some(child);
-    }
-
-    // Child referansını tekrar alabilmek için ayrı scope
-    let status = {
-        let mut opt = CURRENT_EXPORT_PROCESS.lock().unwrap();
-        let ch = opt.as_mut().unwrap();
-        ch.wait().map_err(|e| e.to_string())?
-    };
-
-    // Süreç bitti, referansı temizle
-    {
-        let mut guard = CURRENT_EXPORT_PROCESS.lock().unwrap();
-        *guard = None;
-    }
+    // Headless export sürecini başlat; iptal edilirse yalnızca bu işin süreci öldürülür
+    job.stage(ExportStage::Render)?;
+    let status = job.run(
+        Command::new(&browser.path)
+            .arg("--headless")
+            .arg("--disable-gpu")
+            .arg("--disable-features=PrintingPDFHeaderFooter")
+            .arg("--no-pdf-header-footer")
+            .arg("--print-to-pdf-no-header")
+            .arg(format!("--print-to-pdf={}", output_path.to_string_lossy()))
+            .arg(url),
+    );
 
     // Geçici HTML dosyasını sil
     let _ = fs::remove_file(&temp_html_path);
 
-    // İptal isteği geldiyse iptal olarak dön
-    if CANCEL_REQUESTED.load(Ordering::SeqCst) {
-        // Dosya oluştuysa da temizlemeye çalış
-        if output_path.exists() {
-            let _ = fs::remove_file(&output_path);
+    let status = match status {
+        Ok(status) => status,
+        Err(e) => {
+            // İptal edildiyse yarım kalan çıktıyı da temizlemeye çalış
+            if job.is_cancelled() && output_path.exists() {
+                let _ = fs::remove_file(&output_path);
+            }
+            return Err(e.into());
         }
-        CANCEL_REQUESTED.store(false, Ordering::SeqCst);
-        return Err("İşlem iptal edildi".to_string());
-    }
+    };
 
     // Başarı kontrolü
     if status.success() && output_path.exists() {
-        // Güvenlik için: Bazı Chromium sürümleri header/footer'ı yine de basabilir.
-        // Alt kenardan daha agresif kırp: ~140pt (yaklaşık 49mm) – olası file:/// yolu ve sayfa numarası tamamen kalkar.
-        let _ = remove_pdf_footer_by_cropping(&output_path, 140.0);
-        Ok(output_path.to_string_lossy().to_string())
+        if let Err(e) = job.stage(ExportStage::PostProcess) {
+            let _ = fs::remove_file(&output_path);
+            return Err(e.into());
+        }
+        // Yer imleri ve belge bilgisi yazılamazsa PDF yine de kullanılabilir
+        if let Err(e) = pdf_meta::annotate_file(&output_path, &headings, metadata.as_ref()) {
+            log::warn!("PDF yer imleri ve belge bilgisi yazılamadı: {}", e);
+        }
+        Ok(ExportResult::new(&resolved).with_warnings(warnings))
     } else {
-        Err("PDF oluşturulamadı".to_string())
+        Err("PDF oluşturulamadı".to_string().into())
     }
 }
 
-// HTML export: Markdown veya verilen HTML içeriğini tek dosyalık şık bir sayfaya dönüştür ve masaüstüne kaydet
+// HTML export: Markdown veya verilen HTML içeriğini tek dosyalık şık bir sayfaya dönüştür ve kaydet
 #[tauri::command]
-async fn export_to_html(content: String, filename: String, dark: Option<bool>, base_dir: Option<String>) -> Result<String, String> {
-    let is_dark = dark.unwrap_or(false);
-    if CANCEL_REQUESTED.load(Ordering::SeqCst) { return Err("İşlem iptal edildi".to_string()); }
-
-    let html_body_raw = if content.trim_start().starts_with("<") { content } else { markdown_to_html(&content)? };
-    if CANCEL_REQUESTED.load(Ordering::SeqCst) { return Err("İşlem iptal edildi".to_string()); }
-    let html_body = inline_or_fix_images_cancellable(&html_body_raw, base_dir.as_deref())?;
-
-    let page_html = if is_dark { format!(
-        r#"<!DOCTYPE html>
-<html>
-<head>
-  <meta charset=\"UTF-8\" />
-  <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\" />
-  <title>{}</title>
-  <style>
-    :root {{ --accent-500: #ec4899; }}
-    body {{ font-family: Inter, -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; margin: 40px auto; max-width: 860px; line-height: 1.7; color: #e5e7eb; background: #0f172a; }}
-    h1, h2, h3, h4, h5, h6 {{ color: #fff; margin-top: 28px; margin-bottom: 12px; font-weight: 700; }}
-    h1 {{ border-bottom: 1px solid #374151; padding-bottom: 6px; }}
-    h2 {{ border-bottom: 1px solid #374151; padding-bottom: 4px; }}
-    p {{ margin: 12px 0; }}
-    code {{ background: #111827; border: 1px solid #334155; color: #f59e0b; padding: 2px 6px; border-radius: 6px; }}
-    pre {{ background: #0b1220; border: 1px solid #334155; color: #cbd5e1; padding: 16px; border-radius: 12px; overflow: auto; }}
-    blockquote {{ border-left: 4px solid var(--accent-500); margin: 1em 0; padding: 8px 16px; background: #111827; color: #cbd5e1; }}
-    a {{ color: #f472b6; text-decoration: none; }}
-    a:hover {{ text-decoration: underline; }}
-    table {{ border-collapse: collapse; width: 100%; margin: 16px 0; font-size: 14px; }}
-    th, td {{ border: 1px solid #334155; padding: 10px; text-align: left; }}
-    th {{ background: #111827; color: #e5e7eb; }}
-    img {{ max-width: 100%; height: auto; display: block; margin: 8px auto; }}
-  </style>
-</head>
-<body>
-{html}
-</body>
-</html>"#, filename, html = html_body)
-    } else { format!(
-        r#"<!DOCTYPE html>
-<html>
-<head>
-  <meta charset=\"UTF-8\" />
-  <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\" />
-  <title>{}</title>
-  <style>
-    :root {{ --accent-500: #ec4899; }}
-    body {{ font-family: Inter, -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; margin: 40px auto; max-width: 860px; line-height: 1.7; color: #374151; background: #fff; }}
-    h1, h2, h3, h4, h5, h6 {{ color: #111827; margin-top: 28px; margin-bottom: 12px; font-weight: 700; }}
-    h1 {{ border-bottom: 2px solid #e5e7eb; padding-bottom: 8px; }}
-    h2 {{ border-bottom: 1px solid #e5e7eb; padding-bottom: 6px; }}
-    p {{ margin: 12px 0; }}
-    code {{ background: #f8f9fa; border: 1px solid #e5e7eb; color: #e83e8c; padding: 2px 6px; border-radius: 6px; }}
-    pre {{ background: #f8f9fa; border: 1px solid #e5e7eb; color: #374151; padding: 16px; border-radius: 12px; overflow: auto; }}
-    blockquote {{ border-left: 4px solid var(--accent-500); margin: 1em 0; padding: 8px 16px; background: #f8f9fa; color: #6b7280; }}
-    a {{ color: #ec4899; text-decoration: none; }}
-    a:hover {{ text-decoration: underline; }}
-    table {{ border-collapse: collapse; width: 100%; margin: 16px 0; font-size: 14px; }}
-    th, td {{ border: 1px solid #e5e7eb; padding: 10px; text-align: left; }}
-    th {{ background: #f8f9fa; color: #111827; }}
-    img {{ max-width: 100%; height: auto; display: block; margin: 8px auto; }}
-  </style>
-</head>
-<body>
-{html}
-</body>
-</html>"#, filename, html = html_body) };
-
-    let desktop_dir = dirs::desktop_dir().ok_or("Masaüstü dizini bulunamadı")?;
-    let output_path = desktop_dir.join(format!("{}.html", filename));
-    std::fs::write(&output_path, page_html).map_err(|e| e.to_string())?;
-    Ok(output_path.to_string_lossy().to_string())
+#[allow(clippy::too_many_arguments)]
+async fn export_to_html(app: tauri::AppHandle, jobs: tauri::State<'_, ExportJobs>, themes: tauri::State<'_, ThemeState>, workspace: tauri::State<'_, WorkspaceState>, content: String, filename: String, theme: Option<String>, base_dir: Option<String>, root: Option<String>, destination: Option<ExportDestination>, toc: Option<TocOptions>) -> Result<u64, String> {
+    let theme = themes.load(theme.as_deref())?;
+    let paths = confine_export(&workspace, base_dir.as_deref(), root.as_deref(), destination)?;
+    Ok(jobs.spawn(app, ExportFormat::Html, move |job| html_export(job, content, filename, theme, paths, toc.unwrap_or_default())))
 }
 
-// Minimal DOCX export: Markdown'ı düz paragraflara dönüştürerek OOXML yapısında ziple
-#[tauri::command]
-async fn export_to_docx(content: String, filename: String, dark: Option<bool>, base_dir: Option<String>) -> Result<String, String> {
-    use std::io::Write as _;
-    let desktop_dir = dirs::desktop_dir().ok_or("Masaüstü dizini bulunamadı")?;
-    let output_path = desktop_dir.join(format!("{}.docx", filename));
-
-    let file = std::fs::File::create(&output_path).map_err(|e| e.to_string())?;
-    let mut zip = zip::ZipWriter::new(file);
-    let opts = zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
-
-    // İçerik: HTML mi geldi, Markdown mı? HTML değilse Markdown'u HTML'e çevir ve görselleri düzelt
-    let html_raw = if content.trim_start().starts_with("<") { content } else { markdown_to_html(&content)? };
-    let html_body = inline_or_fix_images_cancellable(&html_raw, base_dir.as_deref())?;
-    let is_dark = dark.unwrap_or(false);
-    let afchunk_html = if is_dark {
-        format!(
-            "{}{}{}{}{}",
-            "<!DOCTYPE html>\n<html><head><meta charset=\"UTF-8\"/><style>",
-            "body{font-family:Inter,-apple-system,BlinkMacSystemFont,'Segoe UI',Roboto,Arial,sans-serif;line-height:1.6;color:#e5e7eb;background:#0f172a;}",
-            "h1,h2,h3{font-weight:700;margin:1em 0 .6em;color:#fff;} table{border-collapse:collapse;width:100%;} th,td{border:1px solid #334155;padding:8px;text-align:left;color:#e5e7eb;} th{background:#111827;} code{background:#0b1220;border:1px solid #334155;color:#f59e0b;padding:2px 6px;border-radius:6px;} pre{background:#0b1220;border:1px solid #334155;color:#cbd5e1;padding:12px;border-radius:8px;}",
-            "blockquote{border-left:4px solid #ec4899;padding:8px 12px;background:#111827;color:#cbd5e1;}",
-            "</style></head><body>") + &html_body + "</body></html>"
-    } else {
-        format!(
-            "{}{}{}{}{}",
-            "<!DOCTYPE html>\n<html><head><meta charset=\"UTF-8\"/><style>",
-            "body{font-family:Inter,-apple-system,BlinkMacSystemFont,'Segoe UI',Roboto,Arial,sans-serif;line-height:1.6;color:#111827;background:#ffffff;}",
-            "h1,h2,h3{font-weight:700;margin:1em 0 .6em;} table{border-collapse:collapse;width:100%;} th,td{border:1px solid #e5e7eb;padding:8px;text-align:left;} code{background:#f8f9fa;border:1px solid #e5e7eb;padding:2px 6px;border-radius:6px;} pre{background:#f8f9fa;border:1px solid #e5e7eb;padding:12px;border-radius:8px;}",
-            "blockquote{border-left:4px solid #ec4899;padding:8px 12px;background:#f8f9fa;color:#374151;}",
-            "</style></head><body>") + &html_body + "</body></html>"
-    };
+fn html_export(job: &Job, content: String, filename: String, theme: Theme, paths: ExportPaths, toc: TocOptions) -> Result<ExportResult, String> {
+    let ExportPaths { scope, base_dir, root, destination } = paths;
+    job.stage(ExportStage::Convert)?;
 
-    // [Content_Types].xml
-    zip.start_file("[Content_Types].xml", opts).map_err(|e| e.to_string())?;
-    zip.write_all(br#"<?xml version="1.0" encoding="UTF-8"?>
-<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
-  <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
-  <Default Extension="xml" ContentType="application/xml"/>
-  <Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/>
-  <Override PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml"/>
-  <Override PartName="/docProps/app.xml" ContentType="application/vnd.openxmlformats-officedocument.extended-properties+xml"/>
-  <Override PartName="/word/afchunk1.html" ContentType="text/html"/>
-</Types>"#).map_err(|e| e.to_string())?;
-
-    // _rels/.rels
-    zip.add_directory("_rels/", opts).map_err(|e| e.to_string())?;
-    zip.start_file("_rels/.rels", opts).map_err(|e| e.to_string())?;
-    zip.write_all(br#"<?xml version="1.0" encoding="UTF-8"?>
-<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
-  <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/>
-  <Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties" Target="docProps/core.xml"/>
-  <Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/extended-properties" Target="docProps/app.xml"/>
-</Relationships>"#).map_err(|e| e.to_string())?;
-
-    // docProps/app.xml
-    zip.add_directory("docProps/", opts).map_err(|e| e.to_string())?;
-    zip.start_file("docProps/app.xml", opts).map_err(|e| e.to_string())?;
-    zip.write_all(br#"<?xml version="1.0" encoding="UTF-8"?>
-<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/extended-properties" xmlns:vt="http://schemas.openxmlformats.org/officeDocument/2006/docPropsVTypes">
-  <Application>PigNote</Application>
-  <DocSecurity>0</DocSecurity>
-  <ScaleCrop>false</ScaleCrop>
-  <Company></Company>
-  <LinksUpToDate>false</LinksUpToDate>
-  <SharedDoc>false</SharedDoc>
-  <HyperlinksChanged>false</HyperlinksChanged>
-  <AppVersion>16.0000</AppVersion>
-</Properties>"#).map_err(|e| e.to_string())?;
-
-    // docProps/core.xml
-    let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
-    zip.start_file("docProps/core.xml", opts).map_err(|e| e.to_string())?;
-    let core_xml = format!(
-        "{}{}{}{}{}{}{}",
-        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
-        "<cp:coreProperties ",
-        "xmlns:cp=\"http://schemas.openxmlformats.org/package/2006/metadata/core-properties\" ",
-        "xmlns:dc=\"http://purl.org/dc/elements/1.1/\" xmlns:dcterms=\"http://purl.org/dc/terms/\" xmlns:dcmitype=\"http://purl.org/dc/dcmitype/\" xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\">\n",
-        format!("  <dcterms:created xsi:type=\"dcterms:W3CDTF\">{}</dcterms:created>\n", now),
-        format!("  <dcterms:modified xsi:type=\"dcterms:W3CDTF\">{}</dcterms:modified>\n", now),
-        "  <dc:creator>PigNote</dc:creator>\n  <cp:lastModifiedBy>PigNote</cp:lastModifiedBy>\n</cp:coreProperties>"
-    );
-    zip.write_all(core_xml.as_bytes()).map_err(|e| e.to_string())?;
-
-    // word/_rels/document.xml.rels (aFChunk – dikkat: 'F' büyük)
-    zip.add_directory("word/_rels/", opts).map_err(|e| e.to_string())?;
-    zip.start_file("word/_rels/document.xml.rels", opts).map_err(|e| e.to_string())?;
-    zip.write_all(br#"<?xml version="1.0" encoding="UTF-8"?>
-<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
-  <Relationship Id="rIdHtml1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/aFChunk" Target="afchunk1.html"/>
-</Relationships>"#).map_err(|e| e.to_string())?;
-
-    // word/document.xml
-    zip.add_directory("word/", opts).map_err(|e| e.to_string())?;
-    zip.start_file("word/document.xml", opts).map_err(|e| e.to_string())?;
-
-    // Basit paragraf üretimi: boş satıra göre paragraflara böl
-    fn xml_escape(input: &str) -> String {
-        input
-            .replace('&', "&amp;")
-            .replace('<', "&lt;")
-            .replace('>', "&gt;")
-            .replace('"', "&quot;")
-            .replace('\'', "&apos;")
-    }
-    let mut doc_xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n");
-    doc_xml.push_str("<w:document xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\" xmlns:r=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships\"><w:body>\n");
-    doc_xml.push_str("<w:altChunk r:id=\"rIdHtml1\"/>\n");
-    doc_xml.push_str("<w:sectPr><w:pgSz w:w=\"11906\" w:h=\"16838\"/><w:pgMar w:top=\"1440\" w:right=\"1440\" w:bottom=\"1440\" w:left=\"1440\"/></w:sectPr>");
-    doc_xml.push_str("</w:body></w:document>");
-
-    zip.write_all(doc_xml.as_bytes()).map_err(|e| e.to_string())?;
-    // HTML chunk
-    zip.start_file("word/afchunk1.html", opts).map_err(|e| e.to_string())?;
-    zip.write_all(afchunk_html.as_bytes()).map_err(|e| e.to_string())?;
-    zip.finish().map_err(|e| e.to_string())?;
-
-    Ok(output_path.to_string_lossy().to_string())
-}
+    // Wiki bağlantıları çıktının klasörüne göre göreli yazıldığı için hedef önce belirlenir
+    let resolved = export_path::resolve(&filename, "html", destination.as_ref())?;
+    let links = link_context(&content, root.as_deref(), base_dir.as_deref(), output_dir(&resolved));
+    let (metadata, warnings) = export_metadata(&content);
+    let html_body_raw = if content.trim_start().starts_with("<") { content } else { markdown_to_html(&content, links.as_ref(), &toc)? };
+    job.stage(ExportStage::InlineImages)?;
+    let html_body = inline_or_fix_images_cancellable(job, &html_body_raw, &scope)?;
+
+    let page_html = theme.render(Template::Html, &Page { filename: &filename, metadata: metadata.as_ref(), body: &html_body, watermark: "", brand_footer: "", brand_icon: "", page_css: "" });
 
-// Global export süreci ve iptal flag'i
-static CURRENT_EXPORT_PROCESS: Lazy<Mutex<Option<Child>>> = Lazy::new(|| Mutex::new(None));
-static CANCEL_REQUESTED: AtomicBool = AtomicBool::new(false);
+    job.stage(ExportStage::Render)?;
+    std::fs::write(&resolved.path, page_html).map_err(|e| e.to_string())?;
+    Ok(ExportResult::new(&resolved).with_warnings(warnings))
+}
 
-// Export iptal komutu
+// DOCX export: Markdown AST'sinden başlık, liste, tablo, görsel ve linkleri gerçek Word yapılarıyla yazar
 #[tauri::command]
-async fn cancel_export() -> Result<(), String> {
-    CANCEL_REQUESTED.store(true, Ordering::SeqCst);
-    let mut guard = CURRENT_EXPORT_PROCESS.lock().unwrap();
-    if let Some(child) = guard.as_mut() {
-        let _ = child.kill();
-    }
-    Ok(())
+#[allow(clippy::too_many_arguments)]
+async fn export_to_docx(app: tauri::AppHandle, jobs: tauri::State<'_, ExportJobs>, themes: tauri::State<'_, ThemeState>, workspace: tauri::State<'_, WorkspaceState>, content: String, filename: String, theme: Option<String>, base_dir: Option<String>, root: Option<String>, destination: Option<ExportDestination>, toc: Option<TocOptions>) -> Result<u64, String> {
+    // DOCX şablon kullanmaz; temadan yalnızca açık/koyu renk seçimi alınır
+    let dark = themes.load(theme.as_deref())?.dark;
+    let paths = confine_export(&workspace, base_dir.as_deref(), root.as_deref(), destination)?;
+    Ok(jobs.spawn(app, ExportFormat::Docx, move |job| docx_export(job, content, filename, dark, paths, toc.unwrap_or_default())))
 }
 
-// Gelişmiş markdown to HTML converter
-fn markdown_to_html(markdown: &str) -> Result<String, String> {
-    let mut html = String::new();
-    let lines: Vec<&str> = markdown.lines().collect();
-    let mut i = 0;
-    
-    while i < lines.len() {
-        if CANCEL_REQUESTED.load(Ordering::SeqCst) { return Err("İşlem iptal edildi".to_string()); }
-        let line = lines[i].trim();
-        
-        if line.is_empty() {
-            i += 1;
-            continue;
-        }
-        
-        // Tablo parsing
-        if line.starts_with("|") && line.ends_with("|") {
-            html.push_str("<table>\n");
-            
-            // Tablo başlığı
-            let header_cells: Vec<&str> = line.split("|").filter(|s| !s.trim().is_empty()).collect();
-            html.push_str("<thead>\n<tr>\n");
-            for cell in &header_cells {
-                html.push_str(&format!("<th>{}</th>\n", cell.trim()));
-            }
-            html.push_str("</tr>\n</thead>\n");
-            
-            i += 1;
-            
-            // Ayırıcı satırı atla (| --- | --- |)
-            if i < lines.len() && lines[i].trim().starts_with("|") && lines[i].trim().contains("---") {
-                i += 1;
-            }
-            
-            // Tablo gövdesi
-            html.push_str("<tbody>\n");
-            while i < lines.len() && lines[i].trim().starts_with("|") && lines[i].trim().ends_with("|") {
-                if CANCEL_REQUESTED.load(Ordering::SeqCst) { return Err("İşlem iptal edildi".to_string()); }
-                let row_cells: Vec<&str> = lines[i].split("|").filter(|s| !s.trim().is_empty()).collect();
-                html.push_str("<tr>\n");
-                for cell in &row_cells {
-                    html.push_str(&format!("<td>{}</td>\n", process_inline_markdown(cell.trim())));
-                }
-                html.push_str("</tr>\n");
-                i += 1;
-            }
-            html.push_str("</tbody>\n</table>\n");
-            continue;
-        }
-        
-        // Başlıklar
-        if line.starts_with("###### ") {
-            html.push_str(&format!("<h6>{}</h6>\n", &line[7..]));
-        } else if line.starts_with("##### ") {
-            html.push_str(&format!("<h5>{}</h5>\n", &line[6..]));
-        } else if line.starts_with("#### ") {
-            html.push_str(&format!("<h4>{}</h4>\n", &line[5..]));
-        } else if line.starts_with("### ") {
-            html.push_str(&format!("<h3>{}</h3>\n", &line[4..]));
-        } else if line.starts_with("## ") {
-            html.push_str(&format!("<h2>{}</h2>\n", &line[3..]));
-        } else if line.starts_with("# ") {
-            html.push_str(&format!("<h1>{}</h1>\n", &line[2..]));
-        }
-        // Kod blokları
-        else if line.starts_with("```") {
-            html.push_str("<pre><code>");
-            i += 1;
-            while i < lines.len() && !lines[i].trim().starts_with("```") {
-                html.push_str(&format!("{}\n", lines[i]));
-                i += 1;
-            }
-            html.push_str("</code></pre>\n");
-        }
-        // Liste öğeleri
-        else if line.starts_with("- ") || line.starts_with("* ") {
-            html.push_str("<ul>\n");
-            while i < lines.len() && (lines[i].trim().starts_with("- ") || lines[i].trim().starts_with("* ")) {
-                if CANCEL_REQUESTED.load(Ordering::SeqCst) { return Err("İşlem iptal edildi".to_string()); }
-                let content = &lines[i].trim()[2..];
-                html.push_str(&format!("<li>{}</li>\n", process_inline_markdown(content)));
-                i += 1;
-            }
-            html.push_str("</ul>\n");
-            continue;
-        }
-        // Numaralı liste
-        else if line.matches(char::is_numeric).next().is_some() && line.contains(". ") {
-            html.push_str("<ol>\n");
-            while i < lines.len() && lines[i].trim().matches(char::is_numeric).next().is_some() && lines[i].trim().contains(". ") {
-                if CANCEL_REQUESTED.load(Ordering::SeqCst) { return Err("İşlem iptal edildi".to_string()); }
-                let parts: Vec<&str> = lines[i].trim().splitn(2, ". ").collect();
-                if parts.len() == 2 {
-                    html.push_str(&format!("<li>{}</li>\n", process_inline_markdown(parts[1])));
-                }
-                i += 1;
-            }
-            html.push_str("</ol>\n");
-            continue;
-        }
-        // Alıntılar
-        else if line.starts_with("> ") {
-            html.push_str("<blockquote>\n");
-            while i < lines.len() && lines[i].trim().starts_with("> ") {
-                if CANCEL_REQUESTED.load(Ordering::SeqCst) { return Err("İşlem iptal edildi".to_string()); }
-                let content = &lines[i].trim()[2..];
-                html.push_str(&format!("<p>{}</p>\n", process_inline_markdown(content)));
-                i += 1;
-            }
-            html.push_str("</blockquote>\n");
-            continue;
-        }
-        // Normal paragraflar
-        else {
-            html.push_str(&format!("<p>{}</p>\n", process_inline_markdown(line)));
+fn docx_export(job: &Job, content: String, filename: String, dark: bool, paths: ExportPaths, toc: TocOptions) -> Result<ExportResult, String> {
+    let ExportPaths { scope, base_dir, root, destination } = paths;
+    job.stage(ExportStage::Convert)?;
+    // Eski istemciler önizleme HTML'i gönderebilir; yapı korunamaz ama metin kaybolmaz
+    let markdown = if content.trim_start().starts_with('<') {
+        docx::html_to_plain_markdown(&content)
+    } else {
+        content
+    };
+    let resolved = export_path::resolve(&filename, "docx", destination.as_ref())?;
+    let links = link_context(&markdown, root.as_deref(), base_dir.as_deref(), output_dir(&resolved));
+    let (metadata, warnings) = export_metadata(&markdown);
+    let options = docx::DocxOptions { dark, scope: &scope, links: links.as_ref(), metadata: metadata.as_ref(), toc, cancel: Some(job.cancel_flag()) };
+    job.stage(ExportStage::Render)?;
+    docx::write_docx(&markdown, &options, &resolved.path)?;
+
+    Ok(ExportResult::new(&resolved).with_warnings(warnings))
+}
+
+// Bozuk ön bilgi export'u durdurmaz ama başlık/yazar kaybolacağı için kullanıcıya uyarı olarak döner
+fn export_metadata(content: &str) -> (Option<frontmatter::NoteMetadata>, Vec<String>) {
+    match frontmatter::try_metadata(content) {
+        Ok(metadata) => (metadata, Vec::new()),
+        Err(e) => {
+            log::warn!("Ön bilgi (front matter) okunamadı: {}", e);
+            (None, vec![format!("Ön bilgi (front matter) okunamadı; başlık, yazar ve etiketler belgeye yazılmadı: {}", e)])
         }
-        
-        i += 1;
     }
-    
-    Ok(html)
 }
 
 // HTML içindeki <img src> yollarını düzeltir:
-// - Eğer src data: ile başlıyorsa olduğu gibi bırak
-// - Eğer src mutlak bir yol veya http(s) değilse ve base_dir verilmişse, file:/// ile mutlaklaştır
-// - Eğer dosya okunabiliyorsa base64 inline (data:image/...) olarak gömer
-fn inline_or_fix_images_cancellable(html: &str, base_dir: Option<&str>) -> Result<String, String> {
+// - Eğer src data: ya da http(s) ile başlıyorsa olduğu gibi bırak
+// - Yerel yol (göreli, mutlak ya da file:///) çalışma alanı dışındaysa src boşaltılır; tarayıcı da okuyamasın
+// - Eğer dosya okunabiliyorsa base64 inline (data:image/...) olarak gömer, okunamıyorsa file:/// ile mutlaklaştır
+fn inline_or_fix_images_cancellable(job: &Job, html: &str, scope: &ReadScope) -> Result<String, String> {
     let img_re = Regex::new(r#"<img\s+[^>]*src=[\"']([^\"'>]+)[\"'][^>]*>"#).unwrap();
     let mut result = String::with_capacity(html.len());
     let mut last_end = 0usize;
     for caps in img_re.captures_iter(html) {
-        if CANCEL_REQUESTED.load(Ordering::SeqCst) { return Err("İşlem iptal edildi".to_string()); }
+        job.check()?;
         let m = caps.get(0).unwrap();
         let src = caps.get(1).map(|m| m.as_str()).unwrap_or("");
         // önceki segmenti ekle
         result.push_str(&html[last_end..m.start()]);
 
-        let replacement = if src.starts_with("data:") {
-            m.as_str().to_string()
-        } else if src.starts_with("http://") || src.starts_with("https://") || src.starts_with("file:///") {
+        let replacement = if src.starts_with("data:") || src.starts_with("http://") || src.starts_with("https://") {
             m.as_str().to_string()
-        } else if let Some(base) = base_dir {
-            let path = std::path::Path::new(base).join(src);
+        } else {
+            let path = match scope.check(Path::new(&images::local_path(src))) {
+                Ok(path) => path,
+                Err(e) => {
+                    log::warn!("Görsel export'a eklenmedi: {}", e);
+                    result.push_str(&m.as_str().replacen(src, "", 1));
+                    last_end = m.end();
+                    continue;
+                }
+            };
             if let Ok(bytes) = std::fs::read(&path) {
-                if CANCEL_REQUESTED.load(Ordering::SeqCst) { return Err("İşlem iptal edildi".to_string()); }
+                job.check()?;
                 let b64 = general_purpose::STANDARD.encode(bytes);
                 let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
                 let mime = match ext.as_str() {
@@ -816,10 +411,8 @@ fn inline_or_fix_images_cancellable(html: &str, base_dir: Option<&str>) -> Resul
                 m.as_str().replacen(src, &format!("data:{};base64,{}", mime, b64), 1)
             } else {
                 let abs = path.to_string_lossy().replace('\\', "/");
-                m.as_str().replacen(src, &format!("file:///{}", abs), 1)
+                m.as_str().replacen(src, &format!("file:///{}", abs.trim_start_matches('/')), 1)
             }
-        } else {
-            m.as_str().to_string()
         };
 
         result.push_str(&replacement);
@@ -829,140 +422,10 @@ fn inline_or_fix_images_cancellable(html: &str, base_dir: Option<&str>) -> Resul
     Ok(result)
 }
 
-fn remove_pdf_footer_by_cropping(path: &std::path::Path, trim_bottom_pt: f64) -> Result<(), String> {
-    let mut doc = LoDocument::load(path).map_err(|e| e.to_string())?;
-    let pages = doc.get_pages();
-    for (_num, page_id) in pages {
-        // 1) Okuma aşaması (immut borrow)
-        let media_box: LoObject = {
-            let page_obj_ro = doc.get_object(page_id).map_err(|e| e.to_string())?;
-            let page_dict_ro = match page_obj_ro {
-                LoObject::Dictionary(ref dict) => dict,
-                _ => continue,
-            };
-
-            // Sayfada MediaBox var mı?
-            if let Ok(mb) = page_dict_ro.get(b"MediaBox").cloned() {
-                mb
-            } else {
-                // Parent'ta var mı?
-                if let Ok(parent_ref) = page_dict_ro.get(b"Parent").and_then(|o| o.as_reference()) {
-                    if let Ok(parent_obj) = doc.get_object(parent_ref) {
-                        if let LoObject::Dictionary(ref parent_dict) = parent_obj {
-                            if let Ok(mb2) = parent_dict.get(b"MediaBox").cloned() {
-                                mb2
-                            } else {
-                                LoObject::Array(vec![
-                                    LoObject::Integer(0),
-                                    LoObject::Integer(0),
-                                    LoObject::Integer(595),
-                                    LoObject::Integer(842),
-                                ])
-                            }
-                        } else {
-                            LoObject::Array(vec![
-                                LoObject::Integer(0),
-                                LoObject::Integer(0),
-                                LoObject::Integer(595),
-                                LoObject::Integer(842),
-                            ])
-                        }
-                    } else {
-                        LoObject::Array(vec![
-                            LoObject::Integer(0),
-                            LoObject::Integer(0),
-                            LoObject::Integer(595),
-                            LoObject::Integer(842),
-                        ])
-                    }
-                } else {
-                    LoObject::Array(vec![
-                        LoObject::Integer(0),
-                        LoObject::Integer(0),
-                        LoObject::Integer(595),
-                        LoObject::Integer(842),
-                    ])
-                }
-            }
-        };
-
-        // MediaBox değerlerini oku
-        let arr = match media_box {
-            LoObject::Array(arr) => arr,
-            _ => continue,
-        };
-        if arr.len() != 4 { continue; }
-
-        let to_f64 = |o: &LoObject| -> Option<f64> {
-            match o {
-                LoObject::Integer(i) => Some(*i as f64),
-                LoObject::Real(r) => Some((*r) as f64),
-                _ => None,
-            }
-        };
-        let llx = to_f64(&arr[0]).unwrap_or(0.0);
-        let lly = to_f64(&arr[1]).unwrap_or(0.0);
-        let urx = to_f64(&arr[2]).unwrap_or(595.0);
-        let ury = to_f64(&arr[3]).unwrap_or(842.0);
-
-        let trim = trim_bottom_pt.max(0.0);
-        let new_lly = (lly + trim).min(ury - 1.0);
-        let new_crop = LoObject::Array(vec![
-            LoObject::Real(llx as f32),
-            LoObject::Real(new_lly as f32),
-            LoObject::Real(urx as f32),
-            LoObject::Real(ury as f32),
-        ]);
-
-        // 2) Yazma aşaması (mut borrow)
-        let page_obj_mut = doc.get_object_mut(page_id).map_err(|e| e.to_string())?;
-        let page_dict_mut = match page_obj_mut {
-            LoObject::Dictionary(ref mut dict) => dict,
-            _ => continue,
-        };
-        page_dict_mut.set(b"MediaBox", new_crop.clone());
-        page_dict_mut.set(b"CropBox", new_crop.clone());
-        page_dict_mut.set(b"TrimBox", new_crop.clone());
-        page_dict_mut.set(b"BleedBox", new_crop.clone());
-        page_dict_mut.set(b"ArtBox", new_crop.clone());
-    }
-    doc.save(path).map_err(|e| e.to_string())?;
-    Ok(())
-}
-
-fn process_inline_markdown(text: &str) -> String {
-    let mut result = text.to_string();
-
-    // Linkler [text](url)
-    let link_pattern = Regex::new(r"\[([^\]]+)\]\(([^)]+)\)").unwrap();
-    result = link_pattern
-        .replace_all(&result, "<a href=\"$2\">$1</a>")
-        .to_string();
-
-    // Satır içi kod `code`
-    let code_re = Regex::new(r"`([^`]+)`").unwrap();
-    result = code_re
-        .replace_all(&result, "<code>$1</code>")
-        .to_string();
-
-    // Kalın **bold**
-    let bold_re = Regex::new(r"\*\*([^*]+)\*\*").unwrap();
-    result = bold_re
-        .replace_all(&result, "<strong>$1</strong>")
-        .to_string();
-
-    // İtalik *italic* (çift yıldız hariç tutmaya çalış)
-    let italic_re = Regex::new(r"(?<!\*)\*([^*]+)\*(?!\*)").unwrap();
-    result = italic_re
-        .replace_all(&result, "<em>$1</em>")
-        .to_string();
-
-    result
-}
-
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
   tauri::Builder::default()
+    .plugin(tauri_plugin_dialog::init())
     .setup(|app| {
       if cfg!(debug_assertions) {
         app.handle().plugin(
@@ -971,21 +434,73 @@ pub fn run() {
             .build(),
         )?;
       }
+      let last_dir_store = app.path().app_config_dir().ok().map(|dir| dir.join("last_dialog_dir"));
+      let provider = dialogs::TauriDialogProvider::new(app.handle().clone());
+      app.manage(dialogs::DialogState::new(Box::new(provider), last_dir_store));
+      app.manage(watcher::WatcherState::default());
+      let index_dir = app.path().app_data_dir().ok().map(|dir| dir.join("search-index"));
+      app.manage(search_index::SearchIndexState::new(index_dir));
+      let history_dir = app.path().app_data_dir().ok().map(|dir| dir.join("history"));
+      app.manage(history::HistoryState::new(history_dir));
+      app.manage(encryption::EncryptionState::default());
+      app.manage(export_jobs::ExportJobs::default());
+      let themes_dir = app.path().app_config_dir().ok().map(|dir| dir.join("themes"));
+      app.manage(themes::ThemeState::new(themes_dir));
+      encryption::EncryptionState::spawn_auto_lock(app.handle().clone());
+      let workspace_store = app.path().app_config_dir().ok().map(|dir| dir.join("workspace_root"));
+      app.manage(workspace::WorkspaceState::new(workspace_store));
       Ok(())
     })
     .invoke_handler(tauri::generate_handler![
         read_file,
         write_file,
-        list_directory,
-        delete_file,
-        open_file_dialog,
-        save_file_dialog,
+        listing::list_directory,
+        recycle::delete_file,
+        recycle::list_trash,
+        recycle::restore_from_trash,
+        dialogs::open_file_dialog,
+        dialogs::save_file_dialog,
+        dialogs::open_workspace_dialog,
+        workspace::get_workspace,
         create_directory,
         rename_file,
         export_to_pdf,
         export_to_html,
         export_to_docx,
-        cancel_export
+        export_jobs::cancel_export,
+        export_jobs::list_export_jobs,
+        export_jobs::set_export_limit,
+        themes::list_export_themes,
+        themes::get_themes_dir,
+        browser::detect_pdf_engine,
+        safe_write::file_fingerprint,
+        watcher::watch_workspace,
+        watcher::unwatch_workspace,
+        search::search_workspace,
+        search::cancel_search,
+        search_index::query_search_index,
+        search_index::rebuild_search_index,
+        wikilinks::get_backlinks,
+        wikilinks::list_unresolved_links,
+        wikilinks::get_link_graph,
+        tags::list_tags,
+        tags::find_notes_by_tag,
+        tags::rename_tag,
+        git::git_status,
+        git::git_stage,
+        git::git_unstage,
+        git::git_commit,
+        git::git_file_log,
+        git::git_diff,
+        history::list_versions,
+        history::read_version,
+        history::diff_versions,
+        history::restore_version,
+        encryption::unlock_notes,
+        encryption::lock_notes,
+        encryption::encryption_status,
+        encryption::encrypt_note,
+        encryption::decrypt_note
     ])
     .run(tauri::generate_context!())
     .expect("error while running tauri application");
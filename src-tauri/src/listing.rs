@@ -0,0 +1,257 @@
+// Gezgin için klasör listeleme: zaman damgaları, doğal sıralama, uzantı/glob süzgeçleri,
+// gizli dosya ve .gitignore desteği, isteğe bağlı derinlik sınırlı ağaç
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs::{self, Metadata};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::State;
+
+use crate::frontmatter::{self, NoteMetadata};
+use crate::workspace::WorkspaceState;
+
+// Ağaç modunda derinlik verilmezse
+const DEFAULT_MAX_DEPTH: usize = 8;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileInfo {
+    pub name: String,
+    pub path: String,
+    pub is_directory: bool,
+    pub size: Option<u64>,
+    // Unix zamanı (saniye); dosya sistemi desteklemiyorsa None
+    pub modified: Option<String>,
+    pub created: Option<String>,
+    // Yalnızca istendiğinde, ön bilgisi olan notlar için
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<NoteMetadata>,
+    // Ağaç modunda klasörün içeriği; derinlik sınırındaki klasörlerde None
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<FileInfo>>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortKey {
+    #[default]
+    Name,
+    Modified,
+    Created,
+    Size,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ListOptions {
+    pub sort_by: SortKey,
+    pub descending: bool,
+    pub folders_first: bool,
+    // Yalnızca dosyalara uygulanır; boşsa hepsi
+    pub extensions: Vec<String>,
+    // Dosya adına uygulanan glob kalıpları (ör. "*.md", "2024-*")
+    pub patterns: Vec<String>,
+    // Nokta ile başlayan dosya ve klasörler
+    pub show_hidden: bool,
+    pub respect_gitignore: bool,
+    pub recursive: bool,
+    // Ağaç modunda kaç seviye inileceği (1 = yalnızca klasörün kendisi)
+    pub max_depth: Option<usize>,
+    pub include_metadata: bool,
+}
+
+impl Default for ListOptions {
+    fn default() -> Self {
+        ListOptions {
+            sort_by: SortKey::Name,
+            descending: false,
+            folders_first: true,
+            extensions: Vec::new(),
+            patterns: Vec::new(),
+            show_hidden: false,
+            respect_gitignore: false,
+            recursive: false,
+            max_depth: None,
+            include_metadata: false,
+        }
+    }
+}
+
+fn timestamp(time: std::io::Result<SystemTime>) -> Option<String> {
+    let seconds = time.ok()?.duration_since(UNIX_EPOCH).ok()?.as_secs();
+    Some(seconds.to_string())
+}
+
+// "not2" < "not10"; büyük/küçük harf duyarsız, eşitlikte özgün yazım belirler
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (lower_a, lower_b) = (a.to_lowercase(), b.to_lowercase());
+    let (mut left, mut right) = (lower_a.chars().peekable(), lower_b.chars().peekable());
+    loop {
+        match (left.peek().copied(), right.peek().copied()) {
+            (None, None) => return a.cmp(b),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let take_number = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+                    let mut digits = String::new();
+                    while let Some(c) = chars.next_if(char::is_ascii_digit) {
+                        digits.push(c);
+                    }
+                    digits.trim_start_matches('0').to_string()
+                };
+                let (x, y) = (take_number(&mut left), take_number(&mut right));
+                let ordering = x.len().cmp(&y.len()).then_with(|| x.cmp(&y));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(x), Some(y)) => {
+                if x != y {
+                    return x.cmp(&y);
+                }
+                left.next();
+                right.next();
+            }
+        }
+    }
+}
+
+struct Listing {
+    options: ListOptions,
+    extensions: Vec<String>,
+    patterns: Option<GlobSet>,
+}
+
+impl Listing {
+    fn new(options: ListOptions) -> Result<Self, String> {
+        let extensions = options.extensions.iter().map(|e| e.trim().trim_start_matches('.').to_lowercase()).filter(|e| !e.is_empty()).collect();
+        let patterns = if options.patterns.is_empty() {
+            None
+        } else {
+            let mut builder = GlobSetBuilder::new();
+            for pattern in &options.patterns {
+                let glob = GlobBuilder::new(pattern.trim())
+                    .case_insensitive(true)
+                    .build()
+                    .map_err(|e| format!("Geçersiz dosya kalıbı '{}': {}", pattern, e))?;
+                builder.add(glob);
+            }
+            Some(builder.build().map_err(|e| e.to_string())?)
+        };
+        Ok(Listing { options, extensions, patterns })
+    }
+
+    fn wants_file(&self, path: &Path) -> bool {
+        if !self.extensions.is_empty() {
+            let extension = path.extension().map(|e| e.to_string_lossy().to_lowercase()).unwrap_or_default();
+            if !self.extensions.contains(&extension) {
+                return false;
+            }
+        }
+        match (&self.patterns, path.file_name()) {
+            (Some(patterns), Some(name)) => patterns.is_match(name),
+            _ => true,
+        }
+    }
+
+    fn info(&self, path: &Path, metadata: &Metadata) -> FileInfo {
+        let is_directory = metadata.is_dir();
+        FileInfo {
+            name: path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+            path: path.to_string_lossy().to_string(),
+            is_directory,
+            size: if metadata.is_file() { Some(metadata.len()) } else { None },
+            modified: timestamp(metadata.modified()),
+            created: timestamp(metadata.created()),
+            metadata: if self.options.include_metadata && !is_directory { frontmatter::read_metadata(path) } else { None },
+            children: None,
+        }
+    }
+
+    fn compare(&self, a: &FileInfo, b: &FileInfo) -> Ordering {
+        if self.options.folders_first && a.is_directory != b.is_directory {
+            return b.is_directory.cmp(&a.is_directory);
+        }
+        let by_name = || natural_cmp(&a.name, &b.name);
+        let ordering = match self.options.sort_by {
+            SortKey::Name => by_name(),
+            // Zaman damgaları aynı uzunlukta olmayabilir; sayı olarak karşılaştırılır
+            SortKey::Modified => seconds(&a.modified).cmp(&seconds(&b.modified)).then_with(by_name),
+            SortKey::Created => seconds(&a.created).cmp(&seconds(&b.created)).then_with(by_name),
+            SortKey::Size => a.size.cmp(&b.size).then_with(by_name),
+        };
+        if self.options.descending { ordering.reverse() } else { ordering }
+    }
+
+    // Gizli dosya ve .gitignore kuralları yürüyücü tarafından uygulanır; sembolik bağlı klasörlere girilmez
+    fn run(&self, root: &Path) -> Vec<FileInfo> {
+        let depth = if self.options.recursive { self.options.max_depth.unwrap_or(DEFAULT_MAX_DEPTH).max(1) } else { 1 };
+        let respect = self.options.respect_gitignore;
+        let walker = WalkBuilder::new(root)
+            .max_depth(Some(depth))
+            .hidden(!self.options.show_hidden)
+            .git_ignore(respect)
+            .git_exclude(respect)
+            .git_global(false)
+            .parents(respect)
+            .require_git(false)
+            .ignore(false)
+            .follow_links(false)
+            .build();
+
+        let mut by_parent: HashMap<PathBuf, Vec<FileInfo>> = HashMap::new();
+        for entry in walker {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    log::warn!("Klasör girdisi okunamadı: {}", e);
+                    continue;
+                }
+            };
+            if entry.depth() == 0 {
+                continue;
+            }
+            let path = entry.path();
+            // Sembolik bağlar hedefin türünü ve boyutunu göstersin; kırık bağlar bağın kendisini
+            let Ok(metadata) = fs::metadata(path).or_else(|_| fs::symlink_metadata(path)) else { continue };
+            if !metadata.is_dir() && !self.wants_file(path) {
+                continue;
+            }
+            let mut info = self.info(path, &metadata);
+            if self.options.recursive && entry.depth() < depth && entry.file_type().is_some_and(|t| t.is_dir()) {
+                info.children = Some(Vec::new());
+            }
+            let parent = path.parent().unwrap_or(root).to_path_buf();
+            by_parent.entry(parent).or_default().push(info);
+        }
+        self.assemble(root, &mut by_parent)
+    }
+
+    fn assemble(&self, dir: &Path, by_parent: &mut HashMap<PathBuf, Vec<FileInfo>>) -> Vec<FileInfo> {
+        let mut entries = by_parent.remove(dir).unwrap_or_default();
+        for entry in &mut entries {
+            if entry.children.is_some() {
+                entry.children = Some(self.assemble(Path::new(&entry.path), by_parent));
+            }
+        }
+        entries.sort_by(|a, b| self.compare(a, b));
+        entries
+    }
+}
+
+fn seconds(timestamp: &Option<String>) -> Option<u64> {
+    timestamp.as_deref().and_then(|t| t.parse().ok())
+}
+
+// Klasör listeleme; ağaç modunda alt klasörler `children` içinde gelir
+#[tauri::command]
+pub async fn list_directory(workspace: State<'_, WorkspaceState>, path: String, options: Option<ListOptions>) -> Result<Vec<FileInfo>, String> {
+    workspace.check(&path)?;
+    let root = PathBuf::from(&path);
+    // Okunamayan klasörün hatası olduğu gibi dönsün (yürüyücü yalnızca uyarı verir)
+    fs::read_dir(&root).map_err(|e| e.to_string())?;
+    let listing = Listing::new(options.unwrap_or_default())?;
+    Ok(listing.run(&root))
+}
@@ -0,0 +1,172 @@
+use comrak::adapters::{HeadingAdapter, HeadingMeta};
+use comrak::nodes::Sourcepos;
+use comrak::{format_html_with_plugins, parse_document, Arena, Options, Plugins};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::io::{self, Write};
+use std::ops::Range;
+use std::sync::Mutex;
+
+use crate::frontmatter;
+use crate::tags;
+use crate::toc::{self, Heading, TocOptions};
+use crate::wikilinks::{self, LinkContext};
+
+// Editördeki ngx-markdown önizlemesiyle aynı davranış için CommonMark + GFM seçenekleri.
+// Önizleme `gfm: true, breaks: false` ve sanitize kapalı çalışıyor; burada da aynısı geçerli.
+pub fn options() -> Options<'static> {
+    let mut options = Options::default();
+    // GFM uzantıları: tablolar (hizalama dahil), üstü çizili, otomatik linkler, görev listeleri
+    options.extension.table = true;
+    options.extension.strikethrough = true;
+    options.extension.autolink = true;
+    options.extension.tasklist = true;
+    options.extension.footnotes = true;
+    // [[Not|takma ad]]: takma ad borudan sonra gelir
+    options.extension.wikilinks_title_after_pipe = true;
+    // Ham HTML blokları önizlemede olduğu gibi geçiyor, export'ta da korunmalı
+    options.render.unsafe_ = true;
+    options.render.hardbreaks = false;
+    options.render.github_pre_lang = false;
+    options
+}
+
+static INLINE_CODE: Lazy<Regex> = Lazy::new(|| Regex::new(r"`[^`\n]*`").unwrap());
+
+// Ham metni satırlarına ayırır (satır sonlarıyla birlikte); kod bloğu çitleri ve içleri düz yazı sayılmaz.
+// Etiket ve wiki bağlantısı taraması AST'ye ihtiyaç duymadan bu satırlar üzerinden yapılır.
+pub fn classify_lines(content: &str) -> Vec<(&str, bool)> {
+    let mut lines = Vec::new();
+    let mut fence: Option<&str> = None;
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            lines.push((line, false));
+        } else if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+            fence = Some(marker);
+            lines.push((line, false));
+        } else {
+            lines.push((line, true));
+        }
+    }
+    lines
+}
+
+// Satırdaki `satır içi kod` aralıkları
+pub fn inline_code_ranges(line: &str) -> Vec<Range<usize>> {
+    INLINE_CODE.find_iter(line).map(|m| m.range()).collect()
+}
+
+// Başlık metninden bağlantı kimliği ("Giriş Bölümü" → "giris-bolumu"). Türkçe harfler ASCII karşılıklarına
+// çevrilir; böylece kimlik URL'de ve PDF hedeflerinde kodlanmadan kullanılabilir.
+pub fn heading_slug(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    for c in text.chars() {
+        let c = match c {
+            'ç' | 'Ç' => 'c',
+            'ğ' | 'Ğ' => 'g',
+            'ı' | 'I' | 'İ' | 'î' | 'Î' => 'i',
+            'ö' | 'Ö' => 'o',
+            'ş' | 'Ş' => 's',
+            'ü' | 'Ü' | 'û' | 'Û' => 'u',
+            'â' | 'Â' => 'a',
+            c => c,
+        };
+        if c.is_alphanumeric() || c == '_' {
+            slug.extend(c.to_lowercase());
+        } else if (c.is_whitespace() || c == '-') && !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+// Başlıklara önceden hesaplanan kimlikleri belge sırasıyla verir
+struct HeadingIds(Mutex<std::vec::IntoIter<String>>);
+
+impl HeadingAdapter for HeadingIds {
+    fn enter(&self, output: &mut dyn Write, heading: &HeadingMeta, _sourcepos: Option<Sourcepos>) -> io::Result<()> {
+        match self.0.lock().unwrap().next() {
+            Some(id) => write!(output, "<h{} id=\"{}\">", heading.level, id),
+            None => write!(output, "<h{}>", heading.level),
+        }
+    }
+
+    fn exit(&self, output: &mut dyn Write, heading: &HeadingMeta) -> io::Result<()> {
+        writeln!(output, "</h{}>", heading.level)
+    }
+}
+
+// Markdown'ı HTML'e çevirir (CommonMark spesifikasyonu + GFM uzantıları); wiki bağlantıları `links` ile çözülür,
+// başlıklar kimlik alır ve "[TOC]" içindekiler listesine dönüşür
+pub fn markdown_to_html(markdown: &str, links: Option<&LinkContext>, toc: &TocOptions) -> Result<String, String> {
+    markdown_to_html_with_headings(markdown, links, toc).map(|(html, _)| html)
+}
+
+// Başlıklar da döner; tarayıcı PDF'inin yer imleri bunlardan yazılır
+pub fn markdown_to_html_with_headings(markdown: &str, links: Option<&LinkContext>, toc: &TocOptions) -> Result<(String, Vec<Heading>), String> {
+    let arena = Arena::new();
+    let options = options();
+    let root = parse_document(&arena, frontmatter::body(markdown), &options);
+    wikilinks::resolve_in_document(root, links);
+    // Etiketler başlık metnini böldüğü için kimlikler ondan önce hesaplanır
+    let headings = toc::expand_in_document(&arena, root, toc);
+    tags::style_in_document(&arena, root);
+
+    let ids = HeadingIds(Mutex::new(headings.iter().map(|heading| heading.id.clone()).collect::<Vec<_>>().into_iter()));
+    let mut plugins = Plugins::default();
+    plugins.render.heading_adapter = Some(&ids);
+    let mut html = Vec::new();
+    format_html_with_plugins(root, &options, &mut html, &plugins).map_err(|e| e.to_string())?;
+    let html = String::from_utf8(html).map_err(|e| e.to_string())?;
+    Ok((html, headings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    // CommonMark 0.31 spesifikasyonu ve GFM eklerinden alınmış örnekler (spec.json biçiminde). Tam spesifikasyon
+    // comrak'ın kendi uyumluluk testlerinde doğrulanıyor; buradaki örnekler bizim seçeneklerimizin (ham HTML açık,
+    // github_pre_lang kapalı, GFM uzantıları) ve markdown_to_html'in eklerinin spesifikasyon çıktısını bozmadığını
+    // denetler. Etiket (#etiket) ve [[wiki]] biçimlendirmesi bilinçli sapma olduğundan bu örneklerde yer almaz.
+    const SPEC_EXAMPLES: &str = include_str!("../tests/fixtures/commonmark_gfm_examples.json");
+
+    #[derive(Deserialize)]
+    struct Example {
+        section: String,
+        markdown: String,
+        html: String,
+    }
+
+    static HEADING_ID: Lazy<Regex> = Lazy::new(|| Regex::new(r#"<h([1-6]) id="[^"]*">"#).unwrap());
+
+    fn render(markdown: &str) -> String {
+        markdown_to_html(markdown, None, &TocOptions::default()).unwrap()
+    }
+
+    #[test]
+    fn follows_commonmark_and_gfm_examples() {
+        let examples: Vec<Example> = serde_json::from_str(SPEC_EXAMPLES).unwrap();
+        let failures: Vec<String> = examples
+            .iter()
+            .enumerate()
+            .filter_map(|(n, example)| {
+                // Başlık kimlikleri spesifikasyonda yok; ayrıca aşağıda denetleniyor
+                let html = HEADING_ID.replace_all(&render(&example.markdown), "<h$1>").to_string();
+                (html != example.html).then(|| format!("#{} {}\n{:?}\nbeklenen: {:?}\nçıkan:    {:?}", n + 1, example.section, example.markdown, example.html, html))
+            })
+            .collect();
+        assert!(failures.is_empty(), "{} örnek uymadı:\n{}", failures.len(), failures.join("\n\n"));
+    }
+
+    #[test]
+    fn headings_get_ids_and_front_matter_is_skipped() {
+        let html = render("---\ntitle: Not\n---\n# Giriş Bölümü\n\nMetin\n");
+        assert_eq!(html, "<h1 id=\"giris-bolumu\">Giriş Bölümü</h1>\n<p>Metin</p>\n");
+    }
+}
@@ -0,0 +1,153 @@
+// PDF sayfa düzeni: kağıt boyutu, yön, kenar boşlukları ve içerik ölçeği. Tarayıcı motoruna temanın
+// stilinden sonra gelen bir @page kuralı olarak, yerleşik motora sayfa ölçüleri olarak verilir.
+// Varsayılanlar önceki çıktıyla aynıdır: dikey A4, her kenarda 12mm, %100 ölçek.
+use serde::Deserialize;
+
+// Chrome'un yazdırma ölçeği sınırları
+const MIN_SCALE: f32 = 0.1;
+const MAX_SCALE: f32 = 2.0;
+const MIN_PAPER_MM: f32 = 50.0;
+const MAX_PAPER_MM: f32 = 1500.0;
+// Kenar boşluklarından sonra kalması gereken en küçük içerik alanı
+const MIN_CONTENT_MM: f32 = 20.0;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaperSize {
+    A3,
+    #[default]
+    A4,
+    A5,
+    Letter,
+    // Genişlik ve yükseklik PageSetup'tan
+    Custom,
+}
+
+impl PaperSize {
+    // Dikey ölçüler (mm)
+    fn dimensions(self) -> Option<(f32, f32)> {
+        match self {
+            PaperSize::A3 => Some((297.0, 420.0)),
+            PaperSize::A4 => Some((210.0, 297.0)),
+            PaperSize::A5 => Some((148.0, 210.0)),
+            PaperSize::Letter => Some((215.9, 279.4)),
+            PaperSize::Custom => None,
+        }
+    }
+}
+
+// mm
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Margins {
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub left: f32,
+}
+
+impl Default for Margins {
+    fn default() -> Self {
+        Margins { top: 12.0, right: 12.0, bottom: 12.0, left: 12.0 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PageSetup {
+    pub size: PaperSize,
+    // Yalnızca özel boyutta kullanılır (mm, dikey yöndeki gibi)
+    pub width: Option<f32>,
+    pub height: Option<f32>,
+    // Geniş tablolar için: genişlik ve yükseklik yer değiştirir
+    pub landscape: bool,
+    pub margins: Margins,
+    // İçerik ölçeği (1 = %100); kenar boşlukları, filigran ve alt bilgi ölçeklenmez
+    pub scale: f32,
+}
+
+impl Default for PageSetup {
+    fn default() -> Self {
+        PageSetup { size: PaperSize::default(), width: None, height: None, landscape: false, margins: Margins::default(), scale: 1.0 }
+    }
+}
+
+impl PageSetup {
+    // Export başlamadan çağrılır; hatalı ayarla yarım PDF üretilmesin
+    pub fn validate(&self) -> Result<(), String> {
+        if self.size == PaperSize::Custom {
+            let (Some(width), Some(height)) = (self.width, self.height) else {
+                return Err("Özel kağıt boyutu için genişlik ve yükseklik gerekli".to_string());
+            };
+            let valid = |value: f32| (MIN_PAPER_MM..=MAX_PAPER_MM).contains(&value);
+            if !valid(width) || !valid(height) {
+                return Err(format!("Kağıt boyutu {}-{} mm arasında olmalı", MIN_PAPER_MM, MAX_PAPER_MM));
+            }
+        }
+        let margins = self.margins;
+        if [margins.top, margins.right, margins.bottom, margins.left].iter().any(|margin| !margin.is_finite() || *margin < 0.0) {
+            return Err("Kenar boşlukları negatif olamaz".to_string());
+        }
+        let (width, height) = self.paper_mm();
+        if width - margins.left - margins.right < MIN_CONTENT_MM || height - margins.top - margins.bottom < MIN_CONTENT_MM {
+            return Err("Kenar boşlukları sayfada içerik için yer bırakmıyor".to_string());
+        }
+        if !(MIN_SCALE..=MAX_SCALE).contains(&self.scale) {
+            return Err(format!("Ölçek {} ile {} arasında olmalı", MIN_SCALE, MAX_SCALE));
+        }
+        Ok(())
+    }
+
+    // Yön uygulanmış kağıt ölçüleri (mm); özel boyut eksikse A4
+    pub fn paper_mm(&self) -> (f32, f32) {
+        let (width, height) = match (self.size.dimensions(), self.width, self.height) {
+            (Some(dimensions), _, _) => dimensions,
+            (None, Some(width), Some(height)) => (width, height),
+            (None, _, _) => (210.0, 297.0),
+        };
+        if self.landscape {
+            (height, width)
+        } else {
+            (width, height)
+        }
+    }
+
+    // Tarayıcı motoru için; temanın @page kuralından sonra geldiği için onu geçersiz kılar.
+    // `footer_band_mm` alt kenar boşluğuna eklenir: alt bilgi bu şeritte durur, içerik altına akmaz.
+    pub fn css(&self, footer_band_mm: f32) -> String {
+        let (width, height) = self.paper_mm();
+        let margins = self.margins;
+        let mut css = format!(
+            "@media print {{\n    @page {{ size: {}mm {}mm; margin: {}mm {}mm {}mm {}mm; }}\n",
+            width,
+            height,
+            margins.top,
+            margins.right,
+            margins.bottom + footer_band_mm,
+            margins.left
+        );
+        if self.scale != 1.0 {
+            css.push_str(&format!("    .print-container {{ zoom: {}; }}\n", self.scale));
+        }
+        css.push_str("}\n");
+        css
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn css_reserves_footer_band_in_bottom_margin() {
+        let page = PageSetup::default();
+        assert!(page.css(0.0).contains("@page { size: 210mm 297mm; margin: 12mm 12mm 12mm 12mm; }"));
+        assert!(page.css(6.0).contains("@page { size: 210mm 297mm; margin: 12mm 12mm 18mm 12mm; }"));
+        assert!(!page.css(0.0).contains("zoom"));
+
+        let page = PageSetup { landscape: true, scale: 0.8, margins: Margins { bottom: 20.0, ..Margins::default() }, ..PageSetup::default() };
+        let css = page.css(6.0);
+        assert!(css.contains("size: 297mm 210mm; margin: 12mm 12mm 26mm 12mm;"), "{css}");
+        assert!(css.contains(".print-container { zoom: 0.8; }"));
+    }
+}
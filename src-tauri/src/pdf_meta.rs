@@ -0,0 +1,291 @@
+// PDF yer imleri (outline) ve belge bilgisi. Her iki PDF motorunun çıktısına lopdf ile yazılır: yer
+// imleri başlık düzenini (h1 > h2 > ...) izler ve başlığın sayfadaki konumuna gider; başlık, yazar, konu
+// ve anahtar kelimeler hem Info sözlüğüne hem XMP meta verisine girer.
+use lopdf::{dictionary, Dictionary, Document, Object, ObjectId, Stream, StringFormat};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::frontmatter::NoteMetadata;
+use crate::themes::escape_html;
+use crate::toc::Heading;
+
+const PRODUCER: &str = "PigNote";
+// Bozuk ya da döngülü ad ağaçlarında sonsuza inilmesin
+const NAME_TREE_DEPTH: usize = 16;
+
+pub struct OutlineItem {
+    pub level: u8,
+    pub title: String,
+    pub page: ObjectId,
+    // Başlığın PDF koordinatlarındaki üst kenarı; bilinmiyorsa sayfanın tamamı gösterilir
+    pub top: Option<f32>,
+}
+
+// Belge bilgisi metinleri: ASCII dışı karakter varsa BOM'lu UTF-16BE
+pub fn text_string(text: &str) -> Object {
+    if text.is_ascii() {
+        return Object::String(text.as_bytes().to_vec(), StringFormat::Literal);
+    }
+    let mut bytes = vec![0xFE, 0xFF];
+    bytes.extend(text.encode_utf16().flat_map(|unit| unit.to_be_bytes()));
+    Object::String(bytes, StringFormat::Hexadecimal)
+}
+
+// Atlanan düzeyler (h1'den sonra h3) bir alt düzey sayılır; tüm yer imleri açık başlar
+pub fn write_outline(doc: &mut Document, items: &[OutlineItem]) -> Result<(), String> {
+    if items.is_empty() {
+        return Ok(());
+    }
+    let outlines_id = doc.new_object_id();
+    let ids: Vec<ObjectId> = items.iter().map(|_| doc.new_object_id()).collect();
+
+    let mut parents: Vec<Option<usize>> = Vec::with_capacity(items.len());
+    let mut stack: Vec<usize> = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        while stack.last().is_some_and(|&top| items[top].level >= item.level) {
+            stack.pop();
+        }
+        parents.push(stack.last().copied());
+        stack.push(index);
+    }
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); items.len()];
+    let mut roots = Vec::new();
+    for (index, parent) in parents.iter().enumerate() {
+        match parent {
+            Some(parent) => children[*parent].push(index),
+            None => roots.push(index),
+        }
+    }
+    // Açık bir öğenin Count'u görünen tüm alt öğelerin sayısıdır; çocuklar hep ebeveynden sonra gelir
+    let mut counts = vec![0i64; items.len()];
+    for index in (0..items.len()).rev() {
+        counts[index] = children[index].iter().map(|child| 1 + counts[*child]).sum();
+    }
+
+    let mut dicts: Vec<Dictionary> = items
+        .iter()
+        .zip(&parents)
+        .map(|(item, parent)| {
+            let dest: Vec<Object> = match item.top {
+                Some(top) => vec![item.page.into(), "XYZ".into(), Object::Null, top.into(), Object::Null],
+                None => vec![item.page.into(), "Fit".into()],
+            };
+            dictionary! {
+                "Title" => text_string(&item.title),
+                "Parent" => parent.map(|parent| ids[parent]).unwrap_or(outlines_id),
+                "Dest" => dest,
+            }
+        })
+        .collect();
+    let link_siblings = |dicts: &mut Vec<Dictionary>, siblings: &[usize]| {
+        for pair in siblings.windows(2) {
+            dicts[pair[0]].set("Next", ids[pair[1]]);
+            dicts[pair[1]].set("Prev", ids[pair[0]]);
+        }
+    };
+    link_siblings(&mut dicts, &roots);
+    for (index, kids) in children.iter().enumerate() {
+        if let (Some(first), Some(last)) = (kids.first(), kids.last()) {
+            dicts[index].set("First", ids[*first]);
+            dicts[index].set("Last", ids[*last]);
+            dicts[index].set("Count", counts[index]);
+            link_siblings(&mut dicts, kids);
+        }
+    }
+    for (id, dict) in ids.iter().zip(dicts) {
+        doc.objects.insert(*id, Object::Dictionary(dict));
+    }
+    let (first, last) = (roots[0], roots[roots.len() - 1]);
+    doc.objects.insert(
+        outlines_id,
+        Object::Dictionary(dictionary! {
+            "Type" => "Outlines",
+            "First" => ids[first],
+            "Last" => ids[last],
+            "Count" => items.len() as i64,
+        }),
+    );
+
+    let catalog = doc.catalog_mut().map_err(|e| e.to_string())?;
+    catalog.set("Outlines", outlines_id);
+    catalog.set("PageMode", "UseOutlines");
+    Ok(())
+}
+
+// Ön bilgide başlık yoksa PDF'in dosya adı kullanılır; önceki Info ve XMP (tarayıcınınki) değiştirilir
+pub fn write_info(doc: &mut Document, metadata: Option<&NoteMetadata>, output_path: &Path) -> Result<(), String> {
+    let metadata = metadata.cloned().unwrap_or_default();
+    let file_title = output_path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+    let title = metadata.title.clone().unwrap_or(file_title);
+    let keywords = metadata.tags.join(", ");
+    let now = chrono::Utc::now().naive_utc();
+    let created = metadata.date_time();
+
+    let mut info = dictionary! {
+        "Title" => text_string(&title),
+        "Creator" => text_string(PRODUCER),
+        "Producer" => text_string(PRODUCER),
+        "ModDate" => text_string(&now.format("D:%Y%m%d%H%M%SZ").to_string()),
+    };
+    if let Some(author) = &metadata.author {
+        info.set("Author", text_string(author));
+    }
+    if let Some(subject) = &metadata.subject {
+        info.set("Subject", text_string(subject));
+    }
+    if !keywords.is_empty() {
+        info.set("Keywords", text_string(&keywords));
+    }
+    if let Some(date) = created {
+        info.set("CreationDate", text_string(&date.format("D:%Y%m%d%H%M%S").to_string()));
+    }
+    if let Ok(old) = doc.trailer.get(b"Info").and_then(Object::as_reference) {
+        doc.objects.remove(&old);
+    }
+    let info_id = doc.add_object(info);
+    doc.trailer.set("Info", info_id);
+
+    let mut properties = format!(
+        "   <dc:format>application/pdf</dc:format>\n   <dc:title><rdf:Alt><rdf:li xml:lang=\"x-default\">{}</rdf:li></rdf:Alt></dc:title>\n",
+        escape_html(&title)
+    );
+    if let Some(author) = &metadata.author {
+        let creators: String = author.split(',').map(str::trim).filter(|name| !name.is_empty()).map(|name| format!("<rdf:li>{}</rdf:li>", escape_html(name))).collect();
+        properties.push_str(&format!("   <dc:creator><rdf:Seq>{}</rdf:Seq></dc:creator>\n", creators));
+    }
+    if let Some(subject) = &metadata.subject {
+        properties.push_str(&format!("   <dc:description><rdf:Alt><rdf:li xml:lang=\"x-default\">{}</rdf:li></rdf:Alt></dc:description>\n", escape_html(subject)));
+    }
+    if !metadata.tags.is_empty() {
+        let tags: String = metadata.tags.iter().map(|tag| format!("<rdf:li>{}</rdf:li>", escape_html(tag))).collect();
+        properties.push_str(&format!("   <dc:subject><rdf:Bag>{}</rdf:Bag></dc:subject>\n", tags));
+        properties.push_str(&format!("   <pdf:Keywords>{}</pdf:Keywords>\n", escape_html(&keywords)));
+    }
+    if let Some(date) = created {
+        properties.push_str(&format!("   <xmp:CreateDate>{}</xmp:CreateDate>\n", date.format("%Y-%m-%dT%H:%M:%S")));
+    }
+    let xmp = format!(
+        concat!(
+            "<?xpacket begin=\"\u{feff}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?>\n",
+            "<x:xmpmeta xmlns:x=\"adobe:ns:meta/\">\n",
+            " <rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\n",
+            "  <rdf:Description rdf:about=\"\" xmlns:dc=\"http://purl.org/dc/elements/1.1/\" ",
+            "xmlns:pdf=\"http://ns.adobe.com/pdf/1.3/\" xmlns:xmp=\"http://ns.adobe.com/xap/1.0/\">\n",
+            "{properties}",
+            "   <pdf:Producer>{producer}</pdf:Producer>\n",
+            "   <xmp:CreatorTool>{producer}</xmp:CreatorTool>\n",
+            "   <xmp:ModifyDate>{now}</xmp:ModifyDate>\n",
+            "  </rdf:Description>\n",
+            " </rdf:RDF>\n",
+            "</x:xmpmeta>\n",
+            "<?xpacket end=\"w\"?>"
+        ),
+        properties = properties,
+        producer = PRODUCER,
+        now = now.format("%Y-%m-%dT%H:%M:%SZ")
+    );
+    // XMP okuyucuların bulabilmesi için sıkıştırılmaz
+    let stream = Stream::new(dictionary! { "Type" => "Metadata", "Subtype" => "XML" }, xmp.into_bytes()).with_compression(false);
+    let metadata_id = doc.add_object(stream);
+    let catalog = doc.catalog_mut().map_err(|e| e.to_string())?;
+    let old = catalog.get(b"Metadata").and_then(Object::as_reference).ok();
+    catalog.set("Metadata", metadata_id);
+    if let Some(old) = old {
+        doc.objects.remove(&old);
+    }
+    Ok(())
+}
+
+// Chromium yalnızca sayfadaki bir bağlantının hedeflediği kimlikler için adlandırılmış hedef yazar;
+// görünmeyen bu liste her başlığın konumunun PDF'e girmesini sağlar
+pub fn destination_anchors(headings: &[Heading]) -> String {
+    let links: String = headings.iter().filter(|heading| heading.listed).map(|heading| format!("<a href=\"#{}\"></a>", heading.id)).collect();
+    if links.is_empty() {
+        return String::new();
+    }
+    format!("<nav class=\"pdf-anchors\" hidden>{}</nav>", links)
+}
+
+// Tarayıcı çıktısına yer imlerini ve belge bilgisini yazar. Başlık konumları Chromium'un adlandırılmış
+// hedeflerinden okunur; hedefi bulunamayan başlık bir önceki başlığın sayfasına gider.
+pub fn annotate_file(path: &Path, headings: &[Heading], metadata: Option<&NoteMetadata>) -> Result<(), String> {
+    let mut doc = Document::load(path).map_err(|e| e.to_string())?;
+    let destinations = named_destinations(&doc);
+    let mut page = *doc.get_pages().values().next().ok_or("PDF'te sayfa bulunamadı")?;
+    let items: Vec<OutlineItem> = headings
+        .iter()
+        .filter(|heading| heading.listed && !heading.text.trim().is_empty())
+        .map(|heading| {
+            let top = match destinations.get(heading.id.as_bytes()) {
+                Some((target, top)) => {
+                    page = *target;
+                    *top
+                }
+                None => None,
+            };
+            OutlineItem { level: heading.level, title: heading.text.trim().to_string(), page, top }
+        })
+        .collect();
+    write_outline(&mut doc, &items)?;
+    write_info(&mut doc, metadata, path)?;
+    // Tarayıcının kendi yer imleri varsa artık hiçbir yerden başvurulmuyor
+    doc.prune_objects();
+    doc.save(path).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// Katalogdaki /Dests sözlüğü (PDF 1.1) ve /Names içindeki /Dests ad ağacı
+fn named_destinations(doc: &Document) -> HashMap<Vec<u8>, (ObjectId, Option<f32>)> {
+    let mut found = HashMap::new();
+    let Ok(catalog) = doc.catalog() else { return found };
+    if let Ok(dests) = catalog.get_deref(b"Dests", doc).and_then(Object::as_dict) {
+        for (name, value) in dests.iter() {
+            if let Some(destination) = destination(doc, value) {
+                found.insert(name.clone(), destination);
+            }
+        }
+    }
+    let tree = catalog.get_deref(b"Names", doc).and_then(Object::as_dict).and_then(|names| names.get_deref(b"Dests", doc)).and_then(Object::as_dict);
+    if let Ok(tree) = tree {
+        collect_name_tree(doc, tree, &mut found, 0);
+    }
+    found
+}
+
+fn collect_name_tree(doc: &Document, node: &Dictionary, found: &mut HashMap<Vec<u8>, (ObjectId, Option<f32>)>, depth: usize) {
+    if depth > NAME_TREE_DEPTH {
+        return;
+    }
+    if let Ok(names) = node.get_deref(b"Names", doc).and_then(Object::as_array) {
+        for pair in names.chunks(2) {
+            if let [name, value] = pair {
+                if let (Ok(name), Some(destination)) = (name.as_str(), destination(doc, value)) {
+                    found.insert(name.to_vec(), destination);
+                }
+            }
+        }
+    }
+    if let Ok(kids) = node.get_deref(b"Kids", doc).and_then(Object::as_array) {
+        for kid in kids {
+            if let Ok((_, Object::Dictionary(kid))) = doc.dereference(kid) {
+                collect_name_tree(doc, kid, found, depth + 1);
+            }
+        }
+    }
+}
+
+// [sayfa /XYZ x y zoom] ya da { D: [...] }; y yalnızca XYZ ve FitH/FitBH hedeflerinde vardır
+fn destination(doc: &Document, value: &Object) -> Option<(ObjectId, Option<f32>)> {
+    let (_, value) = doc.dereference(value).ok()?;
+    let array = match value {
+        Object::Dictionary(dict) => dict.get_deref(b"D", doc).ok()?.as_array().ok()?,
+        other => other.as_array().ok()?,
+    };
+    let page = array.first()?.as_reference().ok()?;
+    let top = match array.get(1).and_then(|kind| kind.as_name_str().ok()) {
+        Some("XYZ") => array.get(3),
+        Some("FitH") | Some("FitBH") => array.get(2),
+        _ => None,
+    };
+    Some((page, top.and_then(|top| top.as_float().ok())))
+}
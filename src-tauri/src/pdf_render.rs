@@ -0,0 +1,1466 @@
+// Tarayıcı gerektirmeyen yerleşik PDF motoru: Markdown AST'sini doğrudan sayfalara dizer.
+// Standart 14 PDF fontu (Helvetica/Courier) kullanılır; font gömülmediği için çıktı küçük kalır.
+use comrak::nodes::{AstNode, ListDelimType, ListType, NodeValue, TableAlignment};
+use comrak::{parse_document, Arena};
+use lopdf::content::{Content, Operation};
+use lopdf::{dictionary, Dictionary, Document, Object, ObjectId, Stream, StringFormat};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::branding::{Footer, FooterPosition, Watermark, WatermarkPosition};
+use crate::frontmatter::{self, NoteMetadata};
+use crate::images::{self, ImageFormat};
+use crate::markdown;
+use crate::page_setup::PageSetup;
+use crate::tags;
+use crate::pdf_meta::{self, OutlineItem};
+use crate::toc::{self, Heading, TocOptions};
+use crate::wikilinks::{self, LinkContext};
+use crate::workspace::ReadScope;
+
+const MM: f32 = 72.0 / 25.4;
+const BODY_SIZE: f32 = 11.0;
+const CODE_SIZE: f32 = 9.0;
+const LIST_INDENT: f32 = 18.0;
+const CELL_PADDING: f32 = 5.0;
+// Tarayıcı çıktısındaki .watermark ve .brand-footer ölçüleri
+const WATERMARK_SIZE: f32 = 64.0;
+const FOOTER_SIZE: f32 = 9.0;
+const FOOTER_LOGO_SIZE: f32 = 12.0;
+
+// Ölçüler pt
+#[derive(Debug, Clone, Copy)]
+pub struct PageLayout {
+    pub width: f32,
+    pub height: f32,
+    pub margin_top: f32,
+    pub margin_right: f32,
+    pub margin_bottom: f32,
+    pub margin_left: f32,
+    pub scale: f32,
+}
+
+impl PageLayout {
+    // İçerik 1/ölçek büyüklüğündeki bir sayfaya dizilir, sayfaya yazılırken `cm` ile gerçek ölçüye getirilir
+    fn scaled(self) -> PageLayout {
+        let scale = self.scale;
+        PageLayout {
+            width: self.width / scale,
+            height: self.height / scale,
+            margin_top: self.margin_top / scale,
+            margin_right: self.margin_right / scale,
+            margin_bottom: self.margin_bottom / scale,
+            margin_left: self.margin_left / scale,
+            scale: 1.0,
+        }
+    }
+
+    fn content_width(&self) -> f32 {
+        self.width - self.margin_left - self.margin_right
+    }
+}
+
+impl From<&PageSetup> for PageLayout {
+    fn from(setup: &PageSetup) -> Self {
+        let (width, height) = setup.paper_mm();
+        let margins = setup.margins;
+        PageLayout {
+            width: width * MM,
+            height: height * MM,
+            margin_top: margins.top * MM,
+            margin_right: margins.right * MM,
+            margin_bottom: margins.bottom * MM,
+            margin_left: margins.left * MM,
+            scale: setup.scale,
+        }
+    }
+}
+
+impl Default for PageLayout {
+    // Chrome çıktısıyla aynı: A4, 12mm kenar boşluğu
+    fn default() -> Self {
+        PageLayout::from(&PageSetup::default())
+    }
+}
+
+pub struct PdfRenderOptions<'a> {
+    pub dark: bool,
+    // Görseller notun klasörüne göre ve yalnızca çalışma alanından okunur
+    pub scope: &'a ReadScope,
+    pub layout: PageLayout,
+    pub links: Option<&'a LinkContext>,
+    // Ön bilgiden gelen başlık/yazar/konu/etiketler belge bilgisine (Info ve XMP) yazılır
+    pub metadata: Option<&'a NoteMetadata>,
+    // Her sayfaya içeriğin üstüne basılır
+    pub watermark: Option<&'a Watermark>,
+    pub footer: Option<&'a Footer>,
+    pub toc: TocOptions,
+    // Export işinin iptal bayrağı
+    pub cancel: Option<&'a AtomicBool>,
+}
+
+// Yerleşik motorun ayrı adımları; export işi bunları Chrome yolundaki ilerleme aşamalarıyla bildirir
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderStep {
+    // Not ve alt bilgi logosundaki görseller okunup çözülür
+    LoadImages,
+    // İçerik sayfalara dizilir
+    Layout,
+    // Yazı tipleri, görseller, yer imleri ve belge bilgisiyle PDF dosyası yazılır
+    Write,
+}
+
+// Markdown'ı yerleşik motorla PDF'e dönüştürüp verilen yola yazar; `on_step` her adımın başında çağrılır
+// ve hata dönerse (ör. iptal) dönüştürme orada durur
+pub fn render_markdown_to_pdf(markdown: &str, options: &PdfRenderOptions, output_path: &Path, mut on_step: impl FnMut(RenderStep) -> Result<(), String>) -> Result<(), String> {
+    let arena = Arena::new();
+    let comrak_options = markdown::options();
+    let root = parse_document(&arena, frontmatter::body(markdown), &comrak_options);
+    wikilinks::resolve_in_document(root, options.links);
+    let headings = toc::expand_in_document(&arena, root, &options.toc);
+
+    let mut renderer = Renderer::new(options);
+    on_step(RenderStep::LoadImages)?;
+    renderer.load_images(root)?;
+    on_step(RenderStep::Layout)?;
+    renderer.headings = headings.into_iter();
+    renderer.render_children(root, &BlockContext::default())?;
+    on_step(RenderStep::Write)?;
+    renderer.finish(output_path)
+}
+
+// Şemasız (göreli) linkler, ör. wiki bağlantıları, PDF'in yanındaki dosyayı açar; URI eylemi bunları çözemez
+fn link_action(url: &str) -> Dictionary {
+    let has_scheme = url
+        .split_once(':')
+        .is_some_and(|(scheme, _)| !scheme.is_empty() && scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c)));
+    if has_scheme || url.starts_with('#') {
+        return dictionary! { "S" => "URI", "URI" => Object::string_literal(url) };
+    }
+    let file = url.split('#').next().unwrap_or(url);
+    dictionary! { "S" => "Launch", "F" => Object::string_literal(percent_decode(file)) }
+}
+
+fn percent_decode(input: &str) -> Vec<u8> {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).and_then(|h| std::str::from_utf8(h).ok()).and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    decoded
+}
+
+type Color = [f32; 3];
+
+fn rgb(hex: u32) -> Color {
+    [
+        ((hex >> 16) & 0xff) as f32 / 255.0,
+        ((hex >> 8) & 0xff) as f32 / 255.0,
+        (hex & 0xff) as f32 / 255.0,
+    ]
+}
+
+// HTML export'taki açık/koyu tema renkleri
+struct Palette {
+    background: Option<Color>,
+    text: Color,
+    heading: Color,
+    muted: Color,
+    accent: Color,
+    link: Color,
+    border: Color,
+    code_background: Color,
+    code_text: Color,
+    inline_code: Color,
+    tag: Color,
+    tag_background: Color,
+    table_header: Color,
+    watermark: Color,
+    footer: Color,
+}
+
+impl Palette {
+    fn new(dark: bool) -> Self {
+        if dark {
+            Palette {
+                background: Some(rgb(0x0f172a)),
+                text: rgb(0xd1d5db),
+                heading: rgb(0xe5e7eb),
+                muted: rgb(0xcbd5e1),
+                accent: rgb(0xec4899),
+                link: rgb(0xf472b6),
+                border: rgb(0x374151),
+                code_background: rgb(0x0b1220),
+                code_text: rgb(0xcbd5e1),
+                inline_code: rgb(0xf59e0b),
+                tag: rgb(0xf9a8d4),
+                tag_background: rgb(0x3b0a24),
+                table_header: rgb(0x111827),
+                watermark: rgb(0xffffff),
+                footer: rgb(0x9ca3af),
+            }
+        } else {
+            Palette {
+                background: None,
+                text: rgb(0x111827),
+                heading: rgb(0x111827),
+                muted: rgb(0x6b7280),
+                accent: rgb(0xec4899),
+                link: rgb(0xec4899),
+                border: rgb(0xe5e7eb),
+                code_background: rgb(0xf3f4f6),
+                code_text: rgb(0x111827),
+                inline_code: rgb(0x111827),
+                tag: rgb(0xbe185d),
+                tag_background: rgb(0xfce7f3),
+                table_header: rgb(0xf3f4f6),
+                watermark: rgb(0x000000),
+                footer: rgb(0x6b7280),
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Font {
+    Regular,
+    Bold,
+    Italic,
+    BoldItalic,
+    Mono,
+}
+
+const FONTS: [Font; 5] = [Font::Regular, Font::Bold, Font::Italic, Font::BoldItalic, Font::Mono];
+
+// Helvetica / Helvetica-Bold AFM genişlikleri (32..=126), 1/1000 em
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556, 556, 556, 556,
+    556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667,
+    556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556, 333, 556,
+    556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722,
+    500, 500, 500, 334, 260, 334, 584,
+];
+const HELVETICA_BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556, 556, 556, 556,
+    556, 556, 556, 556, 333, 333, 584, 584, 584, 611, 975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722,
+    611, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556, 333, 556,
+    611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611, 611, 611, 389, 556, 333, 611, 556, 778,
+    556, 556, 500, 389, 280, 389, 584,
+];
+
+impl Font {
+    fn for_style(style: &Style) -> Font {
+        match (style.code, style.bold, style.italic) {
+            (true, _, _) => Font::Mono,
+            (false, true, true) => Font::BoldItalic,
+            (false, true, false) => Font::Bold,
+            (false, false, true) => Font::Italic,
+            (false, false, false) => Font::Regular,
+        }
+    }
+
+    fn resource(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+            Font::Italic => "F3",
+            Font::BoldItalic => "F4",
+            Font::Mono => "F5",
+        }
+    }
+
+    fn base_font(self) -> &'static str {
+        match self {
+            Font::Regular => "Helvetica",
+            Font::Bold => "Helvetica-Bold",
+            Font::Italic => "Helvetica-Oblique",
+            Font::BoldItalic => "Helvetica-BoldOblique",
+            Font::Mono => "Courier",
+        }
+    }
+
+    fn char_width(self, c: char) -> f32 {
+        let table = match self {
+            Font::Mono => return 600.0,
+            Font::Bold | Font::BoldItalic => &HELVETICA_BOLD_WIDTHS,
+            Font::Regular | Font::Italic => &HELVETICA_WIDTHS,
+        };
+        let c = width_base(c);
+        let code = c as u32;
+        if (32..=126).contains(&code) {
+            return table[(code - 32) as usize] as f32;
+        }
+        match c {
+            '—' | '…' | '‰' => 1000.0,
+            '•' => 350.0,
+            '‘' | '’' | '‚' => 222.0,
+            '“' | '”' | '„' => 333.0,
+            '©' | '®' => 737.0,
+            '°' => 400.0,
+            _ => 556.0,
+        }
+    }
+
+    fn text_width(self, text: &str, size: f32) -> f32 {
+        text.chars().map(|c| self.char_width(c)).sum::<f32>() * size / 1000.0
+    }
+}
+
+// Aksanlı harflerin genişliği temel harfle aynı kabul edilir
+fn width_base(c: char) -> char {
+    match c {
+        '\t' | '\u{a0}' => ' ',
+        'À'..='Å' => 'A',
+        'à'..='å' => 'a',
+        'Ç' => 'C',
+        'ç' => 'c',
+        'È'..='Ë' => 'E',
+        'è'..='ë' => 'e',
+        'Ì'..='Ï' | 'İ' => 'I',
+        'ì'..='ï' | 'ı' => 'i',
+        'Ñ' => 'N',
+        'ñ' => 'n',
+        'Ò'..='Ö' | 'Ø' => 'O',
+        'ò'..='ö' | 'ø' => 'o',
+        'Ù'..='Ü' => 'U',
+        'ù'..='ü' => 'u',
+        'Ý' | 'Ÿ' => 'Y',
+        'ý' | 'ÿ' => 'y',
+        'Ğ' => 'G',
+        'ğ' => 'g',
+        'Ş' | 'Š' => 'S',
+        'ş' | 'š' => 's',
+        'Ž' => 'Z',
+        'ž' => 'z',
+        '–' => '-',
+        _ => c,
+    }
+}
+
+// WinAnsiEncoding'de olmayan Türkçe harfler için boş kod noktalarına glif atanır
+const TURKISH_DIFFERENCES: [(u8, char, &str); 6] = [
+    (0x81, 'Ğ', "Gbreve"),
+    (0x8d, 'ğ', "gbreve"),
+    (0x8f, 'İ', "Idotaccent"),
+    (0x90, 'ı', "dotlessi"),
+    (0x9d, 'Ş', "Scedilla"),
+    (0xa4, 'ş', "scedilla"),
+];
+
+fn encode_text(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| {
+            if let Some((code, _, _)) = TURKISH_DIFFERENCES.iter().find(|(_, ch, _)| *ch == c) {
+                return *code;
+            }
+            match c {
+                '\t' => b' ',
+                ' '..='~' => c as u8,
+                '¤' => b'?',
+                '\u{a0}'..='\u{ff}' => c as u32 as u8,
+                '€' => 0x80,
+                '‚' => 0x82,
+                '„' => 0x84,
+                '…' => 0x85,
+                '‰' => 0x89,
+                'Š' => 0x8a,
+                '‘' => 0x91,
+                '’' => 0x92,
+                '“' => 0x93,
+                '”' => 0x94,
+                '•' => 0x95,
+                '–' => 0x96,
+                '—' => 0x97,
+                '™' => 0x99,
+                'š' => 0x9a,
+                'ž' => 0x9e,
+                'Ÿ' => 0x9f,
+                'Ž' => 0x8e,
+                _ => b'?',
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Style {
+    bold: bool,
+    italic: bool,
+    code: bool,
+    tag: bool,
+    strike: bool,
+    underline: bool,
+    link: Option<usize>,
+}
+
+impl Style {
+    fn size(&self, base: f32) -> f32 {
+        if self.code || self.tag { base * 0.9 } else { base }
+    }
+}
+
+enum Inline {
+    Text(String, Style),
+    Break,
+    Image { url: String, alt: String },
+}
+
+struct Piece {
+    text: String,
+    style: Style,
+    width: f32,
+}
+
+#[derive(Default)]
+struct Line {
+    pieces: Vec<Piece>,
+    width: f32,
+}
+
+impl Line {
+    fn push(&mut self, text: &str, style: Style, width: f32) {
+        self.width += width;
+        if let Some(last) = self.pieces.last_mut() {
+            if last.style == style {
+                last.text.push_str(text);
+                last.width += width;
+                return;
+            }
+        }
+        self.pieces.push(Piece { text: text.to_string(), style, width });
+    }
+}
+
+enum Marker {
+    Text(String),
+    Checkbox(bool),
+}
+
+#[derive(Clone, Default)]
+struct BlockContext {
+    indent: f32,
+    tight: bool,
+    list_depth: usize,
+    color: Option<Color>,
+}
+
+struct PageContent {
+    operations: Vec<Operation>,
+    links: Vec<([f32; 4], String)>,
+}
+
+struct ImageXObject {
+    width: u32,
+    height: u32,
+    color_space: &'static str,
+    components: u8,
+    data: Vec<u8>,
+    jpeg: bool,
+    alpha: Option<Vec<u8>>,
+}
+
+struct Renderer<'a> {
+    // İçeriğin dizildiği ölçekli sayfa; `paper` PDF'teki gerçek sayfa
+    layout: PageLayout,
+    paper: PageLayout,
+    palette: Palette,
+    scope: &'a ReadScope,
+    metadata: Option<&'a NoteMetadata>,
+    watermark: Option<&'a Watermark>,
+    footer: Option<&'a Footer>,
+    cancel: Option<&'a AtomicBool>,
+    pages: Vec<PageContent>,
+    // Sayfanın üstünden itibaren mevcut konum (pt)
+    y: f32,
+    links: Vec<String>,
+    images: Vec<ImageXObject>,
+    image_cache: HashMap<String, Option<usize>>,
+    quote_bars: Vec<f32>,
+    pending_marker: Option<(Marker, f32)>,
+    // Başlıklar belge sırasıyla; "#kimlik" linkleri ve yer imleri başlığın sayfa ve konumuna gider
+    headings: std::vec::IntoIter<Heading>,
+    anchors: HashMap<String, (usize, f32)>,
+    outline: Vec<(Heading, usize, f32)>,
+}
+
+impl<'a> Renderer<'a> {
+    fn new(options: &PdfRenderOptions<'a>) -> Self {
+        let mut renderer = Renderer {
+            layout: options.layout.scaled(),
+            paper: options.layout,
+            palette: Palette::new(options.dark),
+            scope: options.scope,
+            metadata: options.metadata,
+            watermark: options.watermark,
+            footer: options.footer,
+            cancel: options.cancel,
+            pages: Vec::new(),
+            y: 0.0,
+            links: Vec::new(),
+            images: Vec::new(),
+            image_cache: HashMap::new(),
+            quote_bars: Vec::new(),
+            pending_marker: None,
+            headings: Vec::new().into_iter(),
+            anchors: HashMap::new(),
+            outline: Vec::new(),
+        };
+        renderer.new_page();
+        renderer
+    }
+
+    fn content_width(&self) -> f32 {
+        self.layout.content_width()
+    }
+
+    fn bottom(&self) -> f32 {
+        self.layout.height - self.layout.margin_bottom
+    }
+
+    fn at_page_top(&self) -> bool {
+        self.y <= self.layout.margin_top + 0.5
+    }
+
+    fn new_page(&mut self) {
+        self.pages.push(PageContent { operations: Vec::new(), links: Vec::new() });
+        self.y = self.layout.margin_top;
+        if let Some(background) = self.palette.background {
+            let (width, height) = (self.layout.width, self.layout.height);
+            self.fill_rect(0.0, 0.0, width, height, background);
+        }
+    }
+
+    fn ensure_space(&mut self, height: f32) {
+        if self.y + height > self.bottom() && !self.at_page_top() {
+            self.new_page();
+        }
+    }
+
+    // Dikey ilerleme; alıntı içindeysek sol çizgiler de bu aralık için çizilir
+    fn advance(&mut self, height: f32) {
+        let height = height.min(self.bottom() - self.y).max(0.0);
+        let bars = self.quote_bars.clone();
+        let (top, accent) = (self.y, self.palette.accent);
+        for x in bars {
+            self.fill_rect(x, top, 3.0, height, accent);
+        }
+        self.y += height;
+    }
+
+    fn ops(&mut self) -> &mut Vec<Operation> {
+        &mut self.pages.last_mut().expect("en az bir sayfa var").operations
+    }
+
+    fn pdf_y(&self, top: f32) -> f32 {
+        self.layout.height - top
+    }
+
+    fn x(&self, indent: f32) -> f32 {
+        self.layout.margin_left + indent
+    }
+
+    fn fill_rect(&mut self, x: f32, top: f32, width: f32, height: f32, color: Color) {
+        let y = self.pdf_y(top + height);
+        let ops = self.ops();
+        ops.push(Operation::new("q", vec![]));
+        ops.push(Operation::new("rg", color.iter().map(|c| (*c).into()).collect()));
+        ops.push(Operation::new("re", vec![x.into(), y.into(), width.into(), height.into()]));
+        ops.push(Operation::new("f", vec![]));
+        ops.push(Operation::new("Q", vec![]));
+    }
+
+    fn stroke_rect(&mut self, x: f32, top: f32, width: f32, height: f32, color: Color) {
+        let y = self.pdf_y(top + height);
+        let ops = self.ops();
+        ops.push(Operation::new("q", vec![]));
+        ops.push(Operation::new("RG", color.iter().map(|c| (*c).into()).collect()));
+        ops.push(Operation::new("w", vec![0.75.into()]));
+        ops.push(Operation::new("re", vec![x.into(), y.into(), width.into(), height.into()]));
+        ops.push(Operation::new("S", vec![]));
+        ops.push(Operation::new("Q", vec![]));
+    }
+
+    fn hline(&mut self, x: f32, top: f32, width: f32, thickness: f32, color: Color) {
+        self.fill_rect(x, top, width, thickness, color);
+    }
+
+    fn text(&mut self, x: f32, baseline: f32, font: Font, size: f32, color: Color, text: &str) {
+        let y = self.pdf_y(baseline);
+        let ops = self.ops();
+        ops.push(Operation::new("BT", vec![]));
+        ops.push(Operation::new("rg", color.iter().map(|c| (*c).into()).collect()));
+        ops.push(Operation::new("Tf", vec![font.resource().into(), size.into()]));
+        ops.push(Operation::new("Td", vec![x.into(), y.into()]));
+        ops.push(Operation::new("Tj", vec![Object::String(encode_text(text), StringFormat::Literal)]));
+        ops.push(Operation::new("ET", vec![]));
+    }
+
+    fn check_cancel(&self) -> Result<(), String> {
+        if self.cancel.is_some_and(|cancel| cancel.load(Ordering::SeqCst)) {
+            return Err("İşlem iptal edildi".to_string());
+        }
+        Ok(())
+    }
+
+    fn render_children<'n>(&mut self, node: &'n AstNode<'n>, ctx: &BlockContext) -> Result<(), String> {
+        for child in node.children() {
+            self.render_block(child, ctx)?;
+        }
+        Ok(())
+    }
+
+    fn render_block<'n>(&mut self, node: &'n AstNode<'n>, ctx: &BlockContext) -> Result<(), String> {
+        self.check_cancel()?;
+        let value = node.data.borrow().value.clone();
+        match value {
+            NodeValue::Paragraph => {
+                let inlines = self.collect_inlines(node, Style::default());
+                let color = ctx.color.unwrap_or(self.palette.text);
+                self.render_inlines(&inlines, ctx.indent, BODY_SIZE, color)?;
+                self.advance(if ctx.tight { 2.0 } else { 8.0 });
+            }
+            NodeValue::Heading(heading) => {
+                let size = match heading.level {
+                    1 => 18.0,
+                    2 => 16.0,
+                    3 => 14.0,
+                    4 => 12.5,
+                    5 => 11.5,
+                    _ => 11.0,
+                };
+                if !self.at_page_top() {
+                    self.advance(size * 0.8);
+                }
+                // Başlık sayfanın dibinde yalnız kalmasın
+                self.ensure_space(size * 1.4 + 3.0 * BODY_SIZE * 1.45);
+                if let Some(heading) = self.headings.next() {
+                    let position = (self.pages.len() - 1, self.y);
+                    self.anchors.insert(heading.id.clone(), position);
+                    if heading.listed && !heading.text.trim().is_empty() {
+                        self.outline.push((heading, position.0, position.1));
+                    }
+                }
+                let style = Style { bold: true, ..Style::default() };
+                let inlines = self.collect_inlines(node, style);
+                let color = self.palette.heading;
+                self.render_inlines(&inlines, ctx.indent, size, color)?;
+                if heading.level <= 2 {
+                    let (x, width, top, border) = (self.x(ctx.indent), self.content_width() - ctx.indent, self.y + 2.0, self.palette.border);
+                    self.hline(x, top, width, 1.0, border);
+                    self.advance(5.0);
+                }
+                self.advance(size * 0.4);
+            }
+            NodeValue::BlockQuote | NodeValue::MultilineBlockQuote(_) | NodeValue::Alert(_) => {
+                let x = self.x(ctx.indent);
+                self.quote_bars.push(x);
+                let inner = BlockContext { indent: ctx.indent + 14.0, color: Some(self.palette.muted), ..ctx.clone() };
+                let result = self.render_children(node, &inner);
+                self.quote_bars.pop();
+                result?;
+                self.advance(4.0);
+            }
+            NodeValue::List(list) => {
+                for (number, item) in (list.start..).zip(node.children()) {
+                    let checked = match item.data.borrow().value {
+                        NodeValue::TaskItem(mark) => Some(mark.is_some()),
+                        _ => None,
+                    };
+                    let marker = match (checked, list.list_type) {
+                        (Some(done), _) => Marker::Checkbox(done),
+                        (None, ListType::Ordered) => {
+                            let delimiter = if list.delimiter == ListDelimType::Paren { ")" } else { "." };
+                            Marker::Text(format!("{}{}", number, delimiter))
+                        }
+                        (None, ListType::Bullet) => Marker::Text(if ctx.list_depth % 2 == 0 { "•" } else { "–" }.to_string()),
+                    };
+                    self.pending_marker = Some((marker, self.x(ctx.indent)));
+                    let inner = BlockContext {
+                        indent: ctx.indent + LIST_INDENT,
+                        tight: list.tight,
+                        list_depth: ctx.list_depth + 1,
+                        color: ctx.color,
+                    };
+                    self.render_children(item, &inner)?;
+                    self.pending_marker = None;
+                }
+                if ctx.list_depth == 0 {
+                    self.advance(6.0);
+                }
+            }
+            NodeValue::CodeBlock(code) => self.render_code_block(&code.literal, ctx.indent)?,
+            NodeValue::Table(table) => self.render_table(node, &table.alignments, ctx.indent)?,
+            NodeValue::ThematicBreak => {
+                self.ensure_space(14.0);
+                self.advance(6.0);
+                let (x, width, top, border) = (self.x(ctx.indent), self.content_width() - ctx.indent, self.y, self.palette.border);
+                self.hline(x, top, width, 1.0, border);
+                self.advance(8.0);
+            }
+            NodeValue::FootnoteDefinition(definition) => {
+                let inner = BlockContext { color: Some(self.palette.muted), ..ctx.clone() };
+                self.pending_marker = Some((Marker::Text(format!("[{}]", definition.name)), self.x(ctx.indent)));
+                let inner = BlockContext { indent: ctx.indent + LIST_INDENT, ..inner };
+                self.render_children(node, &inner)?;
+                self.pending_marker = None;
+            }
+            // Ham HTML bloklarının düzeni tarayıcı olmadan bilinemez; yalnızca metin bırakılmaz
+            NodeValue::HtmlBlock(_) | NodeValue::FrontMatter(_) => {}
+            _ => self.render_children(node, ctx)?,
+        }
+        Ok(())
+    }
+
+    fn collect_inlines<'n>(&mut self, node: &'n AstNode<'n>, style: Style) -> Vec<Inline> {
+        let mut out = Vec::new();
+        self.collect_inlines_into(node, style, &mut out);
+        out
+    }
+
+    fn collect_inlines_into<'n>(&mut self, node: &'n AstNode<'n>, style: Style, out: &mut Vec<Inline>) {
+        for child in node.children() {
+            let value = child.data.borrow().value.clone();
+            match value {
+                NodeValue::Text(text) => {
+                    for (part, is_tag) in tags::split_text(&text) {
+                        out.push(Inline::Text(part.to_string(), Style { tag: is_tag, ..style }));
+                    }
+                }
+                NodeValue::SoftBreak => out.push(Inline::Text(" ".to_string(), style)),
+                NodeValue::LineBreak => out.push(Inline::Break),
+                NodeValue::Code(code) => out.push(Inline::Text(code.literal, Style { code: true, ..style })),
+                NodeValue::Math(math) => out.push(Inline::Text(math.literal, Style { code: true, ..style })),
+                NodeValue::EscapedTag(tag) => out.push(Inline::Text(tag, style)),
+                NodeValue::HtmlInline(html) => {
+                    if html.to_ascii_lowercase().starts_with("<br") {
+                        out.push(Inline::Break);
+                    }
+                }
+                NodeValue::Emph => self.collect_inlines_into(child, Style { italic: true, ..style }, out),
+                NodeValue::Strong => self.collect_inlines_into(child, Style { bold: true, ..style }, out),
+                NodeValue::Strikethrough => self.collect_inlines_into(child, Style { strike: true, ..style }, out),
+                NodeValue::Underline => self.collect_inlines_into(child, Style { underline: true, ..style }, out),
+                NodeValue::Link(link) => {
+                    self.links.push(link.url);
+                    let link_style = Style { link: Some(self.links.len() - 1), ..style };
+                    self.collect_inlines_into(child, link_style, out);
+                }
+                NodeValue::WikiLink(link) => {
+                    self.links.push(link.url);
+                    let link_style = Style { link: Some(self.links.len() - 1), ..style };
+                    self.collect_inlines_into(child, link_style, out);
+                }
+                NodeValue::Image(link) => {
+                    let alt = plain_text(child);
+                    out.push(Inline::Image { url: link.url, alt });
+                }
+                NodeValue::FootnoteReference(reference) => {
+                    out.push(Inline::Text(format!("[{}]", reference.name), style));
+                }
+                _ => self.collect_inlines_into(child, style, out),
+            }
+        }
+    }
+
+    // Satır içi içeriği sarar ve çizer; görseller ayrı blok olarak araya girer
+    fn render_inlines(&mut self, inlines: &[Inline], indent: f32, size: f32, color: Color) -> Result<(), String> {
+        let width = self.content_width() - indent;
+        let mut segment: Vec<&Inline> = Vec::new();
+        for inline in inlines {
+            if let Inline::Image { url, alt } = inline {
+                self.render_lines(&segment, indent, width, size, color);
+                segment.clear();
+                self.render_image(url, alt, indent)?;
+            } else {
+                segment.push(inline);
+            }
+        }
+        self.render_lines(&segment, indent, width, size, color);
+        Ok(())
+    }
+
+    fn render_lines(&mut self, inlines: &[&Inline], indent: f32, width: f32, size: f32, color: Color) {
+        let line_height = size * 1.45;
+        for line in wrap(inlines, size, width) {
+            self.ensure_space(line_height);
+            let baseline = self.y + (line_height - size) / 2.0 + size * 0.8;
+            self.draw_marker(baseline, size, color);
+            self.draw_line(&line, self.x(indent), baseline, size, color);
+            self.advance(line_height);
+        }
+    }
+
+    fn draw_marker(&mut self, baseline: f32, size: f32, color: Color) {
+        let Some((marker, x)) = self.pending_marker.take() else { return };
+        match marker {
+            Marker::Text(text) => self.text(x, baseline, Font::Regular, size, color, &text),
+            Marker::Checkbox(checked) => {
+                let box_size = size * 0.75;
+                let top = baseline - box_size;
+                self.stroke_rect(x, top, box_size, box_size, color);
+                if checked {
+                    let inset = box_size * 0.25;
+                    let accent = self.palette.accent;
+                    self.fill_rect(x + inset, top + inset, box_size - 2.0 * inset, box_size - 2.0 * inset, accent);
+                }
+            }
+        }
+    }
+
+    fn draw_line(&mut self, line: &Line, x: f32, baseline: f32, size: f32, color: Color) {
+        let mut cursor = x;
+        for piece in &line.pieces {
+            let piece_size = piece.style.size(size);
+            let font = Font::for_style(&piece.style);
+            let mut piece_color = color;
+            if piece.style.code {
+                let background = self.palette.code_background;
+                self.fill_rect(cursor - 1.0, baseline - piece_size * 0.85, piece.width + 2.0, piece_size * 1.15, background);
+                piece_color = self.palette.inline_code;
+            } else if piece.style.tag {
+                let background = self.palette.tag_background;
+                self.fill_rect(cursor - 1.0, baseline - piece_size * 0.85, piece.width + 2.0, piece_size * 1.15, background);
+                piece_color = self.palette.tag;
+            }
+            if piece.style.link.is_some() {
+                piece_color = self.palette.link;
+            }
+            self.text(cursor, baseline, font, piece_size, piece_color, &piece.text);
+            if piece.style.strike {
+                self.hline(cursor, baseline - piece_size * 0.3, piece.width, 0.6, piece_color);
+            }
+            if piece.style.underline || piece.style.link.is_some() {
+                self.hline(cursor, baseline + 1.2, piece.width, 0.5, piece_color);
+            }
+            if let Some(index) = piece.style.link {
+                let url = self.links[index].clone();
+                let rect = [cursor, self.pdf_y(baseline + 2.0), cursor + piece.width, self.pdf_y(baseline - piece_size)];
+                self.pages.last_mut().expect("en az bir sayfa var").links.push((rect, url));
+            }
+            cursor += piece.width;
+        }
+    }
+
+    fn render_code_block(&mut self, literal: &str, indent: f32) -> Result<(), String> {
+        let padding = 6.0;
+        let line_height = CODE_SIZE * 1.4;
+        let x = self.x(indent);
+        let width = self.content_width() - indent;
+        let max_chars = (((width - 2.0 * padding) / (Font::Mono.char_width(' ') * CODE_SIZE / 1000.0)) as usize).max(1);
+        let background = self.palette.code_background;
+        let color = self.palette.code_text;
+
+        self.ensure_space(padding + line_height);
+        let top = self.y;
+        self.fill_rect(x, top, width, padding, background);
+        self.advance(padding);
+        for source_line in literal.trim_end_matches('\n').split('\n') {
+            self.check_cancel()?;
+            let expanded = source_line.replace('\t', "    ");
+            let chars: Vec<char> = expanded.chars().collect();
+            let chunks: Vec<String> = if chars.is_empty() {
+                vec![String::new()]
+            } else {
+                chars.chunks(max_chars).map(|c| c.iter().collect()).collect()
+            };
+            for chunk in chunks {
+                self.ensure_space(line_height);
+                let top = self.y;
+                self.fill_rect(x, top, width, line_height, background);
+                let baseline = top + (line_height - CODE_SIZE) / 2.0 + CODE_SIZE * 0.8;
+                self.draw_marker(baseline, BODY_SIZE, color);
+                self.text(x + padding, baseline, Font::Mono, CODE_SIZE, color, &chunk);
+                self.advance(line_height);
+            }
+        }
+        let top = self.y;
+        self.fill_rect(x, top, width, padding, background);
+        self.advance(padding + 8.0);
+        Ok(())
+    }
+
+    fn render_table<'n>(&mut self, node: &'n AstNode<'n>, alignments: &[TableAlignment], indent: f32) -> Result<(), String> {
+        let mut rows: Vec<(bool, Vec<Vec<Inline>>)> = Vec::new();
+        for row in node.children() {
+            let header = matches!(row.data.borrow().value, NodeValue::TableRow(true));
+            let style = Style { bold: header, ..Style::default() };
+            let cells = row.children().map(|cell| self.collect_inlines(cell, style)).collect();
+            rows.push((header, cells));
+        }
+        let columns = rows.iter().map(|(_, cells)| cells.len()).max().unwrap_or(0);
+        if columns == 0 {
+            return Ok(());
+        }
+
+        let size = BODY_SIZE * 0.9;
+        let line_height = size * 1.4;
+        let available = self.content_width() - indent;
+
+        // Sütun genişlikleri: doğal genişliklere göre orantılı, tablo tüm satırı kaplar (width: 100%)
+        let mut natural = vec![0.0f32; columns];
+        let mut minimum = vec![0.0f32; columns];
+        for (_, cells) in &rows {
+            for (i, cell) in cells.iter().enumerate() {
+                let refs: Vec<&Inline> = cell.iter().collect();
+                let single = wrap(&refs, size, f32::MAX);
+                let line_width = single.iter().map(|l| l.width).fold(0.0, f32::max);
+                natural[i] = natural[i].max(line_width + 2.0 * CELL_PADDING);
+                minimum[i] = minimum[i].max(longest_word(&refs, size) + 2.0 * CELL_PADDING);
+            }
+        }
+        let natural_total: f32 = natural.iter().sum();
+        let widths: Vec<f32> = if natural_total <= available {
+            natural.iter().map(|w| w * available / natural_total).collect()
+        } else {
+            let minimum_total: f32 = minimum.iter().sum();
+            if minimum_total >= available {
+                minimum.iter().map(|w| w * available / minimum_total).collect()
+            } else {
+                let flexible: f32 = natural.iter().zip(&minimum).map(|(n, m)| n - m).sum();
+                natural
+                    .iter()
+                    .zip(&minimum)
+                    .map(|(n, m)| m + (n - m) * (available - minimum_total) / flexible.max(1.0))
+                    .collect()
+            }
+        };
+
+        let header_index = rows.iter().position(|(header, _)| *header);
+        for index in 0..rows.len() {
+            self.check_cancel()?;
+            let row_height = self.table_row_height(&rows[index].1, &widths, size, line_height);
+            if self.y + row_height > self.bottom() && !self.at_page_top() {
+                self.new_page();
+                // Başlık satırı yeni sayfada tekrarlanır
+                if let Some(h) = header_index.filter(|h| *h != index) {
+                    let header_height = self.table_row_height(&rows[h].1, &widths, size, line_height);
+                    self.draw_table_row(&rows[h], &widths, alignments, indent, size, line_height, header_height);
+                }
+            }
+            self.draw_table_row(&rows[index], &widths, alignments, indent, size, line_height, row_height);
+        }
+        self.advance(10.0);
+        Ok(())
+    }
+
+    fn table_row_height(&self, cells: &[Vec<Inline>], widths: &[f32], size: f32, line_height: f32) -> f32 {
+        let lines = cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| {
+                let refs: Vec<&Inline> = cell.iter().collect();
+                wrap(&refs, size, widths[i] - 2.0 * CELL_PADDING).len().max(1)
+            })
+            .max()
+            .unwrap_or(1);
+        lines as f32 * line_height + 2.0 * CELL_PADDING
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn draw_table_row(
+        &mut self,
+        row: &(bool, Vec<Vec<Inline>>),
+        widths: &[f32],
+        alignments: &[TableAlignment],
+        indent: f32,
+        size: f32,
+        line_height: f32,
+        row_height: f32,
+    ) {
+        let (header, cells) = row;
+        let top = self.y;
+        let color = if *header { self.palette.heading } else { self.palette.text };
+        let mut x = self.x(indent);
+        for (i, width) in widths.iter().enumerate() {
+            if *header {
+                let background = self.palette.table_header;
+                self.fill_rect(x, top, *width, row_height, background);
+            }
+            if let Some(cell) = cells.get(i) {
+                let refs: Vec<&Inline> = cell.iter().collect();
+                let inner = width - 2.0 * CELL_PADDING;
+                for (n, line) in wrap(&refs, size, inner).iter().enumerate() {
+                    let offset = match alignments.get(i) {
+                        Some(TableAlignment::Center) => (inner - line.width) / 2.0,
+                        Some(TableAlignment::Right) => inner - line.width,
+                        _ => 0.0,
+                    };
+                    let baseline = top + CELL_PADDING + n as f32 * line_height + (line_height - size) / 2.0 + size * 0.8;
+                    self.draw_line(line, x + CELL_PADDING + offset.max(0.0), baseline, size, color);
+                }
+            }
+            let border = self.palette.border;
+            self.stroke_rect(x, top, *width, row_height, border);
+            x += width;
+        }
+        self.advance(row_height);
+    }
+
+    // Aynı görsel birden çok kez kullanılsa da bir kez okunup gömülür; okunamayanlar için None
+    fn image(&mut self, url: &str) -> Option<usize> {
+        if let Some(index) = self.image_cache.get(url) {
+            return *index;
+        }
+        let index = load_image(url, self.scope).map(|image| {
+            self.images.push(image);
+            self.images.len() - 1
+        });
+        self.image_cache.insert(url.to_string(), index);
+        index
+    }
+
+    // Dizmeden önce tüm görseller okunur; dizme sırasında yalnızca önbellekten alınır
+    fn load_images(&mut self, root: &'a AstNode<'a>) -> Result<(), String> {
+        for node in root.descendants() {
+            if let NodeValue::Image(link) = &node.data.borrow().value {
+                self.check_cancel()?;
+                self.image(&link.url);
+            }
+        }
+        if let Some(logo) = self.footer.and_then(|footer| footer.logo.as_deref()) {
+            self.image(logo);
+        }
+        Ok(())
+    }
+
+    fn render_image(&mut self, url: &str, alt: &str, indent: f32) -> Result<(), String> {
+        self.check_cancel()?;
+        let Some(index) = self.image(url) else {
+            // Yüklenemeyen görselin yerine alternatif metni yaz
+            if !alt.is_empty() {
+                let inline = Inline::Text(format!("[{}]", alt), Style { italic: true, ..Style::default() });
+                let width = self.content_width() - indent;
+                let muted = self.palette.muted;
+                self.render_lines(&[&inline], indent, width, BODY_SIZE, muted);
+            }
+            return Ok(());
+        };
+
+        // CSS'teki gibi: px → pt (0.75), en fazla 17cm x 16cm ve sayfa içeriği kadar
+        let image = &self.images[index];
+        let (natural_width, natural_height) = (image.width as f32 * 0.75, image.height as f32 * 0.75);
+        let available = self.content_width() - indent;
+        let max_width = available.min(170.0 * MM);
+        let max_height = (160.0 * MM).min(self.bottom() - self.layout.margin_top);
+        let scale = (max_width / natural_width).min(max_height / natural_height).min(1.0);
+        let (width, height) = (natural_width * scale, natural_height * scale);
+
+        self.ensure_space(height + 6.0);
+        self.advance(3.0);
+        let x = self.x(indent) + (available - width) / 2.0;
+        let y = self.pdf_y(self.y + height);
+        let ops = self.ops();
+        ops.push(Operation::new("q", vec![]));
+        ops.push(Operation::new("cm", vec![width.into(), 0.into(), 0.into(), height.into(), x.into(), y.into()]));
+        ops.push(Operation::new("Do", vec![format!("Im{}", index + 1).into()]));
+        ops.push(Operation::new("Q", vec![]));
+        self.advance(height + 9.0);
+        Ok(())
+    }
+
+    // Filigran ve alt bilgi tüm sayfalara içerikten sonra, ölçeklenmeden çizilir; saydamlık ExtGState ile verilir
+    fn stamp_ops(&mut self) -> Vec<Operation> {
+        let mut ops = Vec::new();
+        if let Some(watermark) = self.watermark {
+            ops.extend(self.watermark_ops(watermark));
+        }
+        if let Some(footer) = self.footer {
+            let logo = footer.logo.as_deref().and_then(|logo| self.image(logo));
+            ops.extend(self.footer_ops(footer, logo));
+        }
+        ops
+    }
+
+    fn watermark_ops(&self, watermark: &Watermark) -> Vec<Operation> {
+        let (width, height) = (self.paper.width, self.paper.height);
+        let diagonal = watermark.position == WatermarkPosition::Diagonal;
+        let angle: f32 = if diagonal { 30f32.to_radians() } else { 0.0 };
+        // Uzun metin ("CONFIDENTIAL") sayfadan taşmasın
+        let available = if diagonal { width.hypot(height) * 0.7 } else { self.paper.content_width() };
+        let natural = Font::Bold.text_width(&watermark.text, WATERMARK_SIZE);
+        let size = if natural > available { WATERMARK_SIZE * available / natural } else { WATERMARK_SIZE };
+        let text_width = Font::Bold.text_width(&watermark.text, size);
+        let center_y = match watermark.position {
+            WatermarkPosition::Top => height - 24.0 * MM - size * 0.5,
+            WatermarkPosition::Bottom => 28.0 * MM + size * 0.5,
+            WatermarkPosition::Diagonal | WatermarkPosition::Center => height / 2.0,
+        };
+        // Metnin ortası sayfadaki noktaya gelsin: yarım genişlik ve yarım büyük harf yüksekliği döndürülüp çıkarılır
+        let (sin, cos) = angle.sin_cos();
+        let (half_x, half_y) = (text_width / 2.0, size * 0.35);
+        let x = width / 2.0 - (half_x * cos - half_y * sin);
+        let y = center_y - (half_x * sin + half_y * cos);
+        vec![
+            Operation::new("q", vec![]),
+            Operation::new("gs", vec!["GSw".into()]),
+            Operation::new("BT", vec![]),
+            Operation::new("rg", self.palette.watermark.iter().map(|c| (*c).into()).collect()),
+            Operation::new("Tf", vec![Font::Bold.resource().into(), size.into()]),
+            Operation::new("Tm", vec![cos.into(), sin.into(), (-sin).into(), cos.into(), x.into(), y.into()]),
+            Operation::new("Tj", vec![Object::String(encode_text(&watermark.text), StringFormat::Literal)]),
+            Operation::new("ET", vec![]),
+            Operation::new("Q", vec![]),
+        ]
+    }
+
+    // Alt kenar boşluğunun ortasına: logo, 6pt boşluk, metin
+    fn footer_ops(&self, footer: &Footer, logo: Option<usize>) -> Vec<Operation> {
+        let logo_width = logo.map(|index| {
+            let image = &self.images[index];
+            FOOTER_LOGO_SIZE * image.width as f32 / image.height.max(1) as f32
+        });
+        let text_width = Font::Regular.text_width(&footer.text, FOOTER_SIZE);
+        let gap = if logo_width.is_some() && !footer.text.is_empty() { 6.0 } else { 0.0 };
+        let total = logo_width.unwrap_or(0.0) + gap + text_width;
+        let mut x = match footer.position {
+            FooterPosition::Left => self.paper.margin_left,
+            FooterPosition::Center => (self.paper.width - total) / 2.0,
+            FooterPosition::Right => self.paper.width - self.paper.margin_right - total,
+        };
+        let center_y = self.paper.margin_bottom / 2.0;
+
+        let mut ops = vec![Operation::new("q", vec![]), Operation::new("gs", vec!["GSf".into()])];
+        if let (Some(index), Some(logo_width)) = (logo, logo_width) {
+            let y = center_y - FOOTER_LOGO_SIZE / 2.0;
+            ops.push(Operation::new("q", vec![]));
+            ops.push(Operation::new("cm", vec![logo_width.into(), 0.into(), 0.into(), FOOTER_LOGO_SIZE.into(), x.into(), y.into()]));
+            ops.push(Operation::new("Do", vec![format!("Im{}", index + 1).into()]));
+            ops.push(Operation::new("Q", vec![]));
+            x += logo_width + gap;
+        }
+        if !footer.text.is_empty() {
+            ops.push(Operation::new("BT", vec![]));
+            ops.push(Operation::new("rg", self.palette.footer.iter().map(|c| (*c).into()).collect()));
+            ops.push(Operation::new("Tf", vec![Font::Regular.resource().into(), FOOTER_SIZE.into()]));
+            ops.push(Operation::new("Td", vec![x.into(), (center_y - FOOTER_SIZE * 0.35).into()]));
+            ops.push(Operation::new("Tj", vec![Object::String(encode_text(&footer.text), StringFormat::Literal)]));
+            ops.push(Operation::new("ET", vec![]));
+        }
+        ops.push(Operation::new("Q", vec![]));
+        ops
+    }
+
+    fn finish(mut self, output_path: &Path) -> Result<(), String> {
+        let stamp = self.stamp_ops();
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+
+        let mut differences: Vec<Object> = Vec::new();
+        for (code, _, glyph) in TURKISH_DIFFERENCES {
+            differences.push((code as i64).into());
+            differences.push(Object::Name(glyph.as_bytes().to_vec()));
+        }
+        let encoding = dictionary! {
+            "Type" => "Encoding",
+            "BaseEncoding" => "WinAnsiEncoding",
+            "Differences" => differences,
+        };
+        let mut fonts = Dictionary::new();
+        for font in FONTS {
+            let font_id = doc.add_object(dictionary! {
+                "Type" => "Font",
+                "Subtype" => "Type1",
+                "BaseFont" => font.base_font(),
+                "Encoding" => encoding.clone(),
+            });
+            fonts.set(font.resource(), font_id);
+        }
+
+        let mut xobjects = Dictionary::new();
+        for (index, image) in self.images.into_iter().enumerate() {
+            let mut dict = dictionary! {
+                "Type" => "XObject",
+                "Subtype" => "Image",
+                "Width" => image.width as i64,
+                "Height" => image.height as i64,
+                "ColorSpace" => image.color_space,
+                "BitsPerComponent" => 8,
+            };
+            if let Some(alpha) = image.alpha {
+                let mask_id = doc.add_object(Stream::new(
+                    dictionary! {
+                        "Type" => "XObject",
+                        "Subtype" => "Image",
+                        "Width" => image.width as i64,
+                        "Height" => image.height as i64,
+                        "ColorSpace" => "DeviceGray",
+                        "BitsPerComponent" => 8,
+                    },
+                    alpha,
+                ));
+                dict.set("SMask", mask_id);
+            }
+            if image.jpeg {
+                dict.set("Filter", "DCTDecode");
+                // Adobe CMYK JPEG'leri ters çevrilmiş değerlerle saklar
+                if image.components == 4 {
+                    dict.set("Decode", vec![1.into(), 0.into(), 1.into(), 0.into(), 1.into(), 0.into(), 1.into(), 0.into()]);
+                }
+            }
+            let image_id = doc.add_object(Stream::new(dict, image.data).with_compression(!image.jpeg));
+            xobjects.set(format!("Im{}", index + 1), image_id);
+        }
+
+        let opacity = |value: Option<f32>| {
+            let value = value.unwrap_or(1.0);
+            dictionary! { "Type" => "ExtGState", "ca" => value, "CA" => value }
+        };
+        let resources_id = doc.add_object(dictionary! {
+            "Font" => fonts,
+            "XObject" => xobjects,
+            "ExtGState" => dictionary! {
+                "GSw" => opacity(self.watermark.map(|watermark| watermark.opacity)),
+                "GSf" => opacity(self.footer.map(|footer| footer.opacity)),
+            },
+        });
+
+        // İç bağlantıların hedef sayfaları önceden bilinsin
+        let page_ids: Vec<ObjectId> = self.pages.iter().map(|_| doc.new_object_id()).collect();
+        let mut kids: Vec<Object> = Vec::new();
+        let page_count = self.pages.len();
+        let (paper, scale) = (self.paper, self.paper.scale);
+        for (page, page_id) in self.pages.into_iter().zip(&page_ids) {
+            let mut operations = Vec::with_capacity(page.operations.len() + stamp.len() + 3);
+            if scale != 1.0 {
+                operations.push(Operation::new("q", vec![]));
+                operations.push(Operation::new("cm", vec![scale.into(), 0.into(), 0.into(), scale.into(), 0.into(), 0.into()]));
+            }
+            operations.extend(page.operations);
+            if scale != 1.0 {
+                operations.push(Operation::new("Q", vec![]));
+            }
+            operations.extend(stamp.iter().cloned());
+            let content = Content { operations }.encode().map_err(|e| e.to_string())?;
+            let content_id = doc.add_object(Stream::new(dictionary! {}, content));
+            let annotations: Vec<Object> = page
+                .links
+                .into_iter()
+                .map(|(rect, url)| {
+                    let mut annotation = dictionary! {
+                        "Type" => "Annot",
+                        "Subtype" => "Link",
+                        "Rect" => rect.iter().map(|v| (*v * scale).into()).collect::<Vec<Object>>(),
+                        "Border" => vec![0.into(), 0.into(), 0.into()],
+                    };
+                    match url.strip_prefix('#').and_then(|id| self.anchors.get(id)) {
+                        Some((target, top)) => {
+                            let y = paper.height - top * scale;
+                            annotation.set("Dest", vec![page_ids[*target].into(), "XYZ".into(), Object::Null, y.into(), Object::Null]);
+                        }
+                        None => annotation.set("A", link_action(&url)),
+                    }
+                    let annotation_id: ObjectId = doc.add_object(annotation);
+                    annotation_id.into()
+                })
+                .collect();
+            let mut page_dict = dictionary! {
+                "Type" => "Page",
+                "Parent" => pages_id,
+                "Contents" => content_id,
+                "Resources" => resources_id,
+            };
+            if !annotations.is_empty() {
+                page_dict.set("Annots", annotations);
+            }
+            doc.objects.insert(*page_id, Object::Dictionary(page_dict));
+            kids.push((*page_id).into());
+        }
+
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => kids,
+                "Count" => page_count as i64,
+                "MediaBox" => vec![0.into(), 0.into(), paper.width.into(), paper.height.into()],
+            }),
+        );
+        let catalog_id = doc.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+        });
+        doc.trailer.set("Root", catalog_id);
+        let outline: Vec<OutlineItem> = self
+            .outline
+            .into_iter()
+            .map(|(heading, page, top)| OutlineItem { level: heading.level, title: heading.text.trim().to_string(), page: page_ids[page], top: Some(paper.height - top * scale) })
+            .collect();
+        pdf_meta::write_outline(&mut doc, &outline)?;
+        pdf_meta::write_info(&mut doc, self.metadata, output_path)?;
+        doc.compress();
+        doc.save(output_path).map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
+
+fn plain_text<'n>(node: &'n AstNode<'n>) -> String {
+    let mut text = String::new();
+    for descendant in node.descendants() {
+        match &descendant.data.borrow().value {
+            NodeValue::Text(t) => text.push_str(t),
+            NodeValue::Code(code) => text.push_str(&code.literal),
+            NodeValue::SoftBreak | NodeValue::LineBreak => text.push(' '),
+            _ => {}
+        }
+    }
+    text
+}
+
+fn longest_word(inlines: &[&Inline], size: f32) -> f32 {
+    let mut longest = 0.0f32;
+    for inline in inlines {
+        if let Inline::Text(text, style) = inline {
+            let font = Font::for_style(style);
+            for word in text.split_whitespace() {
+                longest = longest.max(font.text_width(word, style.size(size)));
+            }
+        }
+    }
+    longest
+}
+
+// Kelime bazlı satır kaydırma; sığmayan tek kelimeler (URL vb.) karakterden bölünür
+fn wrap(inlines: &[&Inline], size: f32, max_width: f32) -> Vec<Line> {
+    let mut lines = Vec::new();
+    let mut current = Line::default();
+    let mut pending_space: Option<Style> = None;
+
+    for inline in inlines {
+        let (text, style) = match inline {
+            Inline::Text(text, style) => (text, *style),
+            Inline::Break => {
+                lines.push(std::mem::take(&mut current));
+                pending_space = None;
+                continue;
+            }
+            Inline::Image { .. } => continue,
+        };
+        let font = Font::for_style(&style);
+        let piece_size = style.size(size);
+        for token in split_tokens(text) {
+            if token.trim().is_empty() {
+                if !current.pieces.is_empty() {
+                    pending_space = Some(style);
+                }
+                continue;
+            }
+            let word_width = font.text_width(token, piece_size);
+            let space_width = pending_space
+                .map(|s| Font::for_style(&s).text_width(" ", s.size(size)))
+                .unwrap_or(0.0);
+            if !current.pieces.is_empty() && current.width + space_width + word_width > max_width {
+                lines.push(std::mem::take(&mut current));
+                pending_space = None;
+            }
+            if let Some(space_style) = pending_space.take() {
+                if !current.pieces.is_empty() {
+                    current.push(" ", space_style, space_width);
+                }
+            }
+            if word_width <= max_width {
+                current.push(token, style, word_width);
+                continue;
+            }
+            for c in token.chars() {
+                let char_width = font.char_width(c) * piece_size / 1000.0;
+                if !current.pieces.is_empty() && current.width + char_width > max_width {
+                    lines.push(std::mem::take(&mut current));
+                }
+                current.push(c.encode_utf8(&mut [0; 4]), style, char_width);
+            }
+        }
+    }
+    if !current.pieces.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    // Baştaki boş satırlar (ör. yalnızca görsel içeren paragraf) çizilmez
+    if lines.len() == 1 && lines[0].pieces.is_empty() {
+        lines.clear();
+    }
+    lines
+}
+
+fn split_tokens(text: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut in_space = None;
+    for (i, c) in text.char_indices() {
+        let is_space = c.is_whitespace();
+        if in_space.is_some_and(|s| s != is_space) {
+            tokens.push(&text[start..i]);
+            start = i;
+        }
+        in_space = Some(is_space);
+    }
+    if start < text.len() {
+        tokens.push(&text[start..]);
+    }
+    tokens
+}
+
+fn load_image(url: &str, scope: &ReadScope) -> Option<ImageXObject> {
+    let bytes = images::load_image_bytes(url, scope)?;
+    match ImageFormat::detect(&bytes)? {
+        ImageFormat::Jpeg => {
+            let (width, height, components) = images::jpeg_info(&bytes)?;
+            let color_space = match components {
+                1 => "DeviceGray",
+                4 => "DeviceCMYK",
+                _ => "DeviceRGB",
+            };
+            Some(ImageXObject { width, height, color_space, components, data: bytes, jpeg: true, alpha: None })
+        }
+        ImageFormat::Png => decode_png(&bytes),
+        ImageFormat::Gif => None,
+    }
+}
+
+fn decode_png(bytes: &[u8]) -> Option<ImageXObject> {
+    let mut decoder = png::Decoder::new(std::io::Cursor::new(bytes));
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().ok()?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer).ok()?;
+    buffer.truncate(info.buffer_size());
+
+    let (color_space, channels, has_alpha) = match info.color_type {
+        png::ColorType::Grayscale => ("DeviceGray", 1, false),
+        png::ColorType::GrayscaleAlpha => ("DeviceGray", 1, true),
+        png::ColorType::Rgb => ("DeviceRGB", 3, false),
+        png::ColorType::Rgba => ("DeviceRGB", 3, true),
+        png::ColorType::Indexed => return None,
+    };
+    let (data, alpha) = if has_alpha {
+        let stride = channels + 1;
+        let mut data = Vec::with_capacity(buffer.len() / stride * channels);
+        let mut alpha = Vec::with_capacity(buffer.len() / stride);
+        for pixel in buffer.chunks_exact(stride) {
+            data.extend_from_slice(&pixel[..channels]);
+            alpha.push(pixel[channels]);
+        }
+        (data, Some(alpha))
+    } else {
+        (buffer, None)
+    };
+    Some(ImageXObject {
+        width: info.width,
+        height: info.height,
+        color_space,
+        components: channels as u8,
+        data,
+        jpeg: false,
+        alpha,
+    })
+}
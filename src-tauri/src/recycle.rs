@@ -0,0 +1,283 @@
+// Silinen dosya ve klasörler kalıcı olarak silinmez: sistem çöp kutusuna (Linux'ta freedesktop
+// Trash, Windows'ta Geri Dönüşüm Kutusu) taşınır. Sistem çöp kutusu kullanılamıyorsa ya da
+// listeleme/geri yükleme desteklenmiyorsa (macOS) çalışma alanındaki .pignote-trash klasörü kullanılır.
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+use crate::search_index::SearchIndexState;
+use crate::workspace::WorkspaceState;
+
+pub const WORKSPACE_TRASH_DIR: &str = ".pignote-trash";
+
+const SYSTEM_PREFIX: &str = "system:";
+const WORKSPACE_PREFIX: &str = "workspace:";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrashLocation {
+    System,
+    Workspace,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashEntry {
+    // restore_from_trash'e aynen geri verilir. None: öğe sistem çöp kutusuna taşındı ama listede
+    // bulunamadı; kimlikle geri yüklenemez, sistemin kendi çöp kutusundan geri alınabilir
+    pub id: Option<String>,
+    pub name: String,
+    pub original_path: String,
+    pub deleted_at: Option<String>,
+    pub location: TrashLocation,
+}
+
+// .pignote-trash/info/<ad>.json içeriği
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WorkspaceTrashInfo {
+    original_path: String,
+    deleted_at: String,
+}
+
+fn is_in_workspace_trash(path: &Path) -> bool {
+    path.components().any(|c| c.as_os_str() == WORKSPACE_TRASH_DIR)
+}
+
+pub fn absolute(path: &Path) -> Result<PathBuf, String> {
+    if path.is_absolute() {
+        Ok(path.to_path_buf())
+    } else {
+        Ok(std::env::current_dir().map_err(|e| e.to_string())?.join(path))
+    }
+}
+
+fn format_timestamp(seconds: i64) -> Option<String> {
+    chrono::DateTime::from_timestamp(seconds, 0).map(|t| t.to_rfc3339())
+}
+
+#[cfg(any(target_os = "windows", all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))))]
+mod system {
+    use super::{absolute, format_timestamp, TrashEntry, TrashLocation, WorkspaceState, SYSTEM_PREFIX};
+    use std::path::Path;
+
+    fn to_entry(item: &trash::TrashItem) -> TrashEntry {
+        TrashEntry {
+            id: Some(format!("{}{}", SYSTEM_PREFIX, item.id.to_string_lossy())),
+            name: item.name.to_string_lossy().to_string(),
+            original_path: item.original_path().to_string_lossy().to_string(),
+            deleted_at: format_timestamp(item.time_deleted),
+            location: TrashLocation::System,
+        }
+    }
+
+    // Yalnızca taşıma başarısız olursa hata döner (çağıran çalışma alanı çöp kutusuna düşer). Öğe taşındıktan
+    // sonra listede bulunamazsa silme yine başarılıdır; kimliksiz bir kayıt döner.
+    pub fn delete(path: &Path) -> Result<TrashEntry, String> {
+        let original = absolute(path)?;
+        trash::delete(&original).map_err(|e| e.to_string())?;
+        Ok(find_deleted(&original).unwrap_or_else(|e| {
+            log::warn!("Çöp kutusuna taşınan öğe bulunamadı, geri yükleme kimliği verilemiyor: {}", e);
+            TrashEntry {
+                id: None,
+                name: original.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+                original_path: original.to_string_lossy().to_string(),
+                deleted_at: Some(chrono::Utc::now().to_rfc3339()),
+                location: TrashLocation::System,
+            }
+        }))
+    }
+
+    // trash::delete öğeyi döndürmüyor; geri alınabilmesi için en son silinen eşleşmeyi bul
+    fn find_deleted(original: &Path) -> Result<TrashEntry, String> {
+        let items = trash::os_limited::list().map_err(|e| e.to_string())?;
+        items
+            .iter()
+            .filter(|item| item.original_path() == original)
+            .max_by_key(|item| item.time_deleted)
+            .map(to_entry)
+            .ok_or_else(|| "Öğe listede yok".to_string())
+    }
+
+    pub fn list(root: Option<&Path>) -> Result<Vec<TrashEntry>, String> {
+        let items = trash::os_limited::list().map_err(|e| e.to_string())?;
+        Ok(items
+            .iter()
+            .filter(|item| root.map_or(true, |r| item.original_parent.starts_with(r)))
+            .map(to_entry)
+            .collect())
+    }
+
+    pub fn restore(id: &str, workspace: &WorkspaceState) -> Result<String, String> {
+        let items = trash::os_limited::list().map_err(|e| e.to_string())?;
+        let item = items
+            .into_iter()
+            .find(|item| item.id.to_string_lossy() == id)
+            .ok_or("Öğe çöp kutusunda bulunamadı")?;
+        workspace.check(item.original_path())?;
+        let original = item.original_path().to_string_lossy().to_string();
+        trash::os_limited::restore_all([item]).map_err(|e| match e {
+            trash::Error::RestoreCollision { .. } => "Geri yüklenecek konumda aynı isimde bir öğe var".to_string(),
+            other => other.to_string(),
+        })?;
+        Ok(original)
+    }
+}
+
+// Sistem çöp kutusunun listelenemediği platformlar: her silme çalışma alanı çöp kutusuna gider
+#[cfg(not(any(target_os = "windows", all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android")))))]
+mod system {
+    use super::{TrashEntry, WorkspaceState};
+    use std::path::Path;
+
+    pub fn delete(_path: &Path) -> Result<TrashEntry, String> {
+        Err("Sistem çöp kutusu bu platformda geri yüklemeyi desteklemiyor".to_string())
+    }
+
+    pub fn list(_root: Option<&Path>) -> Result<Vec<TrashEntry>, String> {
+        Ok(Vec::new())
+    }
+
+    pub fn restore(_id: &str, _workspace: &WorkspaceState) -> Result<String, String> {
+        Err("Sistem çöp kutusu bu platformda desteklenmiyor".to_string())
+    }
+}
+
+// Çalışma alanı kökü verilmişse ve yol onun içindeyse oraya, değilse dosyanın yanına
+fn workspace_trash_dir(path: &Path, root: Option<&Path>) -> PathBuf {
+    match root {
+        Some(root) if path.starts_with(root) && path != root => root.join(WORKSPACE_TRASH_DIR),
+        _ => path.parent().unwrap_or(Path::new(".")).join(WORKSPACE_TRASH_DIR),
+    }
+}
+
+fn workspace_delete(path: &Path, root: Option<&Path>) -> Result<TrashEntry, String> {
+    let trash_dir = workspace_trash_dir(path, root);
+    let files_dir = trash_dir.join("files");
+    let info_dir = trash_dir.join("info");
+    fs::create_dir_all(&files_dir).map_err(|e| e.to_string())?;
+    fs::create_dir_all(&info_dir).map_err(|e| e.to_string())?;
+
+    let name = path.file_name().ok_or("Geçersiz dosya yolu")?.to_string_lossy().to_string();
+    let now = chrono::Utc::now();
+    let stored_name = format!("{}-{}", now.timestamp_millis(), name);
+    let stored = files_dir.join(&stored_name);
+
+    let info = WorkspaceTrashInfo {
+        original_path: absolute(path)?.to_string_lossy().to_string(),
+        deleted_at: now.to_rfc3339(),
+    };
+    let info_json = serde_json::to_string_pretty(&info).map_err(|e| e.to_string())?;
+    fs::write(info_dir.join(format!("{}.json", stored_name)), info_json).map_err(|e| e.to_string())?;
+    // Yalnızca taşıma: farklı diskler arasında kopyala-sil yapılmaz, veri kaybı riski alınmaz
+    if let Err(e) = fs::rename(path, &stored) {
+        let _ = fs::remove_file(info_dir.join(format!("{}.json", stored_name)));
+        return Err(format!("Çöp kutusuna taşınamadı: {}", e));
+    }
+
+    Ok(TrashEntry {
+        id: Some(format!("{}{}", WORKSPACE_PREFIX, stored.to_string_lossy())),
+        name,
+        original_path: info.original_path,
+        deleted_at: Some(info.deleted_at),
+        location: TrashLocation::Workspace,
+    })
+}
+
+fn workspace_list(root: &Path) -> Vec<TrashEntry> {
+    let trash_dir = root.join(WORKSPACE_TRASH_DIR);
+    let Ok(entries) = fs::read_dir(trash_dir.join("files")) else { return Vec::new() };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let stored_name = entry.file_name().to_string_lossy().to_string();
+            let info_path = trash_dir.join("info").join(format!("{}.json", stored_name));
+            let info: WorkspaceTrashInfo = serde_json::from_str(&fs::read_to_string(info_path).ok()?).ok()?;
+            let name = Path::new(&info.original_path).file_name()?.to_string_lossy().to_string();
+            Some(TrashEntry {
+                id: Some(format!("{}{}", WORKSPACE_PREFIX, entry.path().to_string_lossy())),
+                name,
+                original_path: info.original_path,
+                deleted_at: Some(info.deleted_at),
+                location: TrashLocation::Workspace,
+            })
+        })
+        .collect()
+}
+
+fn workspace_restore(stored: &Path, workspace: &WorkspaceState) -> Result<String, String> {
+    workspace.check_entry(stored)?;
+    // Kimlik bir yol olduğu için yalnızca .pignote-trash/files altındaki öğeler kabul edilir
+    let files_dir = stored.parent().ok_or("Geçersiz çöp kutusu öğesi")?;
+    let trash_dir = files_dir.parent().ok_or("Geçersiz çöp kutusu öğesi")?;
+    if files_dir.file_name() != Some("files".as_ref()) || trash_dir.file_name() != Some(WORKSPACE_TRASH_DIR.as_ref()) {
+        return Err("Geçersiz çöp kutusu öğesi".to_string());
+    }
+    if !stored.exists() {
+        return Err("Öğe çöp kutusunda bulunamadı".to_string());
+    }
+    let stored_name = stored.file_name().ok_or("Geçersiz çöp kutusu öğesi")?.to_string_lossy().to_string();
+    let info_path = trash_dir.join("info").join(format!("{}.json", stored_name));
+    let info: WorkspaceTrashInfo = serde_json::from_str(&fs::read_to_string(&info_path).map_err(|e| e.to_string())?)
+        .map_err(|e| e.to_string())?;
+
+    let original = PathBuf::from(&info.original_path);
+    workspace.check_entry(&original)?;
+    if original.exists() {
+        return Err("Geri yüklenecek konumda aynı isimde bir öğe var".to_string());
+    }
+    if let Some(parent) = original.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    fs::rename(stored, &original).map_err(|e| e.to_string())?;
+    let _ = fs::remove_file(info_path);
+    Ok(info.original_path)
+}
+
+// Dosya/klasörü çöp kutusuna taşır; geri almak için dönen kimlik restore_from_trash'e verilir
+#[tauri::command]
+pub async fn delete_file(index: State<'_, SearchIndexState>, workspace: State<'_, WorkspaceState>, path: String, root: Option<String>) -> Result<TrashEntry, String> {
+    let path = workspace.check_entry(&path)?;
+    let root = workspace.check_or_root(root.as_deref())?;
+    if !path.exists() && !path.is_symlink() {
+        return Err("Silinecek dosya bulunamadı".to_string());
+    }
+    if is_in_workspace_trash(&path) {
+        return Err("Çöp kutusundaki öğeler buradan silinemez".to_string());
+    }
+    let entry = match system::delete(&path) {
+        Ok(entry) => entry,
+        Err(e) => {
+            log::warn!("Sistem çöp kutusu kullanılamadı, çalışma alanı çöp kutusuna taşınıyor: {}", e);
+            workspace_delete(&path, Some(&root))?
+        }
+    };
+    index.path_removed(&path);
+    Ok(entry)
+}
+
+// Çöp kutusundaki öğeler; kök verilmezse çalışma alanından silinenler
+#[tauri::command]
+pub async fn list_trash(workspace: State<'_, WorkspaceState>, root: Option<String>) -> Result<Vec<TrashEntry>, String> {
+    let root = workspace.check_or_root(root.as_deref())?;
+    let mut entries = system::list(Some(&root))?;
+    entries.extend(workspace_list(&root));
+    // En son silinen en üstte
+    entries.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+    Ok(entries)
+}
+
+// Öğeyi eski yerine geri koyar ve geri yüklenen yolu döndürür
+#[tauri::command]
+pub async fn restore_from_trash(index: State<'_, SearchIndexState>, workspace: State<'_, WorkspaceState>, id: String) -> Result<String, String> {
+    let restored = if let Some(system_id) = id.strip_prefix(SYSTEM_PREFIX) {
+        system::restore(system_id, &workspace)?
+    } else if let Some(stored) = id.strip_prefix(WORKSPACE_PREFIX) {
+        workspace_restore(Path::new(stored), &workspace)?
+    } else {
+        return Err("Geçersiz çöp kutusu öğesi".to_string());
+    };
+    index.path_added(Path::new(&restored));
+    Ok(restored)
+}
@@ -0,0 +1,208 @@
+// Çökme/disk dolması durumunda notu yarım bırakmayan kaydetme: içerik önce aynı klasördeki
+// geçici dosyaya yazılıp diske senkronlanır, sonra tek adımda asıl dosyanın yerine taşınır.
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::fs;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::UNIX_EPOCH;
+use tauri::State;
+
+use crate::workspace::{AccessError, WorkspaceState};
+
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WriteOptions {
+    // Önceki sürümü "not.md.bak" olarak sakla
+    #[serde(default)]
+    pub backup: bool,
+    // Dosya yüklendiğinde alınan parmak izi; diskteki dosya farklıysa kayıt reddedilir
+    pub expected_modified: Option<u64>,
+    pub expected_hash: Option<String>,
+}
+
+// Dosyanın diskteki hâli: değiştirilme zamanı (Unix ms) ve içeriğin SHA-256 özeti
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileFingerprint {
+    pub modified: Option<u64>,
+    pub hash: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WriteResult {
+    pub path: String,
+    pub fingerprint: FileFingerprint,
+    pub backup_path: Option<String>,
+}
+
+// Ön yüz çakışmayı `kind` alanından ayırt edip kullanıcıya yeniden yükleme önerebilir
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WriteError {
+    Conflict { path: String, message: String, actual: Option<FileFingerprint> },
+    Io { path: String, message: String },
+    // Yol çalışma alanının dışında ya da geçersiz
+    PermissionDenied { path: String, message: String },
+    // Şifreli not yazılacak ama parola girilmemiş ya da otomatik kilit devreye girmiş
+    Locked { path: String, message: String },
+}
+
+impl fmt::Display for WriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WriteError::Conflict { message, .. }
+            | WriteError::Io { message, .. }
+            | WriteError::PermissionDenied { message, .. }
+            | WriteError::Locked { message, .. } => {
+                write!(f, "{}", message)
+            }
+        }
+    }
+}
+
+impl From<AccessError> for WriteError {
+    fn from(e: AccessError) -> WriteError {
+        let path = match &e {
+            AccessError::PermissionDenied { path, .. } | AccessError::InvalidPath { path, .. } => path.clone(),
+            AccessError::NoWorkspace { .. } => String::new(),
+        };
+        WriteError::PermissionDenied { path, message: e.to_string() }
+    }
+}
+
+impl From<WriteError> for String {
+    fn from(e: WriteError) -> String {
+        e.to_string()
+    }
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> WriteError {
+    WriteError::Io { path: path.to_string_lossy().to_string(), message: e.to_string() }
+}
+
+pub fn fingerprint(path: &Path) -> std::io::Result<FileFingerprint> {
+    let bytes = fs::read(path)?;
+    let modified = fs::metadata(path)?
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64);
+    Ok(FileFingerprint { modified, hash: hash_bytes(&bytes), size: bytes.len() as u64 })
+}
+
+pub fn hash_bytes(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn write_atomic(path: &Path, content: &[u8], options: &WriteOptions) -> Result<WriteResult, WriteError> {
+    // Sembolik bağın kendisini değil işaret ettiği dosyayı değiştir
+    let target = if path.is_symlink() {
+        fs::canonicalize(path).map_err(|e| io_error(path, e))?
+    } else {
+        path.to_path_buf()
+    };
+
+    let existing = match fingerprint(&target) {
+        Ok(fp) => Some(fp),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(io_error(&target, e)),
+    };
+    check_precondition(&target, existing.as_ref(), options)?;
+
+    let backup_path = match (&existing, options.backup) {
+        (Some(_), true) => {
+            let backup = backup_path(&target);
+            fs::copy(&target, &backup).map_err(|e| io_error(&backup, e))?;
+            Some(backup.to_string_lossy().to_string())
+        }
+        _ => None,
+    };
+
+    let temp = temp_path(&target);
+    if let Err(e) = write_temp(&temp, &target, content) {
+        let _ = fs::remove_file(&temp);
+        return Err(io_error(&target, e));
+    }
+    if let Err(e) = fs::rename(&temp, &target) {
+        let _ = fs::remove_file(&temp);
+        return Err(io_error(&target, e));
+    }
+    sync_parent(&target);
+
+    let fingerprint = fingerprint(&target).map_err(|e| io_error(&target, e))?;
+    Ok(WriteResult { path: path.to_string_lossy().to_string(), fingerprint, backup_path })
+}
+
+fn check_precondition(path: &Path, existing: Option<&FileFingerprint>, options: &WriteOptions) -> Result<(), WriteError> {
+    if options.expected_modified.is_none() && options.expected_hash.is_none() {
+        return Ok(());
+    }
+    let conflict = |message: &str| WriteError::Conflict {
+        path: path.to_string_lossy().to_string(),
+        message: message.to_string(),
+        actual: existing.cloned(),
+    };
+    let Some(existing) = existing else {
+        return Err(conflict("Dosya yüklendikten sonra diskten silinmiş"));
+    };
+    // Özet verilmişse yalnızca içerik karşılaştırılır; dokunulup değişmeyen dosya çakışma sayılmaz
+    let changed = match (&options.expected_hash, options.expected_modified) {
+        (Some(hash), _) => !hash.eq_ignore_ascii_case(&existing.hash),
+        (None, Some(modified)) => existing.modified != Some(modified),
+        (None, None) => false,
+    };
+    if changed {
+        return Err(conflict("Dosya yüklendikten sonra başka bir uygulama tarafından değiştirilmiş"));
+    }
+    Ok(())
+}
+
+fn write_temp(temp: &Path, target: &Path, content: &[u8]) -> std::io::Result<()> {
+    let mut file = fs::OpenOptions::new().write(true).create_new(true).open(temp)?;
+    file.write_all(content)?;
+    file.sync_all()?;
+    // Var olan dosyanın izinleri yeni dosyaya taşınsın
+    if let Ok(metadata) = fs::metadata(target) {
+        fs::set_permissions(temp, metadata.permissions())?;
+    }
+    Ok(())
+}
+
+fn temp_path(target: &Path) -> PathBuf {
+    let name = target.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let unique = format!(".{}.{}-{}.pignote-tmp", name, std::process::id(), TEMP_COUNTER.fetch_add(1, Ordering::Relaxed));
+    target.with_file_name(unique)
+}
+
+pub fn backup_path(target: &Path) -> PathBuf {
+    let mut name = target.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    target.with_file_name(name)
+}
+
+// Yeniden adlandırmanın kalıcı olması için klasör girdisini de senkronla (Windows'ta desteklenmiyor)
+#[cfg(unix)]
+fn sync_parent(target: &Path) {
+    if let Some(parent) = target.parent().filter(|p| !p.as_os_str().is_empty()) {
+        if let Ok(dir) = fs::File::open(parent) {
+            let _ = dir.sync_all();
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_parent(_target: &Path) {}
+
+// Editör dosyayı yüklediğinde parmak izini alır, kaydederken geri gönderir
+#[tauri::command]
+pub async fn file_fingerprint(workspace: State<'_, WorkspaceState>, path: String) -> Result<FileFingerprint, String> {
+    let path = workspace.check(&path)?;
+    fingerprint(&path).map_err(|e| e.to_string())
+}
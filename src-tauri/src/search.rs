@@ -0,0 +1,315 @@
+// Çalışma alanında tam metin arama. Sonuçlar dosya dosya "search-result" olayıyla akar,
+// bitişte "search-finished" gönderilir. Yeni bir arama ya da cancel_search öncekini durdurur.
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tauri::{AppHandle, Emitter, State};
+
+use crate::workspace::WorkspaceState;
+
+pub const SEARCH_RESULT_EVENT: &str = "search-result";
+pub const SEARCH_FINISHED_EVENT: &str = "search-finished";
+
+// Her zaman atlanan klasörler
+const DEFAULT_IGNORES: &[&str] = &["**/.git", "**/node_modules", "**/.pignote-trash"];
+pub const DEFAULT_EXTENSIONS: &[&str] = &["md", "markdown", "txt"];
+pub const MAX_FILE_SIZE: u64 = 5 * 1024 * 1024;
+const MAX_LINE_PREVIEW: usize = 400;
+
+static NEXT_SEARCH_ID: AtomicU64 = AtomicU64::new(1);
+// Çalışmaya devam etmesi gereken aramanın kimliği; 0 ise hiçbiri
+static ACTIVE_SEARCH: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SearchOptions {
+    pub regex: bool,
+    pub case_sensitive: bool,
+    pub whole_word: bool,
+    // Kullanıcının glob kalıpları (ör. "arsiv/**", "*.txt"); varsayılanlara eklenir
+    pub ignore: Vec<String>,
+    // Boşsa md/markdown/txt
+    pub extensions: Vec<String>,
+    // Eşleşen satırın önünde/arkasında gösterilecek satır sayısı
+    pub context_lines: usize,
+    pub max_results: usize,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        SearchOptions {
+            regex: false,
+            case_sensitive: false,
+            whole_word: false,
+            ignore: Vec::new(),
+            extensions: Vec::new(),
+            context_lines: 1,
+            max_results: 2000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchMatch {
+    // 1 tabanlı satır; sütun UTF-16 birimi cinsinden (editörle aynı)
+    pub line: usize,
+    pub column: usize,
+    pub length: usize,
+    pub text: String,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchFileResult {
+    pub search_id: u64,
+    pub path: String,
+    pub matches: Vec<SearchMatch>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchSummary {
+    pub search_id: u64,
+    pub files_scanned: usize,
+    pub files_matched: usize,
+    pub match_count: usize,
+    // max_results'a ulaşıldığı için durduruldu
+    pub truncated: bool,
+    pub cancelled: bool,
+}
+
+fn build_matcher(query: &str, options: &SearchOptions) -> Result<Regex, String> {
+    let pattern = if options.regex { query.to_string() } else { regex::escape(query) };
+    let pattern = if options.whole_word { format!(r"\b(?:{})\b", pattern) } else { pattern };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(!options.case_sensitive)
+        .build()
+        .map_err(|e| format!("Geçersiz düzenli ifade: {}", e))
+}
+
+pub fn build_ignore_set(patterns: &[String]) -> Result<GlobSet, String> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in DEFAULT_IGNORES.iter().copied().chain(patterns.iter().map(String::as_str)) {
+        let pattern = pattern.trim();
+        if pattern.is_empty() {
+            continue;
+        }
+        builder.add(Glob::new(pattern).map_err(|e| format!("Geçersiz yok sayma kalıbı '{}': {}", pattern, e))?);
+        // "arsiv" gibi yalın klasör adları her derinlikte eşleşsin
+        if !pattern.contains('/') {
+            builder.add(Glob::new(&format!("**/{}", pattern)).map_err(|e| e.to_string())?);
+        }
+    }
+    builder.build().map_err(|e| e.to_string())
+}
+
+pub fn is_note_file(path: &Path) -> bool {
+    path.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .is_some_and(|e| DEFAULT_EXTENSIONS.contains(&e.as_str()))
+}
+
+// `dir` altındaki not dosyaları; yok sayma kalıpları `root`a göre uygulanır. Sembolik bağlar (klasör ya da dosya)
+// atlanır: çalışma alanı dışındaki bir nota bağ, arama, etiket ve dizin üzerinden içeriğini sızdırmasın.
+pub fn note_files(root: &Path, dir: &Path, ignore: &GlobSet) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else { continue };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if ignore.is_match(path.strip_prefix(root).unwrap_or(&path)) {
+                continue;
+            }
+            let Ok(file_type) = entry.file_type() else { continue };
+            if file_type.is_dir() {
+                stack.push(path);
+            } else if file_type.is_file() && is_note_file(&path) {
+                files.push(path);
+            }
+        }
+    }
+    files
+}
+
+fn utf16_len(s: &str) -> usize {
+    s.encode_utf16().count()
+}
+
+pub fn preview(line: &str) -> String {
+    if line.chars().count() <= MAX_LINE_PREVIEW {
+        line.to_string()
+    } else {
+        let cut: String = line.chars().take(MAX_LINE_PREVIEW).collect();
+        format!("{}…", cut)
+    }
+}
+
+fn search_text(content: &str, matcher: &Regex, context_lines: usize, remaining: usize) -> Vec<SearchMatch> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut matches = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        for found in matcher.find_iter(line) {
+            if found.as_str().is_empty() {
+                continue;
+            }
+            if matches.len() >= remaining {
+                return matches;
+            }
+            let from = index.saturating_sub(context_lines);
+            let to = (index + 1 + context_lines).min(lines.len());
+            matches.push(SearchMatch {
+                line: index + 1,
+                column: utf16_len(&line[..found.start()]) + 1,
+                length: utf16_len(found.as_str()),
+                text: preview(line),
+                before: lines[from..index].iter().map(|l| preview(l)).collect(),
+                after: lines[index + 1..to].iter().map(|l| preview(l)).collect(),
+            });
+        }
+    }
+    matches
+}
+
+struct Search {
+    id: u64,
+    root: PathBuf,
+    matcher: Regex,
+    ignore: GlobSet,
+    extensions: Vec<String>,
+    options: SearchOptions,
+    summary: SearchSummary,
+}
+
+impl Search {
+    fn is_active(&self) -> bool {
+        ACTIVE_SEARCH.load(Ordering::SeqCst) == self.id
+    }
+
+    fn is_ignored(&self, path: &Path) -> bool {
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        self.ignore.is_match(relative)
+    }
+
+    fn wants_file(&self, path: &Path) -> bool {
+        path.extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .is_some_and(|e| self.extensions.contains(&e))
+    }
+
+    // list_directory ile aynı read_dir gezintisi; sembolik bağlı klasörlere girilmez (döngü riski)
+    fn run(&mut self, app: &AppHandle) {
+        let mut stack = vec![self.root.clone()];
+        while let Some(dir) = stack.pop() {
+            let Ok(entries) = fs::read_dir(&dir) else { continue };
+            let mut entries: Vec<_> = entries.filter_map(|e| e.ok()).collect();
+            entries.sort_by_key(|e| e.file_name());
+            // Yığından alfabetik sırayla çıksın diye klasörler ters sırada eklenir
+            let mut subdirs = Vec::new();
+            for entry in entries {
+                if !self.is_active() {
+                    self.summary.cancelled = true;
+                    return;
+                }
+                let path = entry.path();
+                if self.is_ignored(&path) {
+                    continue;
+                }
+                let Ok(file_type) = entry.file_type() else { continue };
+                if file_type.is_dir() {
+                    subdirs.push(path);
+                } else if self.wants_file(&path) {
+                    self.scan_file(app, &path);
+                    if self.summary.truncated {
+                        return;
+                    }
+                }
+            }
+            stack.extend(subdirs.into_iter().rev());
+        }
+    }
+
+    fn scan_file(&mut self, app: &AppHandle, path: &Path) {
+        if fs::metadata(path).map(|m| m.len() > MAX_FILE_SIZE).unwrap_or(true) {
+            return;
+        }
+        let Ok(bytes) = fs::read(path) else { return };
+        // İkili dosyaları atla
+        if bytes.contains(&0) {
+            return;
+        }
+        self.summary.files_scanned += 1;
+        let content = String::from_utf8_lossy(&bytes);
+        let remaining = self.options.max_results - self.summary.match_count;
+        let matches = search_text(&content, &self.matcher, self.options.context_lines, remaining);
+        if matches.is_empty() {
+            return;
+        }
+        self.summary.files_matched += 1;
+        self.summary.match_count += matches.len();
+        if self.summary.match_count >= self.options.max_results {
+            self.summary.truncated = true;
+        }
+        let result = SearchFileResult { search_id: self.id, path: path.to_string_lossy().to_string(), matches };
+        if let Err(e) = app.emit(SEARCH_RESULT_EVENT, result) {
+            log::warn!("Arama sonucu gönderilemedi: {}", e);
+        }
+    }
+}
+
+// Aramayı başlatır ve hemen kimliğini döndürür; sonuçlar olaylarla gelir
+#[tauri::command]
+pub async fn search_workspace(app: AppHandle, workspace: State<'_, WorkspaceState>, root: String, query: String, options: Option<SearchOptions>) -> Result<u64, String> {
+    workspace.check(&root)?;
+    if query.is_empty() {
+        return Err("Arama ifadesi boş olamaz".to_string());
+    }
+    let root = PathBuf::from(root);
+    if !root.is_dir() {
+        return Err("Arama klasörü bulunamadı".to_string());
+    }
+    let mut options = options.unwrap_or_default();
+    options.max_results = options.max_results.max(1);
+    let matcher = build_matcher(&query, &options)?;
+    let ignore = build_ignore_set(&options.ignore)?;
+    let extensions = if options.extensions.is_empty() {
+        DEFAULT_EXTENSIONS.iter().map(|e| e.to_string()).collect()
+    } else {
+        options.extensions.iter().map(|e| e.trim_start_matches('.').to_lowercase()).collect()
+    };
+
+    let id = NEXT_SEARCH_ID.fetch_add(1, Ordering::SeqCst);
+    // Önceki arama varsa bir sonraki kontrolde kendiliğinden durur
+    ACTIVE_SEARCH.store(id, Ordering::SeqCst);
+
+    let summary = SearchSummary {
+        search_id: id,
+        files_scanned: 0,
+        files_matched: 0,
+        match_count: 0,
+        truncated: false,
+        cancelled: false,
+    };
+    let mut search = Search { id, root, matcher, ignore, extensions, options, summary };
+    std::thread::spawn(move || {
+        search.run(&app);
+        let _ = ACTIVE_SEARCH.compare_exchange(id, 0, Ordering::SeqCst, Ordering::SeqCst);
+        if let Err(e) = app.emit(SEARCH_FINISHED_EVENT, &search.summary) {
+            log::warn!("Arama özeti gönderilemedi: {}", e);
+        }
+    });
+    Ok(id)
+}
+
+// Süren aramayı durdurur (export iptali gibi); arama bitişte cancelled: true ile raporlanır
+#[tauri::command]
+pub async fn cancel_search() -> Result<(), String> {
+    ACTIVE_SEARCH.store(0, Ordering::SeqCst);
+    Ok(())
+}
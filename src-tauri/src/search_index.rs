@@ -0,0 +1,588 @@
+// Büyük not kasaları için diskte tutulan ters dizin. Her çalışma alanı için uygulama veri
+// klasöründe tek bir dosya saklanır; açılışta değiştirilme zamanı/boyutu farklı olan dosyalar
+// yeniden dizinlenir, write_file / rename_file / delete_file ise dizini anında günceller.
+use globset::GlobSet;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, UNIX_EPOCH};
+use tauri::State;
+
+use crate::safe_write::{self, FileFingerprint};
+use crate::search;
+use crate::workspace::WorkspaceState;
+
+// Biçim değişirse artırılır; eski dosyalar sessizce yeniden oluşturulur
+const INDEX_VERSION: u32 = 1;
+// Art arda kayıtlarda dizin dosyası her seferinde yazılmasın
+const SAVE_DELAY: Duration = Duration::from_secs(2);
+const DEFAULT_LIMIT: usize = 50;
+// Base64 gömülü görseller gibi anlamsız uzun dizilerin dizini şişirmemesi için
+const MAX_TERM_LEN: usize = 64;
+// "a*" gibi çok kısa önekler binlerce terime açılmasın
+const MAX_PREFIX_TERMS: usize = 500;
+// BM25 parametreleri
+const K1: f64 = 1.2;
+const B: f64 = 0.75;
+
+#[derive(Serialize, Deserialize)]
+struct Document {
+    // Köke göreli, "/" ayraçlı yol
+    path: String,
+    modified: Option<u64>,
+    size: u64,
+    // Terim sayısı (BM25 uzunluk normalizasyonu için)
+    length: u32,
+    // Silerken hangi terim listelerine bakılacağı
+    terms: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Posting {
+    doc: u32,
+    // Belgedeki terim sıraları, artan
+    positions: Vec<u32>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct Index {
+    version: u32,
+    root: String,
+    next_doc: u32,
+    total_length: u64,
+    docs: HashMap<u32, Document>,
+    terms: BTreeMap<String, Vec<Posting>>,
+    #[serde(skip)]
+    by_path: HashMap<String, u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexHit {
+    pub path: String,
+    pub score: f64,
+    // İlk eşleşmenin bulunduğu satır (1 tabanlı) ve o satırın kısaltılmış hâli
+    pub line: Option<usize>,
+    pub snippet: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexStats {
+    pub root: String,
+    pub documents: usize,
+    pub terms: usize,
+}
+
+enum Clause {
+    Term(String),
+    Prefix(String),
+    Phrase(Vec<String>),
+}
+
+impl Clause {
+    fn first_term_matches(&self, term: &str) -> bool {
+        match self {
+            Clause::Term(t) => t == term,
+            Clause::Prefix(p) => term.starts_with(p.as_str()),
+            Clause::Phrase(terms) => terms[0] == term,
+        }
+    }
+}
+
+// Harf/rakam dizileri küçük harfe çevrilerek terim olur; "İ" küçültülünce gelen birleşik nokta atılır.
+// Her terimle birlikte metindeki bayt konumu döner (alıntı satırını bulmak için).
+fn tokenize(text: &str) -> Vec<(String, usize)> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        if c.is_alphanumeric() {
+            if current.is_empty() {
+                start = i;
+            }
+            current.extend(c.to_lowercase().filter(|l| *l != '\u{307}'));
+        } else if !current.is_empty() {
+            tokens.push((std::mem::take(&mut current), start));
+        }
+    }
+    if !current.is_empty() {
+        tokens.push((current, start));
+    }
+    tokens.retain(|(t, _)| t.chars().count() <= MAX_TERM_LEN);
+    tokens
+}
+
+// Sorgu dili: boşlukla ayrılan kelimelerin hepsi aranır (VE), "tırnak içi" ifade olarak,
+// sonu * ile biten kelime önek olarak eşleşir. "e-posta" gibi bölünen kelimeler ifade sayılır.
+fn parse_query(query: &str) -> Vec<Clause> {
+    let mut clauses = Vec::new();
+    for (i, segment) in query.split('"').enumerate() {
+        if i % 2 == 1 {
+            let terms: Vec<String> = tokenize(segment).into_iter().map(|(t, _)| t).collect();
+            match terms.len() {
+                0 => {}
+                1 => clauses.push(Clause::Term(terms.into_iter().next().unwrap())),
+                _ => clauses.push(Clause::Phrase(terms)),
+            }
+            continue;
+        }
+        for word in segment.split_whitespace() {
+            let (word, prefix) = match word.strip_suffix('*') {
+                Some(w) => (w, true),
+                None => (word, false),
+            };
+            let mut terms: Vec<String> = tokenize(word).into_iter().map(|(t, _)| t).collect();
+            if terms.is_empty() {
+                continue;
+            }
+            if prefix {
+                let last = terms.pop().unwrap();
+                clauses.extend(terms.into_iter().map(Clause::Term));
+                clauses.push(Clause::Prefix(last));
+            } else if terms.len() == 1 {
+                clauses.push(Clause::Term(terms.pop().unwrap()));
+            } else {
+                clauses.push(Clause::Phrase(terms));
+            }
+        }
+    }
+    clauses
+}
+
+impl Index {
+    fn new(root: &Path) -> Self {
+        Index { version: INDEX_VERSION, root: root.to_string_lossy().to_string(), ..Default::default() }
+    }
+
+    fn rebuild_paths(&mut self) {
+        self.by_path = self.docs.iter().map(|(id, doc)| (doc.path.clone(), *id)).collect();
+    }
+
+    fn remove_doc(&mut self, id: u32) {
+        let Some(doc) = self.docs.remove(&id) else { return };
+        self.by_path.remove(&doc.path);
+        self.total_length -= doc.length as u64;
+        for term in &doc.terms {
+            if let Some(postings) = self.terms.get_mut(term) {
+                postings.retain(|p| p.doc != id);
+                if postings.is_empty() {
+                    self.terms.remove(term);
+                }
+            }
+        }
+    }
+
+    // Dosyayı ya da klasörse altındaki her şeyi çıkarır
+    fn remove_path(&mut self, relative: &str) -> bool {
+        let nested = format!("{}/", relative);
+        let ids: Vec<u32> = self
+            .by_path
+            .iter()
+            .filter(|(path, _)| path.as_str() == relative || path.starts_with(&nested))
+            .map(|(_, id)| *id)
+            .collect();
+        for id in &ids {
+            self.remove_doc(*id);
+        }
+        !ids.is_empty()
+    }
+
+    fn insert(&mut self, relative: &str, modified: Option<u64>, size: u64, content: &str) {
+        if let Some(id) = self.by_path.get(relative).copied() {
+            self.remove_doc(id);
+        }
+        let id = self.next_doc;
+        self.next_doc += 1;
+
+        let tokens = tokenize(content);
+        let mut positions: HashMap<String, Vec<u32>> = HashMap::new();
+        for (position, (term, _)) in tokens.iter().enumerate() {
+            positions.entry(term.clone()).or_default().push(position as u32);
+        }
+        let mut terms = Vec::with_capacity(positions.len());
+        for (term, positions) in positions {
+            self.terms.entry(term.clone()).or_default().push(Posting { doc: id, positions });
+            terms.push(term);
+        }
+        self.total_length += tokens.len() as u64;
+        self.docs.insert(id, Document { path: relative.to_string(), modified, size, length: tokens.len() as u32, terms });
+        self.by_path.insert(relative.to_string(), id);
+    }
+
+    // Belge kimliği → eşleşme sayısı
+    fn evaluate(&self, clause: &Clause) -> HashMap<u32, u32> {
+        let mut counts = HashMap::new();
+        match clause {
+            Clause::Term(term) => {
+                for posting in self.terms.get(term).into_iter().flatten() {
+                    counts.insert(posting.doc, posting.positions.len() as u32);
+                }
+            }
+            Clause::Prefix(prefix) => {
+                let expanded = self
+                    .terms
+                    .range(prefix.clone()..)
+                    .take_while(|(term, _)| term.starts_with(prefix.as_str()))
+                    .take(MAX_PREFIX_TERMS);
+                for (_, postings) in expanded {
+                    for posting in postings {
+                        *counts.entry(posting.doc).or_insert(0) += posting.positions.len() as u32;
+                    }
+                }
+            }
+            Clause::Phrase(terms) => {
+                let lists: Vec<HashMap<u32, &Vec<u32>>> = terms
+                    .iter()
+                    .map(|t| self.terms.get(t).into_iter().flatten().map(|p| (p.doc, &p.positions)).collect())
+                    .collect();
+                for (doc, first) in &lists[0] {
+                    let Some(rest) = lists[1..].iter().map(|l| l.get(doc)).collect::<Option<Vec<_>>>() else { continue };
+                    let occurrences = first
+                        .iter()
+                        .filter(|&&start| {
+                            rest.iter().enumerate().all(|(offset, positions)| positions.binary_search(&(start + offset as u32 + 1)).is_ok())
+                        })
+                        .count();
+                    if occurrences > 0 {
+                        counts.insert(*doc, occurrences as u32);
+                    }
+                }
+            }
+        }
+        counts
+    }
+
+    // Tüm ifadeleri içeren belgeler, BM25 puanına göre
+    fn query(&self, clauses: &[Clause], limit: usize) -> Vec<(u32, f64)> {
+        let n = self.docs.len() as f64;
+        let average = if self.docs.is_empty() { 1.0 } else { (self.total_length as f64 / n).max(1.0) };
+        let mut scores: Option<HashMap<u32, f64>> = None;
+        for clause in clauses {
+            let counts = self.evaluate(clause);
+            let df = counts.len() as f64;
+            let idf = (1.0 + (n - df + 0.5) / (df + 0.5)).ln();
+            let mut next = HashMap::new();
+            for (doc, tf) in counts {
+                let previous = match &scores {
+                    Some(scores) => match scores.get(&doc) {
+                        Some(score) => *score,
+                        None => continue,
+                    },
+                    None => 0.0,
+                };
+                let length = self.docs.get(&doc).map_or(0, |d| d.length) as f64;
+                let tf = tf as f64;
+                let score = idf * tf * (K1 + 1.0) / (tf + K1 * (1.0 - B + B * length / average));
+                next.insert(doc, previous + score);
+            }
+            scores = Some(next);
+        }
+        let mut ranked: Vec<(u32, f64)> = scores.unwrap_or_default().into_iter().collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| self.docs[&a.0].path.cmp(&self.docs[&b.0].path)));
+        ranked.truncate(limit);
+        ranked
+    }
+}
+
+struct Workspace {
+    root: PathBuf,
+    ignore: GlobSet,
+    index: Index,
+}
+
+impl Workspace {
+    fn relative(&self, path: &Path) -> Option<String> {
+        let relative = path.strip_prefix(&self.root).ok()?;
+        let parts: Vec<_> = relative.components().map(|c| c.as_os_str().to_string_lossy()).collect();
+        (!parts.is_empty()).then(|| parts.join("/"))
+    }
+
+    fn wants(&self, path: &Path) -> bool {
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        search::is_note_file(path) && !self.ignore.is_match(relative)
+    }
+
+    fn files_under(&self, dir: &Path) -> Vec<PathBuf> {
+        search::note_files(&self.root, dir, &self.ignore)
+    }
+
+    fn index_file(&mut self, path: &Path) -> bool {
+        let Some(relative) = self.relative(path) else { return false };
+        let Ok(metadata) = fs::metadata(path) else { return self.index.remove_path(&relative) };
+        if metadata.len() > search::MAX_FILE_SIZE {
+            return self.index.remove_path(&relative);
+        }
+        let Ok(bytes) = fs::read(path) else { return false };
+        if bytes.contains(&0) {
+            return self.index.remove_path(&relative);
+        }
+        self.index.insert(&relative, modified_millis(&metadata), metadata.len(), &String::from_utf8_lossy(&bytes));
+        true
+    }
+
+    // Dosya ya da klasörü diskteki hâline göre yeniden dizinler
+    fn index_path(&mut self, path: &Path) -> bool {
+        if path.is_dir() {
+            let mut changed = false;
+            for file in self.files_under(path) {
+                changed |= self.index_file(&file);
+            }
+            changed
+        } else if self.wants(path) {
+            self.index_file(path)
+        } else {
+            false
+        }
+    }
+
+    // Dizini diskle eşitler: yeni/değişmiş dosyalar okunur, kaybolanlar çıkarılır
+    fn refresh(&mut self) -> bool {
+        let mut changed = false;
+        let mut seen = HashSet::new();
+        for file in self.files_under(&self.root.clone()) {
+            let Some(relative) = self.relative(&file) else { continue };
+            let unchanged = match (self.index.by_path.get(&relative), fs::metadata(&file)) {
+                (Some(id), Ok(metadata)) => {
+                    let doc = &self.index.docs[id];
+                    doc.size == metadata.len() && doc.modified.is_some() && doc.modified == modified_millis(&metadata)
+                }
+                _ => false,
+            };
+            if !unchanged {
+                changed |= self.index_file(&file);
+            }
+            seen.insert(relative);
+        }
+        let stale: Vec<u32> = self
+            .index
+            .docs
+            .iter()
+            .filter(|(_, doc)| !seen.contains(&doc.path))
+            .map(|(id, _)| *id)
+            .collect();
+        changed |= !stale.is_empty();
+        for id in stale {
+            self.index.remove_doc(id);
+        }
+        changed
+    }
+
+    fn stats(&self) -> IndexStats {
+        IndexStats {
+            root: self.root.to_string_lossy().to_string(),
+            documents: self.index.docs.len(),
+            terms: self.index.terms.len(),
+        }
+    }
+
+    // Dosya okunduğu için çalışma alanı kilidi dışında çağrılır
+    fn snippet(path: &Path, clauses: &[Clause]) -> Option<(usize, String)> {
+        let content = fs::read_to_string(path).ok()?;
+        let (_, offset) = tokenize(&content)
+            .into_iter()
+            .find(|(term, _)| clauses.iter().any(|c| c.first_term_matches(term)))?;
+        let line_start = content[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = content[offset..].find('\n').map_or(content.len(), |i| offset + i);
+        let line = search::preview(content[line_start..line_end].trim());
+        Some((content[..offset].matches('\n').count() + 1, line))
+    }
+}
+
+fn modified_millis(metadata: &fs::Metadata) -> Option<u64> {
+    metadata.modified().ok().and_then(|t| t.duration_since(UNIX_EPOCH).ok()).map(|d| d.as_millis() as u64)
+}
+
+// Henüz var olmayan ya da silinmiş yollar için üst klasör kanonikleştirilir
+fn normalize(path: &Path) -> PathBuf {
+    if let Ok(path) = dunce::canonicalize(path) {
+        return path;
+    }
+    match (path.parent().and_then(|p| dunce::canonicalize(p).ok()), path.file_name()) {
+        (Some(parent), Some(name)) => parent.join(name),
+        _ => path.to_path_buf(),
+    }
+}
+
+pub struct SearchIndexState {
+    // Uygulama veri klasöründeki search-index; bulunamazsa dizin yalnızca bellekte tutulur
+    dir: Option<PathBuf>,
+    workspaces: Arc<Mutex<HashMap<PathBuf, Workspace>>>,
+    dirty: Arc<Mutex<HashSet<PathBuf>>>,
+    save_generation: Arc<AtomicU64>,
+}
+
+impl SearchIndexState {
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        SearchIndexState {
+            dir,
+            workspaces: Arc::default(),
+            dirty: Arc::default(),
+            save_generation: Arc::default(),
+        }
+    }
+
+    fn index_file_path(dir: &Path, root: &Path) -> PathBuf {
+        let hash = safe_write::hash_bytes(root.to_string_lossy().as_bytes());
+        dir.join(format!("{}.bin", &hash[..16]))
+    }
+
+    fn read_snapshot(&self, root: &Path) -> Option<Index> {
+        let file = Self::index_file_path(self.dir.as_ref()?, root);
+        let bytes = fs::read(&file).ok()?;
+        match bincode::deserialize::<Index>(&bytes) {
+            Ok(index) if index.version == INDEX_VERSION && Path::new(&index.root) == root => Some(index),
+            Ok(_) => None,
+            Err(e) => {
+                log::warn!("Arama dizini okunamadı, yeniden oluşturulacak: {}", e);
+                None
+            }
+        }
+    }
+
+    fn save(dir: &Path, workspace: &Workspace) -> Result<(), String> {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        let bytes = bincode::serialize(&workspace.index).map_err(|e| e.to_string())?;
+        safe_write::write_atomic(&Self::index_file_path(dir, &workspace.root), &bytes, &Default::default())?;
+        Ok(())
+    }
+
+    fn build(&self, root: &Path, fresh: bool) -> Result<Workspace, String> {
+        let index = if fresh { None } else { self.read_snapshot(root) };
+        let mut index = index.unwrap_or_else(|| Index::new(root));
+        index.rebuild_paths();
+        let mut workspace = Workspace { root: root.to_path_buf(), ignore: search::build_ignore_set(&[])?, index };
+        let changed = workspace.refresh();
+        if let (Some(dir), true) = (&self.dir, changed || fresh) {
+            if let Err(e) = Self::save(dir, &workspace) {
+                log::warn!("Arama dizini kaydedilemedi: {}", e);
+            }
+        }
+        Ok(workspace)
+    }
+
+    // Dizin bellekte yoksa diskten yükler ve eşitler. Uzun sürebilecek tarama kilit dışında yapılır ki
+    // bu sırada kaydedilen dosyalar beklemesin.
+    fn with_workspace<T>(&self, root: &str, f: impl FnOnce(&Workspace) -> T) -> Result<T, String> {
+        let root = dunce::canonicalize(root).map_err(|e| format!("Çalışma alanı bulunamadı: {}", e))?;
+        if !root.is_dir() {
+            return Err("Çalışma alanı bir klasör değil".to_string());
+        }
+        if !self.workspaces.lock().unwrap().contains_key(&root) {
+            let workspace = self.build(&root, false)?;
+            self.workspaces.lock().unwrap().entry(root.clone()).or_insert(workspace);
+        }
+        let workspaces = self.workspaces.lock().unwrap();
+        Ok(f(&workspaces[&root]))
+    }
+
+    // Yolu içeren yüklü çalışma alanlarını günceller; değişen dizinler kısa bir gecikmeyle diske yazılır
+    fn update(&self, path: &Path, f: impl Fn(&mut Workspace, &Path) -> bool) {
+        let path = normalize(path);
+        let mut changed = Vec::new();
+        for workspace in self.workspaces.lock().unwrap().values_mut() {
+            if path.starts_with(&workspace.root) && f(workspace, &path) {
+                changed.push(workspace.root.clone());
+            }
+        }
+        if !changed.is_empty() {
+            self.dirty.lock().unwrap().extend(changed);
+            self.schedule_save();
+        }
+    }
+
+    fn schedule_save(&self) {
+        let Some(dir) = self.dir.clone() else { return };
+        let generation = self.save_generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let current = self.save_generation.clone();
+        let workspaces = self.workspaces.clone();
+        let dirty = self.dirty.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(SAVE_DELAY);
+            if current.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            let roots: Vec<PathBuf> = dirty.lock().unwrap().drain().collect();
+            let workspaces = workspaces.lock().unwrap();
+            for root in roots {
+                if let Some(workspace) = workspaces.get(&root) {
+                    if let Err(e) = Self::save(&dir, workspace) {
+                        log::warn!("Arama dizini kaydedilemedi: {}", e);
+                    }
+                }
+            }
+        });
+    }
+
+    // write_file ve save_file_dialog sonrası: içerik zaten elde, dosya yeniden okunmaz
+    pub fn file_written(&self, path: &Path, content: &str, fingerprint: &FileFingerprint) {
+        self.update(path, |workspace, path| {
+            if !workspace.wants(path) {
+                return false;
+            }
+            let Some(relative) = workspace.relative(path) else { return false };
+            workspace.index.insert(&relative, fingerprint.modified, fingerprint.size, content);
+            true
+        });
+    }
+
+    pub fn path_removed(&self, path: &Path) {
+        self.update(path, |workspace, path| {
+            workspace.relative(path).is_some_and(|relative| workspace.index.remove_path(&relative))
+        });
+    }
+
+    // Çöpten geri yükleme gibi diskte yeni beliren dosya/klasörler
+    pub fn path_added(&self, path: &Path) {
+        self.update(path, |workspace, path| workspace.index_path(path));
+    }
+
+    pub fn path_renamed(&self, from: &Path, to: &Path) {
+        self.path_removed(from);
+        self.path_added(to);
+    }
+}
+
+// Dizinde sıralı arama; dizin ilk kullanımda oluşturulur ya da diskten yüklenir
+#[tauri::command]
+pub async fn query_search_index(state: State<'_, SearchIndexState>, workspace: State<'_, WorkspaceState>, root: String, query: String, limit: Option<usize>) -> Result<Vec<IndexHit>, String> {
+    workspace.check(&root)?;
+    let clauses = parse_query(&query);
+    if clauses.is_empty() {
+        return Err("Arama ifadesi boş olamaz".to_string());
+    }
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).max(1);
+    // Kilit yalnızca sıralama için tutulur; önizleme için dosyalar okunurken kayıtlar ve izleyici beklemesin
+    let hits: Vec<(PathBuf, _)> = state.with_workspace(&root, |workspace| {
+        workspace
+            .index
+            .query(&clauses, limit)
+            .into_iter()
+            .map(|(id, score)| (workspace.root.join(&workspace.index.docs[&id].path), score))
+            .collect()
+    })?;
+    Ok(hits
+        .into_iter()
+        .map(|(path, score)| {
+            let (line, snippet) = Workspace::snippet(&path, &clauses).unzip();
+            IndexHit { path: path.to_string_lossy().to_string(), score, line, snippet }
+        })
+        .collect())
+}
+
+// Bozuk ya da eskimiş dizini atıp çalışma alanını baştan dizinler
+#[tauri::command]
+pub async fn rebuild_search_index(state: State<'_, SearchIndexState>, workspace: State<'_, WorkspaceState>, root: String) -> Result<IndexStats, String> {
+    workspace.check(&root)?;
+    let root = dunce::canonicalize(&root).map_err(|e| format!("Çalışma alanı bulunamadı: {}", e))?;
+    if !root.is_dir() {
+        return Err("Çalışma alanı bir klasör değil".to_string());
+    }
+    let workspace = state.build(&root, true)?;
+    let stats = workspace.stats();
+    state.workspaces.lock().unwrap().insert(root, workspace);
+    Ok(stats)
+}
@@ -0,0 +1,310 @@
+// Notlardaki #etiketler ve ön bilgideki (front matter) "tags:" listesi. "#proje/alfa" gibi iç içe
+// etiketler üst etiketin ("proje") sayımına da katılır; etiketler büyük/küçük harf duyarsız gruplanır.
+use comrak::arena_tree::Node;
+use comrak::nodes::{Ast, AstNode, NodeValue};
+use comrak::Arena;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+use tauri::State;
+
+use crate::frontmatter;
+use crate::history::{self, HistoryState};
+use crate::markdown;
+use crate::search_index::SearchIndexState;
+use crate::wikilinks::NoteSet;
+use crate::workspace::WorkspaceState;
+
+// Önündeki karakter satır başı, boşluk ya da virgül olmalı: "sayfa#bolum" ve "(#baslik)" linkleri etiket değil
+static TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?:^|[\s,;])(#([\p{L}\p{N}_\-]+(?:/[\p{L}\p{N}_\-]+)*))").unwrap());
+// Ön bilgideki liste öğeleri; "#" öneki isteğe bağlı
+static TAG_TOKEN: Lazy<Regex> = Lazy::new(|| Regex::new(r"#?[\p{L}\p{N}_\-]+(?:/[\p{L}\p{N}_\-]+)*").unwrap());
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagInfo {
+    // İlk görüldüğü yazılış
+    pub name: String,
+    // Alt etiketlerdekiler dahil kullanım sayısı
+    pub count: usize,
+    pub files: Vec<String>,
+    pub nested: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameTagResult {
+    pub files: Vec<String>,
+    pub replacements: usize,
+}
+
+// "#1984" gibi yalnızca rakamdan oluşanlar etiket sayılmaz (başlık/issue numaraları)
+fn is_valid(tag: &str) -> bool {
+    tag.chars().any(char::is_alphabetic)
+}
+
+fn normalize(tag: &str) -> String {
+    tag.trim().trim_start_matches('#').trim_end_matches('/').to_string()
+}
+
+// Metindeki etiketler: "#" dahil bayt aralığı ve "#" olmadan etiket
+pub fn find_tags(text: &str) -> Vec<(Range<usize>, &str)> {
+    TAG.captures_iter(text)
+        .filter_map(|captures| {
+            let whole = captures.get(1)?;
+            let tag = captures.get(2)?.as_str();
+            is_valid(tag).then(|| (whole.range(), tag))
+        })
+        .collect()
+}
+
+// Metni düz ve etiket parçalarına böler (export çeviricileri için)
+pub fn split_text(text: &str) -> Vec<(&str, bool)> {
+    let mut parts = Vec::new();
+    let mut last = 0;
+    for (range, _) in find_tags(text) {
+        if range.start > last {
+            parts.push((&text[last..range.start], false));
+        }
+        parts.push((&text[range.clone()], true));
+        last = range.end;
+    }
+    if last < text.len() || parts.is_empty() {
+        parts.push((&text[last..], false));
+    }
+    parts
+}
+
+// Notun tüm etiket kullanımları (tekrarlar dahil): önce ön bilgidekiler, sonra metindekiler
+pub fn extract(content: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    if let Some(metadata) = frontmatter::metadata(content) {
+        tags.extend(metadata.tags.iter().map(|t| normalize(t)).filter(|t| is_valid(t)));
+    }
+    for (line, prose) in markdown::classify_lines(frontmatter::body(content)) {
+        if !prose {
+            continue;
+        }
+        let code = markdown::inline_code_ranges(line);
+        for (range, tag) in find_tags(line) {
+            if !code.iter().any(|r| r.start < range.end && range.start < r.end) {
+                tags.push(tag.to_string());
+            }
+        }
+    }
+    tags
+}
+
+// "proje/alfa/x" → ["proje", "proje/alfa", "proje/alfa/x"]
+fn with_ancestors(tag: &str) -> Vec<&str> {
+    tag.match_indices('/').map(|(i, _)| &tag[..i]).chain(std::iter::once(tag)).collect()
+}
+
+// `tag` ya da alt etiketi mi; öyleyse kalan alt yol ("alfa/x") döner
+fn strip_tag<'t>(tag: &'t str, parent: &str) -> Option<&'t str> {
+    let depth = parent.split('/').count();
+    let parts: Vec<&str> = tag.splitn(depth + 1, '/').collect();
+    let matches = parts.len() >= depth && parts.iter().zip(parent.split('/')).all(|(a, b)| a.to_lowercase() == b.to_lowercase());
+    matches.then(|| parts.get(depth).copied().unwrap_or(""))
+}
+
+fn renamed(tag: &str, from: &str, to: &str) -> Option<String> {
+    let rest = strip_tag(tag, from)?;
+    Some(if rest.is_empty() { to.to_string() } else { format!("{}/{}", to, rest) })
+}
+
+fn rename_tokens(text: &str, from: &str, to: &str, count: &mut usize) -> String {
+    TAG_TOKEN
+        .replace_all(text, |captures: &regex::Captures| {
+            let token = &captures[0];
+            let (hash, tag) = match token.strip_prefix('#') {
+                Some(tag) => ("#", tag),
+                None => ("", token),
+            };
+            match renamed(tag, from, to) {
+                Some(new_tag) => {
+                    *count += 1;
+                    format!("{}{}", hash, new_tag)
+                }
+                None => token.to_string(),
+            }
+        })
+        .into_owned()
+}
+
+// Ön bilgide yalnızca tags/tag anahtarının değeri ve altındaki liste satırları değiştirilir;
+// biçim ve yorumlar korunur
+fn rename_in_front_matter(yaml: &str, from: &str, to: &str, count: &mut usize) -> String {
+    let mut out = String::with_capacity(yaml.len());
+    let mut in_tags = false;
+    for line in yaml.split_inclusive('\n') {
+        let is_key = !line.starts_with([' ', '\t', '-', '#']) && line.contains(':');
+        if is_key {
+            let colon = line.find(':').unwrap();
+            in_tags = frontmatter::TAG_KEYS.contains(&line[..colon].trim());
+            if in_tags {
+                out.push_str(&line[..=colon]);
+                out.push_str(&rename_tokens(&line[colon + 1..], from, to, count));
+                continue;
+            }
+        } else if in_tags && line.trim_start().starts_with('-') {
+            out.push_str(&rename_tokens(line, from, to, count));
+            continue;
+        }
+        out.push_str(line);
+    }
+    out
+}
+
+fn rename_in_content(content: &str, from: &str, to: &str) -> (String, usize) {
+    let mut count = 0;
+    let mut out = String::with_capacity(content.len());
+    let mut body = content;
+    if let Some(front) = frontmatter::split(content) {
+        out.push_str(&content[..front.yaml.start]);
+        out.push_str(&rename_in_front_matter(&content[front.yaml.clone()], from, to, &mut count));
+        out.push_str(&content[front.yaml.end..front.body_start]);
+        body = &content[front.body_start..];
+    }
+    for (line, prose) in markdown::classify_lines(body) {
+        if !prose {
+            out.push_str(line);
+            continue;
+        }
+        let code = markdown::inline_code_ranges(line);
+        let mut last = 0;
+        for (range, tag) in find_tags(line) {
+            if code.iter().any(|r| r.start < range.end && range.start < r.end) {
+                continue;
+            }
+            if let Some(new_tag) = renamed(tag, from, to) {
+                out.push_str(&line[last..range.start]);
+                out.push('#');
+                out.push_str(&new_tag);
+                last = range.end;
+                count += 1;
+            }
+        }
+        out.push_str(&line[last..]);
+    }
+    (out, count)
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+// HTML çıktısı için metin düğümlerindeki etiketleri <span class="tag"> yapar (görsel açıklamaları hariç)
+pub fn style_in_document<'a>(arena: &'a Arena<AstNode<'a>>, root: &'a AstNode<'a>) {
+    let texts: Vec<_> = root
+        .descendants()
+        .filter(|node| matches!(&node.data.borrow().value, NodeValue::Text(text) if text.contains('#')))
+        .filter(|node| !node.ancestors().any(|a| matches!(a.data.borrow().value, NodeValue::Image(_))))
+        .collect();
+    for node in texts {
+        let (text, start) = {
+            let ast = node.data.borrow();
+            let NodeValue::Text(text) = &ast.value else { continue };
+            (text.clone(), ast.sourcepos.start)
+        };
+        let parts = split_text(&text);
+        if !parts.iter().any(|(_, is_tag)| *is_tag) {
+            continue;
+        }
+        for (part, is_tag) in parts {
+            let value = if is_tag {
+                NodeValue::HtmlInline(format!("<span class=\"tag\">{}</span>", html_escape(part)))
+            } else {
+                NodeValue::Text(part.to_string())
+            };
+            node.insert_before(arena.alloc(Node::new(RefCell::new(Ast::new(value, start)))));
+        }
+        node.detach();
+    }
+}
+
+// Tüm etiketler, kullanım sayıları ve geçtikleri notlar; üst etiketler alt etiketlerin kullanımlarını da sayar
+#[tauri::command]
+pub async fn list_tags(workspace: State<'_, WorkspaceState>, root: String) -> Result<Vec<TagInfo>, String> {
+    workspace.check(&root)?;
+    let notes = NoteSet::scan(Path::new(&root))?;
+    let mut tags: BTreeMap<String, TagInfo> = BTreeMap::new();
+    let mut files: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for note in notes.paths() {
+        let Ok(content) = fs::read_to_string(note) else { continue };
+        let path = note.to_string_lossy().to_string();
+        for tag in extract(&content) {
+            for name in with_ancestors(&tag) {
+                let key = name.to_lowercase();
+                let info = tags.entry(key.clone()).or_insert_with(|| TagInfo {
+                    name: name.to_string(),
+                    count: 0,
+                    files: Vec::new(),
+                    nested: name.contains('/'),
+                });
+                info.count += 1;
+                files.entry(key).or_default().insert(path.clone());
+            }
+        }
+    }
+    Ok(tags
+        .into_iter()
+        .map(|(key, mut info)| {
+            info.files = files.remove(&key).unwrap_or_default().into_iter().collect();
+            info
+        })
+        .collect())
+}
+
+// Etiketi ya da alt etiketlerinden birini taşıyan notlar
+#[tauri::command]
+pub async fn find_notes_by_tag(workspace: State<'_, WorkspaceState>, root: String, tag: String) -> Result<Vec<String>, String> {
+    workspace.check(&root)?;
+    let tag = normalize(&tag);
+    if tag.is_empty() {
+        return Err("Etiket boş olamaz".to_string());
+    }
+    let notes = NoteSet::scan(Path::new(&root))?;
+    Ok(notes
+        .paths()
+        .iter()
+        .filter(|note| {
+            fs::read_to_string(note).is_ok_and(|content| extract(&content).iter().any(|t| strip_tag(t, &tag).is_some()))
+        })
+        .map(|note| note.to_string_lossy().to_string())
+        .collect())
+}
+
+// Etiketi (alt etiketleriyle birlikte) tüm çalışma alanında yeniden adlandırır: "#proje" → "#is" iken
+// "#proje/alfa" → "#is/alfa". Kod blokları ve satır içi kod değiştirilmez.
+#[tauri::command]
+pub async fn rename_tag(index: State<'_, SearchIndexState>, history: State<'_, HistoryState>, workspace: State<'_, WorkspaceState>, root: String, from: String, to: String) -> Result<RenameTagResult, String> {
+    workspace.check(&root)?;
+    let from = normalize(&from);
+    let to = normalize(&to);
+    if from.is_empty() || !is_valid(&from) {
+        return Err("Geçersiz etiket adı".to_string());
+    }
+    if !is_valid(&to) || TAG_TOKEN.find(&to).map(|m| m.as_str()) != Some(to.as_str()) {
+        return Err("Yeni etiket adı yalnızca harf, rakam, '-', '_' ve '/' içerebilir".to_string());
+    }
+    let notes = NoteSet::scan(Path::new(&root))?;
+    let mut result = RenameTagResult { files: Vec::new(), replacements: 0 };
+    for note in notes.paths() {
+        let Ok(content) = fs::read_to_string(note) else { continue };
+        let (updated, count) = rename_in_content(&content, &from, &to);
+        if count == 0 {
+            continue;
+        }
+        history::write_note(&history, &index, note, updated.as_bytes(), &Default::default())
+            .map_err(|e| format!("{} güncellenemedi: {}", note.display(), e))?;
+        result.files.push(note.to_string_lossy().to_string());
+        result.replacements += count;
+    }
+    Ok(result)
+}
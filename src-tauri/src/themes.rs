@@ -0,0 +1,296 @@
+// Export temaları: her tema PDF ve HTML çıktısı için bir HTML iskeleti (pdf.html, html.html) ve stil
+// dosyası (pdf.css, html.css) sağlar. Yerleşik temalar themes/ klasöründen derlemeye gömülür; kullanıcı
+// temaları ayar klasöründeki themes/<kimlik>/ altından her export'ta yeniden okunur. Temanın vermediği
+// dosyalar theme.json'daki "extends" ile belirtilen yerleşik temadan (varsayılan: light) alınır.
+//
+// İskeletlerde kullanılabilen yer tutucular: {{title}}, {{meta}}, {{css}}, {{body}}, {{author}},
+// {{date}}, {{tags}}, {{filename}}, {{watermark}}, {{brand_footer}} ve {{brand_icon}} (alt bilgi logosu).
+// Tanınmayan yer tutucular olduğu gibi kalır. PDF'te {{css}} export'un sayfa düzeni (@page) kuralıyla biter.
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use tauri::State;
+
+use crate::frontmatter::NoteMetadata;
+
+pub const DEFAULT_THEME: &str = "light";
+const MANIFEST: &str = "theme.json";
+const FILES: &[&str] = &["pdf.html", "pdf.css", "html.html", "html.css"];
+
+struct BuiltinTheme {
+    id: &'static str,
+    manifest: &'static str,
+    files: &'static [(&'static str, &'static str)],
+}
+
+const BUILTIN: &[BuiltinTheme] = &[
+    BuiltinTheme {
+        id: "light",
+        manifest: include_str!("../themes/light/theme.json"),
+        files: &[
+            ("pdf.html", include_str!("../themes/light/pdf.html")),
+            ("pdf.css", include_str!("../themes/light/pdf.css")),
+            ("html.html", include_str!("../themes/light/html.html")),
+            ("html.css", include_str!("../themes/light/html.css")),
+        ],
+    },
+    BuiltinTheme {
+        id: "dark",
+        manifest: include_str!("../themes/dark/theme.json"),
+        files: &[
+            ("pdf.css", include_str!("../themes/dark/pdf.css")),
+            ("html.css", include_str!("../themes/dark/html.css")),
+        ],
+    },
+];
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Manifest {
+    name: Option<String>,
+    // Yerleşik PDF motoru ve DOCX bu bayrağa göre renk seçer; verilmezse temel temadan gelir
+    dark: Option<bool>,
+    extends: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Template {
+    Pdf,
+    Html,
+}
+
+impl Template {
+    fn files(self) -> (&'static str, &'static str) {
+        match self {
+            Template::Pdf => ("pdf.html", "pdf.css"),
+            Template::Html => ("html.html", "html.css"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThemeInfo {
+    pub id: String,
+    pub name: String,
+    pub dark: bool,
+    pub builtin: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub id: String,
+    pub name: String,
+    pub dark: bool,
+    files: HashMap<String, String>,
+}
+
+// İskelete yerleştirilecek sayfa bilgileri
+pub struct Page<'a> {
+    pub filename: &'a str,
+    pub metadata: Option<&'a NoteMetadata>,
+    pub body: &'a str,
+    // PDF filigranı ve alt bilgisinin hazır HTML'i; kapalıysa boş
+    pub watermark: &'a str,
+    pub brand_footer: &'a str,
+    pub brand_icon: &'a str,
+    // Temanın stilinden sonra eklenir; HTML export'unda boş
+    pub page_css: &'a str,
+}
+
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+// <title> ön bilgideki başlıktan, yoksa dosya adından; yazar, tarih ve etiketler <meta> olarak eklenir
+fn html_head(metadata: Option<&NoteMetadata>, filename: &str) -> (String, String) {
+    let Some(metadata) = metadata else { return (escape_html(filename), String::new()) };
+    let title = escape_html(metadata.title.as_deref().unwrap_or(filename));
+    let mut meta = String::new();
+    if let Some(author) = &metadata.author {
+        meta.push_str(&format!("<meta name=\"author\" content=\"{}\" />", escape_html(author)));
+    }
+    if let Some(date) = &metadata.date {
+        meta.push_str(&format!("<meta name=\"date\" content=\"{}\" />", escape_html(date)));
+    }
+    if !metadata.tags.is_empty() {
+        meta.push_str(&format!("<meta name=\"keywords\" content=\"{}\" />", escape_html(&metadata.tags.join(", "))));
+    }
+    (title, meta)
+}
+
+// Tek geçişte yerine koyar; böylece not içeriğindeki "{{...}}" metinleri yer tutucu sayılmaz
+fn fill(template: &str, values: &HashMap<&str, String>) -> String {
+    let mut out = String::with_capacity(template.len() + values.values().map(String::len).sum::<usize>());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find("}}").and_then(|end| values.get(after[..end].trim()).map(|value| (end, value))) {
+            Some((end, value)) => {
+                out.push_str(value);
+                rest = &after[end + 2..];
+            }
+            None => {
+                out.push_str("{{");
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+impl Theme {
+    pub fn render(&self, template: Template, page: &Page) -> String {
+        let (skeleton, css) = template.files();
+        let (title, meta) = html_head(page.metadata, page.filename);
+        let field = |value: Option<&String>| value.map(|v| escape_html(v)).unwrap_or_default();
+        let mut values = HashMap::new();
+        values.insert("title", title);
+        values.insert("meta", meta);
+        values.insert("css", format!("{}{}", self.file(css), page.page_css));
+        values.insert("body", page.body.to_string());
+        values.insert("author", field(page.metadata.and_then(|m| m.author.as_ref())));
+        values.insert("date", field(page.metadata.and_then(|m| m.date.as_ref())));
+        values.insert("tags", page.metadata.map(|m| escape_html(&m.tags.join(", "))).unwrap_or_default());
+        values.insert("filename", escape_html(page.filename));
+        values.insert("watermark", page.watermark.to_string());
+        values.insert("brand_footer", page.brand_footer.to_string());
+        values.insert("brand_icon", page.brand_icon.to_string());
+        fill(self.file(skeleton), &values)
+    }
+
+    fn file(&self, name: &str) -> &str {
+        self.files.get(name).map(String::as_str).unwrap_or_default()
+    }
+}
+
+fn builtin(id: &str) -> Option<&'static BuiltinTheme> {
+    BUILTIN.iter().find(|theme| theme.id == id)
+}
+
+fn parse_manifest(text: &str, id: &str) -> Result<Manifest, String> {
+    serde_json::from_str(text).map_err(|e| format!("Tema ayarları okunamadı ({}): {}", id, e))
+}
+
+// Yerleşik tema, temel aldığı temanın dosyalarıyla tamamlanır
+fn load_builtin(id: &str) -> Result<Theme, String> {
+    let source = builtin(id).ok_or_else(|| format!("Export teması bulunamadı: {}", id))?;
+    let manifest = parse_manifest(source.manifest, id)?;
+    let mut theme = match manifest.extends.as_deref() {
+        Some(base) if base != id => load_builtin(base)?,
+        _ => Theme { id: String::new(), name: String::new(), dark: false, files: HashMap::new() },
+    };
+    for (name, content) in source.files {
+        theme.files.insert(name.to_string(), content.to_string());
+    }
+    theme.id = id.to_string();
+    theme.name = manifest.name.unwrap_or_else(|| id.to_string());
+    theme.dark = manifest.dark.unwrap_or(theme.dark);
+    Ok(theme)
+}
+
+// Klasör adı tema kimliğidir; yol ayırıcıları ve ".." kabul edilmez
+fn valid_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+}
+
+pub struct ThemeState {
+    // Ayar klasörü bulunamazsa yalnızca yerleşik temalar kullanılır
+    user_dir: Option<PathBuf>,
+}
+
+impl ThemeState {
+    pub fn new(user_dir: Option<PathBuf>) -> Self {
+        ThemeState { user_dir }
+    }
+
+    fn user_theme_dir(&self, id: &str) -> Option<PathBuf> {
+        let dir = self.user_dir.as_ref()?.join(id);
+        dir.is_dir().then_some(dir)
+    }
+
+    // Aynı kimlikli kullanıcı teması yerleşik temanın yerine geçer
+    pub fn load(&self, id: Option<&str>) -> Result<Theme, String> {
+        let id = id.filter(|id| !id.is_empty()).unwrap_or(DEFAULT_THEME);
+        if !valid_id(id) {
+            return Err(format!("Geçersiz tema adı: {}", id));
+        }
+        let Some(dir) = self.user_theme_dir(id) else { return load_builtin(id) };
+
+        let manifest = match fs::read_to_string(dir.join(MANIFEST)) {
+            Ok(text) => parse_manifest(&text, id)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Manifest::default(),
+            Err(e) => return Err(format!("Tema ayarları okunamadı ({}): {}", id, e)),
+        };
+        let base = match manifest.extends.as_deref() {
+            Some(base) => base,
+            None if builtin(id).is_some() => id,
+            None => DEFAULT_THEME,
+        };
+        let mut theme = load_builtin(base)?;
+        for name in FILES {
+            match fs::read_to_string(dir.join(name)) {
+                Ok(content) => {
+                    theme.files.insert(name.to_string(), content);
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(format!("Tema dosyası okunamadı ({}/{}): {}", id, name, e)),
+            }
+        }
+        // Yerleşik temayı değiştiren kullanıcı teması ad vermezse yerleşik adı korunur
+        if base != id {
+            theme.name = id.to_string();
+        }
+        theme.id = id.to_string();
+        theme.name = manifest.name.unwrap_or(theme.name);
+        theme.dark = manifest.dark.unwrap_or(theme.dark);
+        Ok(theme)
+    }
+
+    // Yerleşik temalar önce, kullanıcı temaları ada göre sıralı; bozuk temalar listede yer almaz
+    pub fn list(&self) -> Vec<ThemeInfo> {
+        let mut user_ids: Vec<String> = self
+            .user_dir
+            .as_ref()
+            .and_then(|dir| fs::read_dir(dir).ok())
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|id| valid_id(id))
+            .collect();
+        user_ids.sort();
+
+        let mut themes = Vec::new();
+        let ids = BUILTIN.iter().map(|theme| theme.id.to_string()).chain(user_ids.iter().filter(|id| builtin(id).is_none()).cloned());
+        for id in ids {
+            match self.load(Some(&id)) {
+                Ok(theme) => themes.push(ThemeInfo {
+                    builtin: self.user_theme_dir(&id).is_none(),
+                    id: theme.id,
+                    name: theme.name,
+                    dark: theme.dark,
+                }),
+                Err(e) => log::warn!("{}", e),
+            }
+        }
+        themes
+    }
+}
+
+#[tauri::command]
+pub async fn list_export_themes(themes: State<'_, ThemeState>) -> Result<Vec<ThemeInfo>, String> {
+    Ok(themes.list())
+}
+
+// Kullanıcı temalarının konacağı klasör; yoksa oluşturulur
+#[tauri::command]
+pub async fn get_themes_dir(themes: State<'_, ThemeState>) -> Result<String, String> {
+    let dir = themes.user_dir.as_ref().ok_or("Ayar klasörü bulunamadı")?;
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    Ok(dir.to_string_lossy().to_string())
+}
@@ -0,0 +1,165 @@
+// Başlık kimlikleri ve içindekiler tablosu. Kimlikler HTML, PDF ve DOCX çıktılarında ve wiki
+// bağlantılarındaki "#başlık" adreslerinde aynıdır. "[TOC]" satırı (istenirse belgenin başı) başlıklara
+// bağlantı veren iç içe bir listeye dönüştürülür; liste AST'ye eklendiği için her motor kendi liste ve
+// bağlantı düzeniyle çizer.
+use comrak::arena_tree::Node;
+use comrak::nodes::{Ast, AstNode, LineColumn, ListType, NodeHtmlBlock, NodeLink, NodeList, NodeValue};
+use comrak::Arena;
+use serde::Deserialize;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::markdown;
+
+const MARKER: &str = "[TOC]";
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TocOptions {
+    // "[TOC]" yoksa belgenin başına eklenir
+    pub auto: bool,
+    // Listeye girecek en derin başlık düzeyi (1-6)
+    pub depth: u8,
+}
+
+impl Default for TocOptions {
+    fn default() -> Self {
+        TocOptions { auto: false, depth: 3 }
+    }
+}
+
+// Aynı başlık tekrar ederse GitHub'daki gibi "-1", "-2" eklenir
+#[derive(Default)]
+pub struct Slugger {
+    used: HashMap<String, usize>,
+}
+
+impl Slugger {
+    pub fn slug(&mut self, text: &str) -> String {
+        let mut base = markdown::heading_slug(text);
+        if base.is_empty() {
+            base = "baslik".to_string();
+        }
+        let mut slug = base.clone();
+        while let Some(count) = self.used.get_mut(&slug) {
+            *count += 1;
+            slug = format!("{}-{}", base, count);
+        }
+        self.used.insert(slug.clone(), 0);
+        slug
+    }
+}
+
+pub struct Heading {
+    pub level: u8,
+    pub text: String,
+    pub id: String,
+    // Dipnot içindeki başlıklar kimlik alır ama içindekilere ve PDF yer imlerine girmez
+    pub listed: bool,
+}
+
+// Biçimlendirme atılmış başlık metni (comrak'ın HTML'deki başlık metniyle aynı kurallar)
+pub fn heading_text<'a>(node: &'a AstNode<'a>) -> String {
+    let mut text = String::new();
+    for descendant in node.descendants() {
+        match &descendant.data.borrow().value {
+            NodeValue::Text(literal) => text.push_str(literal),
+            NodeValue::Code(code) => text.push_str(&code.literal),
+            NodeValue::Math(math) => text.push_str(&math.literal),
+            NodeValue::LineBreak | NodeValue::SoftBreak => text.push(' '),
+            _ => {}
+        }
+    }
+    text
+}
+
+// Belge sırasıyla tüm başlıklar; motorlar başlıkları bu sırayla çizer ve kimlikleri buradan alır
+pub fn headings<'a>(root: &'a AstNode<'a>) -> Vec<Heading> {
+    let mut slugger = Slugger::default();
+    root.descendants()
+        .filter_map(|node| {
+            let NodeValue::Heading(heading) = node.data.borrow().value else { return None };
+            let text = heading_text(node);
+            let listed = !node.ancestors().any(|a| matches!(a.data.borrow().value, NodeValue::FootnoteDefinition(_)));
+            Some(Heading { level: heading.level, id: slugger.slug(&text), text, listed })
+        })
+        .collect()
+}
+
+fn is_marker<'a>(node: &'a AstNode<'a>) -> bool {
+    matches!(node.data.borrow().value, NodeValue::Paragraph) && heading_text(node).trim().eq_ignore_ascii_case(MARKER)
+}
+
+fn new_node<'a>(arena: &'a Arena<AstNode<'a>>, value: NodeValue) -> &'a AstNode<'a> {
+    arena.alloc(Node::new(RefCell::new(Ast::new(value, LineColumn { line: 0, column: 0 }))))
+}
+
+// Atlanan düzeyler (h1'den sonra h3) bir alt liste açar; listenin en üstü ilk başlığın düzeyidir
+fn build_list<'a>(arena: &'a Arena<AstNode<'a>>, headings: &[&Heading]) -> &'a AstNode<'a> {
+    let list_value = NodeList { list_type: ListType::Bullet, bullet_char: b'-', tight: true, ..NodeList::default() };
+    let root = new_node(arena, NodeValue::List(list_value));
+    let mut stack: Vec<(u8, &AstNode)> = Vec::new();
+    for heading in headings {
+        while stack.last().is_some_and(|(level, _)| *level > heading.level) {
+            stack.pop();
+        }
+        let list = match stack.last() {
+            None => root,
+            Some((level, list)) if *level == heading.level => *list,
+            Some((_, list)) => {
+                let parent = list.last_child().unwrap_or(*list);
+                let sublist = new_node(arena, NodeValue::List(list_value));
+                parent.append(sublist);
+                sublist
+            }
+        };
+        if !stack.last().is_some_and(|(level, _)| *level == heading.level) {
+            stack.push((heading.level, list));
+        }
+
+        let item = new_node(arena, NodeValue::Item(list_value));
+        let paragraph = new_node(arena, NodeValue::Paragraph);
+        let link = new_node(arena, NodeValue::Link(NodeLink { url: format!("#{}", heading.id), title: String::new() }));
+        link.append(new_node(arena, NodeValue::Text(heading.text.clone())));
+        paragraph.append(link);
+        item.append(paragraph);
+        list.append(item);
+    }
+    root
+}
+
+// HTML'de liste <nav class="toc"> içinde durur; diğer motorlar ham HTML bloklarını yok sayar
+fn insert_before<'a>(arena: &'a Arena<AstNode<'a>>, anchor: &'a AstNode<'a>, list: &'a AstNode<'a>) {
+    let html = |literal: &str| NodeValue::HtmlBlock(NodeHtmlBlock { block_type: 6, literal: literal.to_string() });
+    anchor.insert_before(new_node(arena, html("<nav class=\"toc\">\n")));
+    anchor.insert_before(list);
+    anchor.insert_before(new_node(arena, html("</nav>\n")));
+}
+
+// "[TOC]" paragraflarını içindekiler listesiyle değiştirir; hiç yoksa ve `auto` açıksa listeyi belgenin
+// başına ekler. Başlıklar (kimlikleriyle) döner.
+pub fn expand_in_document<'a>(arena: &'a Arena<AstNode<'a>>, root: &'a AstNode<'a>, options: &TocOptions) -> Vec<Heading> {
+    let headings = headings(root);
+    let depth = options.depth.clamp(1, 6);
+    let listed: Vec<&Heading> = headings.iter().filter(|h| h.listed && h.level <= depth).collect();
+    let markers: Vec<_> = root.descendants().filter(|node| is_marker(node)).collect();
+
+    if listed.is_empty() {
+        for marker in markers {
+            marker.detach();
+        }
+        return headings;
+    }
+    if markers.is_empty() && options.auto {
+        let list = build_list(arena, &listed);
+        match root.first_child() {
+            Some(first) => insert_before(arena, first, list),
+            None => root.append(list),
+        }
+    }
+    for marker in markers {
+        insert_before(arena, marker, build_list(arena, &listed));
+        marker.detach();
+    }
+    headings
+}
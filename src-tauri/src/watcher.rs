@@ -0,0 +1,158 @@
+// Çalışma alanındaki dış değişiklikleri (git pull, senkronizasyon, başka editörler) izler ve
+// debounce edilmiş olayları ön yüze "fs-change" Tauri olayı olarak iletir.
+use notify_debouncer_full::notify::event::{ModifyKind, RenameMode};
+use notify_debouncer_full::notify::{EventKind, RecommendedWatcher, RecursiveMode};
+use notify_debouncer_full::{new_debouncer, DebounceEventResult, Debouncer, RecommendedCache};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::workspace::WorkspaceState;
+
+pub const FS_CHANGE_EVENT: &str = "fs-change";
+
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+// Bu klasörlerin içindeki değişiklikler kullanıcıya ait değil
+const IGNORED_DIRS: &[&str] = &[".git", crate::recycle::WORKSPACE_TRASH_DIR];
+// safe_write'ın geçici dosyaları
+const IGNORED_SUFFIXES: &[&str] = &[".pignote-tmp"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Created,
+    Modified,
+    Removed,
+    Renamed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FsChange {
+    pub kind: ChangeKind,
+    pub path: String,
+    // Yalnızca Renamed için: eski yol
+    pub old_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FsChangeBatch {
+    pub root: String,
+    pub changes: Vec<FsChange>,
+}
+
+#[derive(Default)]
+pub struct WatcherState {
+    watchers: Mutex<HashMap<PathBuf, Debouncer<RecommendedWatcher, RecommendedCache>>>,
+}
+
+fn is_ignored(path: &Path) -> bool {
+    let in_ignored_dir = path
+        .components()
+        .any(|c| IGNORED_DIRS.iter().any(|d| c.as_os_str() == *d));
+    let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    in_ignored_dir || IGNORED_SUFFIXES.iter().any(|s| name.ends_with(s))
+}
+
+fn change(kind: ChangeKind, path: &Path) -> FsChange {
+    FsChange { kind, path: path.to_string_lossy().to_string(), old_path: None }
+}
+
+// notify olay türlerini ön yüzün anladığı dört türe indirger
+fn to_changes(kind: &EventKind, all_paths: &[PathBuf]) -> Vec<FsChange> {
+    // Atomik kayıt geçici dosyanın asıl dosyanın üzerine taşınmasıdır: içerik değişikliği sayılır
+    if let (EventKind::Modify(ModifyKind::Name(RenameMode::Both)), [from, to]) = (kind, all_paths) {
+        return match (is_ignored(from), is_ignored(to)) {
+            (false, false) => {
+                let mut renamed = change(ChangeKind::Renamed, to);
+                renamed.old_path = Some(from.to_string_lossy().to_string());
+                vec![renamed]
+            }
+            (true, false) => vec![change(ChangeKind::Modified, to)],
+            (false, true) => vec![change(ChangeKind::Removed, from)],
+            (true, true) => Vec::new(),
+        };
+    }
+
+    let paths: Vec<&PathBuf> = all_paths.iter().filter(|p| !is_ignored(p)).collect();
+    match kind {
+        EventKind::Create(_) => paths.iter().map(|p| change(ChangeKind::Created, p)).collect(),
+        EventKind::Remove(_) => paths.iter().map(|p| change(ChangeKind::Removed, p)).collect(),
+        // Eşi bulunamayan taşımalar: izlenen alanın dışına çıkan silinmiş, içeri giren oluşturulmuş sayılır
+        EventKind::Modify(ModifyKind::Name(RenameMode::From)) => paths.iter().map(|p| change(ChangeKind::Removed, p)).collect(),
+        EventKind::Modify(ModifyKind::Name(RenameMode::To)) => paths.iter().map(|p| change(ChangeKind::Created, p)).collect(),
+        EventKind::Modify(ModifyKind::Name(_)) => paths
+            .iter()
+            .map(|p| change(if p.exists() { ChangeKind::Created } else { ChangeKind::Removed }, p))
+            .collect(),
+        // Yalnızca izin/zaman damgası değişiklikleri içerik değiştirmez
+        EventKind::Modify(ModifyKind::Metadata(_)) => Vec::new(),
+        EventKind::Modify(_) => paths.iter().map(|p| change(ChangeKind::Modified, p)).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn collect_changes(events: &[notify_debouncer_full::DebouncedEvent]) -> Vec<FsChange> {
+    let mut changes: Vec<FsChange> = Vec::new();
+    for event in events {
+        for change in to_changes(&event.kind, &event.paths) {
+            if !changes.contains(&change) {
+                changes.push(change);
+            }
+        }
+    }
+    changes
+}
+
+// Klasörü alt klasörleriyle birlikte izlemeye başlar; aynı klasör ikinci kez eklenirse bir şey yapmaz
+#[tauri::command]
+pub async fn watch_workspace(app: AppHandle, state: State<'_, WatcherState>, workspace: State<'_, WorkspaceState>, root: String) -> Result<(), String> {
+    workspace.check(&root)?;
+    let root_path = std::fs::canonicalize(&root).map_err(|e| format!("İzlenecek klasör bulunamadı: {}", e))?;
+    if !root_path.is_dir() {
+        return Err("İzlenecek yol bir klasör değil".to_string());
+    }
+    let mut watchers = state.watchers.lock().unwrap();
+    if watchers.contains_key(&root_path) {
+        return Ok(());
+    }
+
+    // Olay yolları izlenen yolun biçimini izler; Windows'ta canonicalize "\\?\" öneki eklediği için
+    // anahtar olarak kanonik yol, izleme için kullanıcının verdiği yol kullanılır
+    let root_label = root.clone();
+    let mut debouncer = new_debouncer(DEBOUNCE, None, move |result: DebounceEventResult| match result {
+        Ok(events) => {
+            let changes = collect_changes(&events);
+            if changes.is_empty() {
+                return;
+            }
+            let batch = FsChangeBatch { root: root_label.clone(), changes };
+            if let Err(e) = app.emit(FS_CHANGE_EVENT, batch) {
+                log::warn!("Dosya değişikliği olayı gönderilemedi: {}", e);
+            }
+        }
+        Err(errors) => {
+            for e in errors {
+                log::warn!("Dosya izleme hatası: {}", e);
+            }
+        }
+    })
+    .map_err(|e| e.to_string())?;
+    debouncer.watch(Path::new(&root), RecursiveMode::Recursive).map_err(|e| e.to_string())?;
+    watchers.insert(root_path, debouncer);
+    Ok(())
+}
+
+// İzlemeyi durdurur; izlenmeyen bir klasör için hata vermez
+#[tauri::command]
+pub async fn unwatch_workspace(state: State<'_, WatcherState>, root: String) -> Result<(), String> {
+    let root_path = std::fs::canonicalize(&root).unwrap_or_else(|_| PathBuf::from(&root));
+    if let Some(debouncer) = state.watchers.lock().unwrap().remove(&root_path) {
+        debouncer.stop_nonblocking();
+    }
+    Ok(())
+}
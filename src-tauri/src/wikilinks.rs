@@ -0,0 +1,313 @@
+// Notlar arası [[Not]], [[Not#Başlık]] ve [[Not|takma ad]] bağlantıları. Hedef, çalışma alanındaki
+// not dosyalarının adına (büyük/küçük harf duyarsız) ya da köke göre yoluna ("klasör/Not") göre çözülür.
+// Export'ta çözülen bağlantılar gerçek linklere, çözülemeyenler düz metne dönüşür.
+use comrak::nodes::{AstNode, NodeLink, NodeValue};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use tauri::State;
+
+use crate::markdown;
+use crate::search;
+use crate::workspace::WorkspaceState;
+
+static WIKI_LINK: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[\[([^\[\]\n]+)\]\]").unwrap());
+
+// Köşeli parantez içi: "Not#Başlık|takma ad"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WikiTarget {
+    // Boşsa aynı notun içindeki başlığa bağlantıdır ([[#Başlık]])
+    pub note: String,
+    pub heading: Option<String>,
+    pub alias: Option<String>,
+}
+
+impl WikiTarget {
+    pub fn parse(raw: &str) -> Self {
+        let (target, alias) = match raw.split_once('|') {
+            Some((target, alias)) => (target, Some(alias.trim().to_string()).filter(|a| !a.is_empty())),
+            None => (raw, None),
+        };
+        let (note, heading) = match target.split_once('#') {
+            Some((note, heading)) => (note, Some(heading.trim().to_string()).filter(|h| !h.is_empty())),
+            None => (target, None),
+        };
+        WikiTarget { note: note.trim().to_string(), heading, alias }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteLink {
+    pub source: String,
+    // 1 tabanlı satır
+    pub line: usize,
+    pub target: String,
+    pub heading: Option<String>,
+    pub alias: Option<String>,
+    // Çözülemediyse None
+    pub resolved: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LinkGraph {
+    pub notes: Vec<String>,
+    pub links: Vec<NoteLink>,
+}
+
+// "a/./b/../c" → "a/c"; dosya sistemine dokunmadan
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+fn without_extension(path: &Path) -> PathBuf {
+    if search::is_note_file(path) {
+        path.with_extension("")
+    } else {
+        path.to_path_buf()
+    }
+}
+
+fn comparable(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy().to_lowercase())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+// Çalışma alanındaki not dosyaları
+pub struct NoteSet {
+    root: PathBuf,
+    notes: Vec<PathBuf>,
+}
+
+impl NoteSet {
+    pub fn scan(root: &Path) -> Result<Self, String> {
+        if !root.is_dir() {
+            return Err("Çalışma alanı bulunamadı".to_string());
+        }
+        let ignore = search::build_ignore_set(&[])?;
+        let mut notes = search::note_files(root, root, &ignore);
+        notes.sort();
+        Ok(NoteSet { root: root.to_path_buf(), notes })
+    }
+
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.notes
+    }
+
+    // Aynı adda birden fazla not varsa önce bağlantıyı içeren notun klasöründeki, sonra köke en yakın olan seçilir
+    pub fn resolve(&self, note: &str, from_dir: &Path) -> Option<&PathBuf> {
+        let name = note.trim().replace('\\', "/");
+        if name.is_empty() {
+            return None;
+        }
+        let wanted = comparable(&without_extension(Path::new(&name)));
+        let candidates = self.notes.iter().filter(|path| {
+            if name.contains('/') {
+                let from_note = normalize_lexically(&from_dir.join(without_extension(Path::new(&name))));
+                let relative = path.strip_prefix(&self.root).unwrap_or(path);
+                comparable(&without_extension(path)) == comparable(&from_note) || comparable(&without_extension(relative)) == wanted
+            } else {
+                path.file_stem().is_some_and(|stem| stem.to_string_lossy().to_lowercase() == wanted)
+            }
+        });
+        candidates.min_by_key(|path| (path.parent() != Some(from_dir), path.components().count()))
+    }
+
+    fn links_in(&self, source: &Path) -> Vec<NoteLink> {
+        let Ok(content) = fs::read_to_string(source) else { return Vec::new() };
+        let from_dir = source.parent().unwrap_or(&self.root);
+        scan_links(&content)
+            .into_iter()
+            .map(|(line, target)| {
+                let resolved = if target.note.is_empty() {
+                    Some(source.to_path_buf())
+                } else {
+                    self.resolve(&target.note, from_dir).cloned()
+                };
+                NoteLink {
+                    source: source.to_string_lossy().to_string(),
+                    line,
+                    target: target.note,
+                    heading: target.heading,
+                    alias: target.alias,
+                    resolved: resolved.map(|p| p.to_string_lossy().to_string()),
+                }
+            })
+            .collect()
+    }
+
+    fn all_links(&self) -> Vec<NoteLink> {
+        self.notes.iter().flat_map(|note| self.links_in(note)).collect()
+    }
+}
+
+// Kod blokları ve satır içi kod dışındaki [[...]] bağlantıları, satır numaralarıyla
+fn scan_links(content: &str) -> Vec<(usize, WikiTarget)> {
+    let mut links = Vec::new();
+    for (index, (line, prose)) in markdown::classify_lines(content).into_iter().enumerate() {
+        if !prose {
+            continue;
+        }
+        let code = markdown::inline_code_ranges(line);
+        for captures in WIKI_LINK.captures_iter(line) {
+            let whole = captures.get(0).unwrap();
+            if code.iter().any(|r| r.start < whole.end() && whole.start() < r.end) {
+                continue;
+            }
+            links.push((index + 1, WikiTarget::parse(&captures[1])));
+        }
+    }
+    links
+}
+
+// Linklerin yazılacağı biçim
+pub enum LinkBase {
+    // Çıktı dosyasının klasörüne göre göreli (HTML, DOCX, yerleşik PDF)
+    Relative(PathBuf),
+    // file:// ile mutlak (tarayıcı geçici klasörde açtığı için Chrome PDF'i)
+    Absolute,
+}
+
+pub struct LinkContext {
+    notes: NoteSet,
+    source_dir: PathBuf,
+    base: LinkBase,
+}
+
+impl LinkContext {
+    // Çalışma alanı kökü yoksa notun klasörü kök sayılır; ikisi de yoksa bağlantılar çözülemez
+    pub fn new(root: Option<&str>, base_dir: Option<&str>, base: LinkBase) -> Option<Self> {
+        let source_dir = PathBuf::from(base_dir.or(root)?);
+        let notes = NoteSet::scan(Path::new(root.or(base_dir)?)).ok()?;
+        Some(LinkContext { notes, source_dir, base })
+    }
+
+    fn href(&self, target: &WikiTarget) -> Option<String> {
+        let path = self.notes.resolve(&target.note, &self.source_dir)?;
+        let url = match &self.base {
+            LinkBase::Relative(output_dir) => relative_url(output_dir, path).unwrap_or_else(|| file_url(path)),
+            LinkBase::Absolute => file_url(path),
+        };
+        Some(format!("{}{}", url, anchor(target)))
+    }
+}
+
+fn anchor(target: &WikiTarget) -> String {
+    target.heading.as_deref().map(|h| format!("#{}", markdown::heading_slug(h))).unwrap_or_default()
+}
+
+fn encode_path(path: &str) -> String {
+    path.chars()
+        .map(|c| match c {
+            ' ' => "%20".to_string(),
+            '#' => "%23".to_string(),
+            '?' => "%3F".to_string(),
+            '%' => "%25".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+fn file_url(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    // Windows'un kanonik "\\?\C:\..." biçimi
+    let path = path.strip_prefix("//?/").unwrap_or(&path);
+    let path = if path.starts_with('/') { path.to_string() } else { format!("/{}", path) };
+    format!("file://{}", encode_path(&path))
+}
+
+// Farklı sürücülerdeki yollar için None
+fn relative_url(from_dir: &Path, to: &Path) -> Option<String> {
+    let from_dir = normalize_lexically(&crate::recycle::absolute(from_dir).ok()?);
+    let to = normalize_lexically(&crate::recycle::absolute(to).ok()?);
+    let from: Vec<_> = from_dir.components().collect();
+    let target: Vec<_> = to.components().collect();
+    if from.first() != target.first() {
+        return None;
+    }
+    let common = from.iter().zip(&target).take_while(|(a, b)| a == b).count();
+    let mut parts: Vec<String> = vec!["..".to_string(); from.len() - common];
+    parts.extend(target[common..].iter().map(|c| c.as_os_str().to_string_lossy().to_string()));
+    Some(encode_path(&parts.join("/")))
+}
+
+// Ayrıştırılmış belgedeki wiki bağlantılarını normal linklere çevirir; çözülemeyenlerin yalnızca metni kalır
+pub fn resolve_in_document<'a>(root: &'a AstNode<'a>, context: Option<&LinkContext>) {
+    let wiki_links: Vec<_> = root
+        .descendants()
+        .filter(|node| matches!(node.data.borrow().value, NodeValue::WikiLink(_)))
+        .collect();
+    for node in wiki_links {
+        let url = match &node.data.borrow().value {
+            NodeValue::WikiLink(link) => link.url.clone(),
+            _ => continue,
+        };
+        // comrak takma adı ayırıp etiket yapıyor; url yalnızca "Not#Başlık"
+        let target = WikiTarget::parse(&url);
+        let href = if target.note.is_empty() {
+            Some(anchor(&target)).filter(|a| !a.is_empty())
+        } else {
+            context.and_then(|c| c.href(&target))
+        };
+        match href {
+            Some(url) => node.data.borrow_mut().value = NodeValue::Link(NodeLink { url, title: String::new() }),
+            None => {
+                for child in node.children().collect::<Vec<_>>() {
+                    node.insert_before(child);
+                }
+                node.detach();
+            }
+        }
+    }
+}
+
+// Bu nota bağlantı veren notlar
+#[tauri::command]
+pub async fn get_backlinks(workspace: State<'_, WorkspaceState>, root: String, path: String) -> Result<Vec<NoteLink>, String> {
+    workspace.check(&root)?;
+    let notes = NoteSet::scan(Path::new(&root))?;
+    let target = Path::new(&path);
+    Ok(notes
+        .all_links()
+        .into_iter()
+        .filter(|link| !link.target.is_empty() && link.resolved.as_deref().map(Path::new) == Some(target))
+        .collect())
+}
+
+// Hiçbir nota çözülemeyen bağlantılar
+#[tauri::command]
+pub async fn list_unresolved_links(workspace: State<'_, WorkspaceState>, root: String) -> Result<Vec<NoteLink>, String> {
+    workspace.check(&root)?;
+    let notes = NoteSet::scan(Path::new(&root))?;
+    Ok(notes.all_links().into_iter().filter(|link| link.resolved.is_none()).collect())
+}
+
+// Tüm notlar ve aralarındaki bağlantılar (grafik görünümü için); aynı kaynak-hedef çifti bir kez yer alır
+#[tauri::command]
+pub async fn get_link_graph(workspace: State<'_, WorkspaceState>, root: String) -> Result<LinkGraph, String> {
+    workspace.check(&root)?;
+    let notes = NoteSet::scan(Path::new(&root))?;
+    let mut seen = HashSet::new();
+    let links = notes
+        .all_links()
+        .into_iter()
+        .filter(|link| !link.target.is_empty())
+        .filter(|link| seen.insert((link.source.clone(), link.resolved.clone().unwrap_or_else(|| link.target.to_lowercase()))))
+        .collect();
+    Ok(LinkGraph { notes: notes.notes.iter().map(|p| p.to_string_lossy().to_string()).collect(), links })
+}
@@ -0,0 +1,370 @@
+// Çalışma alanı sınırı: dosya komutları yalnızca kullanıcının açtığı kök klasörün içinde çalışır.
+// Kök yalnızca yerel klasör seçme penceresiyle değişir; önizlemeye sızan bir betik ~/.ssh'i okuyamaz,
+// ev klasörünü silemez. Yollar kanonikleştirilerek sembolik bağlar ve ".." ile kaçış da engellenir.
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+use tauri::State;
+
+// Ön yüz izin hatasını `kind` alanından ayırt edebilir
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AccessError {
+    NoWorkspace { message: String },
+    PermissionDenied { path: String, message: String },
+    InvalidPath { path: String, message: String },
+}
+
+impl fmt::Display for AccessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccessError::NoWorkspace { message }
+            | AccessError::PermissionDenied { message, .. }
+            | AccessError::InvalidPath { message, .. } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl From<AccessError> for String {
+    fn from(e: AccessError) -> String {
+        e.to_string()
+    }
+}
+
+fn invalid(path: &Path, message: &str) -> AccessError {
+    AccessError::InvalidPath { path: path.to_string_lossy().to_string(), message: message.to_string() }
+}
+
+// Var olan en derin üst klasör kanonikleştirilir, henüz olmayan kısım (yeni dosya/klasör) olduğu gibi eklenir.
+// Olmayan kısımda ".." ya da hedefi çözülemeyen sembolik bağ varsa reddedilir.
+pub fn resolve(path: &Path) -> Result<PathBuf, AccessError> {
+    if !path.is_absolute() {
+        return Err(invalid(path, "Göreli yol kullanılamaz"));
+    }
+    let mut existing = path;
+    let mut missing = Vec::new();
+    loop {
+        match dunce::canonicalize(existing) {
+            Ok(mut resolved) => {
+                for name in missing.iter().rev() {
+                    resolved.push(name);
+                }
+                return Ok(resolved);
+            }
+            Err(_) if fs::symlink_metadata(existing).is_ok() => {
+                return Err(invalid(path, "Yol, hedefi bulunamayan bir sembolik bağ içeriyor"));
+            }
+            Err(_) => {
+                let name = existing.file_name().ok_or_else(|| invalid(path, "Yol '..' içeremez"))?;
+                missing.push(name.to_os_string());
+                existing = existing.parent().ok_or_else(|| invalid(path, "Geçersiz dosya yolu"))?;
+            }
+        }
+    }
+}
+
+// Yeniden adlandırmada yeni ad tek bir yol parçası olmalı
+pub fn check_name(name: &str) -> Result<(), AccessError> {
+    // "C:ad" gibi sürücü önekli adlar da ayraç içermez; tek bir normal parça olup olmadığına bakılır
+    let mut components = Path::new(name).components();
+    let single = matches!((components.next(), components.next()), (Some(Component::Normal(_)), None));
+    if !single || name.contains(['/', '\\']) {
+        return Err(invalid(Path::new(name), "Geçersiz dosya adı"));
+    }
+    Ok(())
+}
+
+pub struct WorkspaceState {
+    root: Mutex<Option<PathBuf>>,
+    // Aç / Farklı Kaydet pencerelerinde kullanıcının kendisinin seçtiği dosyalar kök dışında olsa da kullanılabilir
+    granted: Mutex<HashSet<PathBuf>>,
+    // Açılan kök uygulama yeniden başladığında da geçerli olsun diye bu dosyada tutulur
+    store_path: Option<PathBuf>,
+}
+
+impl WorkspaceState {
+    // Kayıtlı kök yoksa kullanıcı klasör seçene kadar çalışma alanı yoktur; komutlar NoWorkspace döner
+    pub fn new(store_path: Option<PathBuf>) -> Self {
+        let root = store_path
+            .as_ref()
+            .and_then(|p| fs::read_to_string(p).ok())
+            .map(|s| PathBuf::from(s.trim()))
+            .filter(|p| p.is_dir())
+            .and_then(|p| dunce::canonicalize(p).ok());
+        WorkspaceState { root: Mutex::new(root), granted: Mutex::new(HashSet::new()), store_path }
+    }
+
+    pub fn root(&self) -> Option<PathBuf> {
+        self.root.lock().unwrap().clone()
+    }
+
+    // Yalnızca kullanıcı etkileşimiyle (klasör seçme penceresi) çağrılmalı
+    pub fn open(&self, path: &Path) -> Result<PathBuf, AccessError> {
+        let root = resolve(path)?;
+        if !root.is_dir() {
+            return Err(invalid(path, "Çalışma alanı bir klasör olmalı"));
+        }
+        *self.root.lock().unwrap() = Some(root.clone());
+        if let Some(store) = &self.store_path {
+            if let Some(parent) = store.parent() {
+                let _ = fs::create_dir_all(parent);
+            }
+            if let Err(e) = fs::write(store, root.to_string_lossy().as_bytes()) {
+                log::warn!("Çalışma alanı kaydedilemedi: {}", e);
+            }
+        }
+        Ok(root)
+    }
+
+    pub fn grant(&self, path: &Path) {
+        match resolve(path) {
+            Ok(resolved) => {
+                self.granted.lock().unwrap().insert(resolved);
+            }
+            Err(e) => log::warn!("Seçilen dosyaya izin verilemedi: {}", e),
+        }
+    }
+
+    fn root_or_error(&self) -> Result<PathBuf, AccessError> {
+        self.root().ok_or_else(|| AccessError::NoWorkspace { message: "Önce bir çalışma alanı klasörü açın".to_string() })
+    }
+
+    fn confine(&self, path: &Path, resolved: PathBuf, allow_root: bool) -> Result<PathBuf, AccessError> {
+        if self.granted.lock().unwrap().contains(&resolved) {
+            return Ok(resolved);
+        }
+        let root = self.root_or_error()?;
+        if resolved == root && !allow_root {
+            Err(AccessError::PermissionDenied {
+                path: path.to_string_lossy().to_string(),
+                message: "Çalışma alanı klasörünün kendisi silinemez ya da yeniden adlandırılamaz".to_string(),
+            })
+        } else if resolved.starts_with(&root) {
+            Ok(resolved)
+        } else {
+            Err(AccessError::PermissionDenied {
+                path: path.to_string_lossy().to_string(),
+                message: format!("Erişim reddedildi: '{}' çalışma alanının ({}) dışında", path.display(), root.display()),
+            })
+        }
+    }
+
+    // Yol kökün içindeyse (ya da kullanıcı seçtiyse) kanonik hâlini döndürür
+    pub fn check(&self, path: impl AsRef<Path>) -> Result<PathBuf, AccessError> {
+        let path = path.as_ref();
+        self.confine(path, resolve(path)?, true)
+    }
+
+    // Silme ve yeniden adlandırma sembolik bağın hedefine değil kendisine uygulanır: yalnızca üst klasör çözülür.
+    // Kökün kendisi silinemez, yeniden adlandırılamaz.
+    pub fn check_entry(&self, path: impl AsRef<Path>) -> Result<PathBuf, AccessError> {
+        let path = path.as_ref();
+        let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else {
+            return Err(invalid(path, "Geçersiz dosya yolu"));
+        };
+        self.confine(path, resolve(parent)?.join(name), false)
+    }
+
+    // Kök parametresi isteğe bağlı komutlar için: verilmezse çalışma alanının kendisi
+    pub fn check_or_root(&self, path: Option<&str>) -> Result<PathBuf, AccessError> {
+        match path {
+            Some(path) => self.check(path),
+            None => self.root_or_error(),
+        }
+    }
+
+    // Export'un görsel ve logo okuyabileceği alan. Aç penceresiyle seçilmiş kök dışı bir not export
+    // edilirken notun kendi klasörü de alana girer; başka bir klasör base_dir olarak verilemez.
+    pub fn read_scope(&self, base_dir: Option<&str>) -> Result<ReadScope, AccessError> {
+        let granted = self.granted.lock().unwrap().clone();
+        let mut roots: Vec<PathBuf> = self.root().into_iter().collect();
+        let base_dir = match base_dir {
+            Some(dir) => {
+                let resolved = resolve(Path::new(dir))?;
+                if granted.iter().any(|file| file.parent() == Some(resolved.as_path())) {
+                    roots.push(resolved.clone());
+                    Some(resolved)
+                } else {
+                    Some(self.check(dir)?)
+                }
+            }
+            None => None,
+        };
+        Ok(ReadScope { base_dir, roots, granted })
+    }
+}
+
+// Arka planda çalışan export işlerine verilen izin kopyası; iş sürerken kök değişse de iş başladığı alanda kalır
+#[derive(Debug, Clone, Default)]
+pub struct ReadScope {
+    base_dir: Option<PathBuf>,
+    roots: Vec<PathBuf>,
+    granted: HashSet<PathBuf>,
+}
+
+impl ReadScope {
+    pub fn base_dir(&self) -> Option<&Path> {
+        self.base_dir.as_deref()
+    }
+
+    // Göreli yollar notun klasörüne göre çözülür; alan dışındaki yollar reddedilir
+    pub fn check(&self, path: &Path) -> Result<PathBuf, AccessError> {
+        let joined;
+        let path = match &self.base_dir {
+            Some(base) if path.is_relative() => {
+                joined = base.join(path);
+                joined.as_path()
+            }
+            _ => path,
+        };
+        let resolved = resolve(path)?;
+        if self.granted.contains(&resolved) || self.roots.iter().any(|root| resolved.starts_with(root)) {
+            Ok(resolved)
+        } else {
+            Err(AccessError::PermissionDenied {
+                path: path.to_string_lossy().to_string(),
+                message: format!("Erişim reddedildi: '{}' çalışma alanının dışında", path.display()),
+            })
+        }
+    }
+}
+
+// Geçerli çalışma alanı kökü
+#[tauri::command]
+pub async fn get_workspace(state: State<'_, WorkspaceState>) -> Result<Option<String>, String> {
+    Ok(state.root().map(|p| p.to_string_lossy().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Kanonik yol: macOS'ta /tmp bir sembolik bağ olduğu için karşılaştırmalar bununla yapılır
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("pignote_workspace_{}_{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dunce::canonicalize(dir).unwrap()
+    }
+
+    // <dir>/root çalışma alanı, <dir>/outside dışarıda kalan klasör
+    fn workspace(dir: &Path) -> (WorkspaceState, PathBuf, PathBuf) {
+        let root = dir.join("root");
+        let outside = dir.join("outside");
+        fs::create_dir_all(root.join("notes")).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        fs::write(root.join("notes/a.md"), "a").unwrap();
+        fs::write(outside.join("secret.md"), "gizli").unwrap();
+        let state = WorkspaceState::new(None);
+        state.open(&root).unwrap();
+        (state, root, outside)
+    }
+
+    fn denied<T: fmt::Debug>(result: Result<T, AccessError>) -> bool {
+        matches!(result, Err(AccessError::PermissionDenied { .. }))
+    }
+
+    fn invalid_path<T: fmt::Debug>(result: Result<T, AccessError>) -> bool {
+        matches!(result, Err(AccessError::InvalidPath { .. }))
+    }
+
+    #[test]
+    fn no_workspace_until_opened() {
+        let dir = temp_dir("none");
+        let state = WorkspaceState::new(None);
+        assert!(matches!(state.check(dir.join("a.md")), Err(AccessError::NoWorkspace { .. })));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn check_confines_to_root() {
+        let dir = temp_dir("check");
+        let (state, root, outside) = workspace(&dir);
+
+        assert_eq!(state.check(root.join("notes/a.md")).unwrap(), root.join("notes/a.md"));
+        assert_eq!(state.check(root.join("notes/yeni.md")).unwrap(), root.join("notes/yeni.md"));
+        assert_eq!(state.check(&root).unwrap(), root);
+        assert!(denied(state.check(outside.join("secret.md"))));
+        // Var olan kısımdaki ".." çözülür ve dışarı çıkar; olmayan kısımdaki ".." hiç kabul edilmez
+        assert!(denied(state.check(root.join("notes/../../outside/secret.md"))));
+        assert!(invalid_path(state.check(root.join("yok/../../outside/secret.md"))));
+        assert!(invalid_path(state.check("notes/a.md")));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn check_entry_protects_root() {
+        let dir = temp_dir("entry");
+        let (state, root, outside) = workspace(&dir);
+
+        assert_eq!(state.check_entry(root.join("notes")).unwrap(), root.join("notes"));
+        assert!(denied(state.check_entry(&root)));
+        assert!(denied(state.check_entry(outside.join("secret.md"))));
+        assert!(invalid_path(state.check_entry(root.join(".."))));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_cannot_escape() {
+        let dir = temp_dir("symlink");
+        let (state, root, outside) = workspace(&dir);
+        std::os::unix::fs::symlink(outside.join("secret.md"), root.join("link.md")).unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("linkdir")).unwrap();
+        std::os::unix::fs::symlink(dir.join("missing"), root.join("dangling")).unwrap();
+
+        assert!(denied(state.check(root.join("link.md"))));
+        assert!(denied(state.check(root.join("linkdir/secret.md"))));
+        assert!(denied(state.check(root.join("linkdir/new.md"))));
+        assert!(invalid_path(state.check(root.join("dangling/new.md"))));
+        // Bağın kendisi kökün içinde olduğu için silinebilir; hedefi değil bağ silinir
+        assert_eq!(state.check_entry(root.join("link.md")).unwrap(), root.join("link.md"));
+        assert!(denied(state.read_scope(None).unwrap().check(&root.join("link.md"))));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn granted_files_are_allowed_outside_root() {
+        let dir = temp_dir("grant");
+        let (state, _root, outside) = workspace(&dir);
+        fs::write(outside.join("other.md"), "b").unwrap();
+
+        state.grant(&outside.join("secret.md"));
+        assert_eq!(state.check(outside.join("secret.md")).unwrap(), outside.join("secret.md"));
+        assert!(denied(state.check(outside.join("other.md"))));
+        assert!(denied(state.check_entry(&outside)));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn read_scope_resolves_against_base_dir() {
+        let dir = temp_dir("scope");
+        let (state, root, outside) = workspace(&dir);
+        fs::write(root.join("notes/img.png"), "png").unwrap();
+        fs::write(outside.join("img.png"), "png").unwrap();
+
+        let scope = state.read_scope(Some(&root.join("notes").to_string_lossy())).unwrap();
+        assert_eq!(scope.base_dir(), Some(root.join("notes").as_path()));
+        assert_eq!(scope.check(Path::new("img.png")).unwrap(), root.join("notes/img.png"));
+        assert!(denied(scope.check(Path::new("../../outside/img.png"))));
+        assert!(denied(scope.check(&outside.join("img.png"))));
+        // Kök dışındaki klasör ancak içinden bir not seçilmişse base_dir olabilir
+        assert!(denied(state.read_scope(Some(&outside.to_string_lossy()))));
+        state.grant(&outside.join("secret.md"));
+        let scope = state.read_scope(Some(&outside.to_string_lossy())).unwrap();
+        assert_eq!(scope.check(Path::new("img.png")).unwrap(), outside.join("img.png"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn names_are_single_components() {
+        assert!(check_name("not.md").is_ok());
+        assert!(check_name(".gizli").is_ok());
+        for name in ["", ".", "..", "a/b", "a\\b", "/abs", "../x"] {
+            assert!(check_name(name).is_err(), "{name}");
+        }
+    }
+}
@@ -9,7 +9,7 @@ import { NewFileDialogComponent, NewFileOptions } from './components/new-file-di
 import { NewFolderDialogComponent, NewFolderOptions } from './components/new-folder-dialog/new-folder-dialog.component';
 import { DeleteFileDialogComponent, DeleteFileOptions } from './components/delete-file-dialog/delete-file-dialog.component';
 import { NotificationComponent, NotificationOptions } from './components/notification/notification.component';
-import { FileService } from './services/file.service';
+import { BrowserDiscoveryError, ExportResult, FileService, FsChangeBatch, WriteError } from './services/file.service';
 import { SettingsDialogComponent } from 'app/components/settings-dialog/settings-dialog.component';
 import { listen } from '@tauri-apps/api/event';
 // Not: file-drop için pencere API'sine gerek yok; global event ile dinleyeceğiz
@@ -43,6 +43,8 @@ export class AppComponent implements OnInit, OnDestroy {
   showNewFolderDialog = signal(false);
   showDeleteFileDialog = signal(false);
   isExporting = signal(false);
+  // Çalışan export işinin kimliği (iptal için)
+  private exportJobId?: number;
   isSaving = signal(false);
   currentDirectoryPath = signal('');
   refreshTrigger = signal(0);
@@ -88,8 +90,12 @@ export class AppComponent implements OnInit, OnDestroy {
   notifications = signal<NotificationOptions[]>([]);
 
   private autoSaveTimer: any = null;
+  // Açık dosyanın yüklendiği/son kaydedildiği andaki içerik özeti; dışarıda değişmişse kayıt reddedilir
+  private loadedHash: string | null = null;
   private readonly autoSaveDelayMs = 1000; // içerik durduktan 1 sn sonra kaydet
   private unlistenFileDrop?: () => void;
+  private unlistenFsChange?: () => void;
+  private watchedRoot = '';
   private unlistenFileDropHover?: () => void;
   private unlistenFileDropCancel?: () => void;
 
@@ -107,6 +113,44 @@ export class AppComponent implements OnInit, OnDestroy {
   onFileSelected(event: { path: string, content: string }) {
     this.currentContent.set(event.content);
     this.currentFilePath.set(event.path);
+    this.trackFingerprint(event.path);
+  }
+
+  private trackFingerprint(path: string) {
+    this.loadedHash = null;
+    this.fileService.fileFingerprint(path).subscribe({
+      next: (fingerprint) => this.loadedHash = fingerprint.hash,
+      error: () => this.loadedHash = null
+    });
+  }
+
+  private saveOptions() {
+    return this.loadedHash ? { expectedHash: this.loadedHash } : undefined;
+  }
+
+  private isConflict(error: unknown): error is WriteError {
+    return typeof error === 'object' && error !== null && (error as WriteError).kind === 'conflict';
+  }
+
+  private errorMessage(error: unknown): string {
+    if (typeof error === 'object' && error !== null && 'message' in error) {
+      return String((error as { message: unknown }).message);
+    }
+    return String(error);
+  }
+
+  // Tarayıcı bulunamadığında arama sonucuna göre yönlendirici mesaj
+  private pdfErrorMessage(error: unknown): string {
+    if (typeof error === 'object' && error !== null && 'kind' in error) {
+      const browserError = error as BrowserDiscoveryError;
+      if (browserError.kind === 'invalid_override') {
+        return `PIGNOTE_BROWSER ile belirtilen tarayıcı çalıştırılamıyor: ${browserError.path}`;
+      }
+      if (browserError.kind === 'no_engine_found') {
+        return `Chrome/Edge/Chromium bulunamadı (${browserError.searched.length} konum arandı). Yerleşik PDF motorunu seçebilirsiniz.`;
+      }
+    }
+    return this.errorMessage(error);
   }
 
   onContentChanged(content: string) {
@@ -183,11 +227,13 @@ export class AppComponent implements OnInit, OnDestroy {
 
   cancelExport() {
     if (!this.isExporting()) return;
+    const jobId = this.exportJobId;
     // Kullanıcı algısı için anında kapat
     this.isExporting.set(false);
     this.showToast('İşlem iptal ediliyor...');
-    // Arkaplanda iptal komutunu gönder
-    this.fileService.cancelExport().subscribe({
+    if (jobId === undefined) return;
+    // Arkaplanda iptal komutunu gönder; yalnızca bu export durur
+    this.fileService.cancelExport(jobId).subscribe({
       next: () => {
         this.showToast('İşlem iptal edildi');
       },
@@ -206,31 +252,37 @@ export class AppComponent implements OnInit, OnDestroy {
 
     const filename = options.filename || 'export';
     const fullPath = filename;
+    const destination = { outputDir: options.outputDir, overwrite: options.overwrite };
 
     this.isExporting.set(true);
     this.showExportDialog.set(false);
+    this.exportJobId = undefined;
+    const onStart = (jobId: number) => this.exportJobId = jobId;
 
-    const isDark = this.isDarkMode();
-    const previewHtml = (document.querySelector('.preview-container div') as HTMLElement)?.innerHTML || '';
+    // Tema seçilmezse önizlemeyle aynı görünüm
+    const theme = options.theme || (this.isDarkMode() ? 'dark' : 'light');
     const currentPath = this.currentFilePath();
-    const baseDir = currentPath ? currentPath.split(/\\|\//).slice(0, -1).join('\\') : this.currentDirectoryPath();
+    // Yolun kendi ayracı korunur; Linux/macOS'ta '\\' ile birleştirilen yol bulunamıyordu
+    const baseDir = currentPath ? currentPath.slice(0, Math.max(currentPath.lastIndexOf('/'), currentPath.lastIndexOf('\\'))) : this.currentDirectoryPath();
+    const root = this.currentDirectoryPath() || undefined;
 
     if (options.format === 'pdf') {
-      this.fileService.exportToPdf(previewHtml || content, fullPath, isDark, baseDir).subscribe({
-        next: (outputPath) => {
+      // Markdown gönderilir: [[wiki]] bağlantıları önizleme HTML'inde çözülmüş olmuyor
+      this.fileService.exportToPdf(content, fullPath, theme, baseDir, root, undefined, destination, options.branding, options.toc, options.pageSetup, onStart).subscribe({
+        next: (result) => {
           this.isExporting.set(false);
-          this.showToast(`✅ PDF dosyası başarıyla oluşturuldu: ${outputPath}`);
+          this.showExportResult('PDF', result);
         },
         error: (error) => {
           this.isExporting.set(false);
-          this.showToast(`❌ PDF oluşturma hatası: ${error}`);
+          this.showToast(`❌ PDF oluşturma hatası: ${this.pdfErrorMessage(error)}`);
         }
       });
     } else if (options.format === 'html') {
-      this.fileService.exportToHtml(previewHtml || content, fullPath, isDark, baseDir).subscribe({
-        next: (outputPath) => {
+      this.fileService.exportToHtml(content, fullPath, theme, baseDir, root, destination, options.toc, onStart).subscribe({
+        next: (result) => {
           this.isExporting.set(false);
-          this.showToast(`✅ HTML dosyası başarıyla oluşturuldu: ${outputPath}`);
+          this.showExportResult('HTML', result);
         },
         error: (error) => {
           this.isExporting.set(false);
@@ -238,11 +290,11 @@ export class AppComponent implements OnInit, OnDestroy {
         }
       });
     } else if (options.format === 'docx') {
-      // DOCX için önizleme HTML'sini gönder ki görünüm birebir ayni olsun
-      this.fileService.exportToDocx(previewHtml || content, fullPath, isDark, baseDir).subscribe({
-        next: (outputPath) => {
+      // DOCX Markdown'dan üretilir; başlık, liste ve tablolar gerçek Word yapılarına dönüşür
+      this.fileService.exportToDocx(content, fullPath, theme, baseDir, root, destination, options.toc, onStart).subscribe({
+        next: (result) => {
           this.isExporting.set(false);
-          this.showToast(`✅ DOCX dosyası başarıyla oluşturuldu: ${outputPath}`);
+          this.showExportResult('DOCX', result);
         },
         error: (error) => {
           this.isExporting.set(false);
@@ -252,6 +304,15 @@ export class AppComponent implements OnInit, OnDestroy {
     }
   }
 
+  // Uyarı varsa dosya yine oluşmuştur; kaybolan bilgi kullanıcıdan gizlenmesin
+  private showExportResult(kind: string, result: ExportResult) {
+    if (result.warnings?.length) {
+      this.showToast(`⚠️ ${kind} dosyası oluşturuldu: ${result.path} — ${result.warnings.join(' ')}`);
+    } else {
+      this.showToast(`✅ ${kind} dosyası başarıyla oluşturuldu: ${result.path}`);
+    }
+  }
+
   onExportCancel() {
     this.showExportDialog.set(false);
   }
@@ -266,6 +327,46 @@ export class AppComponent implements OnInit, OnDestroy {
 
   onCurrentPathChanged(path: string) {
     this.currentDirectoryPath.set(path);
+    this.watchWorkspace(path);
+  }
+
+  private watchWorkspace(root: string) {
+    if (!this.isTauriEnv() || !root || root === this.watchedRoot) return;
+    if (this.watchedRoot) {
+      this.fileService.unwatchWorkspace(this.watchedRoot).subscribe({ error: () => {} });
+    }
+    this.watchedRoot = root;
+    this.fileService.watchWorkspace(root).subscribe({
+      error: (error) => console.warn('Klasör izlenemiyor:', error)
+    });
+  }
+
+  private onFsChange(batch: FsChangeBatch) {
+    this.onRefreshRequested();
+
+    const current = this.currentFilePath();
+    if (!current) return;
+    const normalize = (p: string) => p.replace(/\\/g, '/');
+    const change = batch.changes.find(c => normalize(c.path) === normalize(current) || (c.oldPath && normalize(c.oldPath) === normalize(current)));
+    if (!change) return;
+
+    if (change.kind === 'renamed' && change.oldPath && normalize(change.oldPath) === normalize(current)) {
+      this.currentFilePath.set(change.path);
+      this.showToast('ℹ️ Açık dosya dışarıda yeniden adlandırıldı');
+    } else if (change.kind === 'removed') {
+      this.loadedHash = null;
+      this.showToast('⚠️ Açık dosya diskten silindi');
+    } else {
+      // Kendi kayıtlarımız da olay üretir; içerik özeti aynıysa dış değişiklik yoktur
+      this.fileService.fileFingerprint(current).subscribe({
+        next: (fingerprint) => {
+          if (this.loadedHash && fingerprint.hash !== this.loadedHash) {
+            this.showToast('⚠️ Açık dosya başka bir uygulama tarafından değiştirildi');
+          }
+        },
+        error: () => {}
+      });
+    }
   }
 
   onRefreshRequested() {
@@ -283,7 +384,8 @@ export class AppComponent implements OnInit, OnDestroy {
       : `${options.filename}\n\nBuraya notlarınızı yazabilirsiniz...\n`;
     
     this.fileService.writeFile(filePath, defaultContent).subscribe({
-      next: () => {
+      next: (result) => {
+        this.loadedHash = result.fingerprint.hash;
         this.showToast(`✅ Dosya başarıyla oluşturuldu: ${fileName}`);
         this.showNewFileDialog.set(false);
         // Dosyayı aç
@@ -293,7 +395,7 @@ export class AppComponent implements OnInit, OnDestroy {
         this.onRefreshRequested();
       },
       error: (error) => {
-        this.showToast(`❌ Dosya oluşturma hatası: ${error}`);
+        this.showToast(`❌ Dosya oluşturma hatası: ${this.errorMessage(error)}`);
       }
     });
   }
@@ -330,9 +432,9 @@ export class AppComponent implements OnInit, OnDestroy {
   }
 
   onDeleteFileConfirm(options: DeleteFileOptions) {
-    this.fileService.deleteFile(options.filePath).subscribe({
+    this.fileService.deleteFile(options.filePath, this.currentDirectoryPath() || undefined).subscribe({
       next: () => {
-        this.showToast(`✅ ${options.isDirectory ? 'Klasör' : 'Dosya'} başarıyla silindi: ${options.fileName}`);
+        this.showToast(`✅ ${options.isDirectory ? 'Klasör' : 'Dosya'} çöp kutusuna taşındı: ${options.fileName}`);
         this.showDeleteFileDialog.set(false);
         // File explorer'ı yenile
         this.onRefreshRequested();
@@ -397,14 +499,19 @@ export class AppComponent implements OnInit, OnDestroy {
 
   private saveFile(path: string, content: string) {
     this.isSaving.set(true);
-    this.fileService.writeFile(path, content).subscribe({
-      next: () => {
+    this.fileService.writeFile(path, content, this.saveOptions()).subscribe({
+      next: (result) => {
+        this.loadedHash = result.fingerprint.hash;
         this.showSaveNotification('Kaydedildi!');
         // kısa süreli spinner
         setTimeout(() => this.isSaving.set(false), 500);
       },
       error: (error) => {
-        this.showToast(`❌ Kaydetme hatası: ${error}`);
+        if (this.isConflict(error)) {
+          this.showToast('⚠️ Dosya diskte değişmiş, üzerine yazılmadı. Farklı kaydedin veya dosyayı yeniden açın.');
+        } else {
+          this.showToast(`❌ Kaydetme hatası: ${this.errorMessage(error)}`);
+        }
         this.isSaving.set(false);
       }
     });
@@ -416,8 +523,12 @@ export class AppComponent implements OnInit, OnDestroy {
       next: (path) => {
         if (path) {
           this.currentFilePath.set(path);
+          this.trackFingerprint(path);
           this.showSaveNotification('Kaydedildi!');
           setTimeout(() => this.isSaving.set(false), 500);
+        } else {
+          // Kullanıcı pencereyi kapattı
+          this.isSaving.set(false);
         }
       },
       error: (error) => {
@@ -430,13 +541,18 @@ export class AppComponent implements OnInit, OnDestroy {
   // Otomatik kaydet (sessiz, bildirim göstermeden)
   private autoSaveFile(path: string, content: string) {
     this.isSaving.set(true);
-    this.fileService.writeFile(path, content).subscribe({
-      next: () => {
+    this.fileService.writeFile(path, content, this.saveOptions()).subscribe({
+      next: (result) => {
+        this.loadedHash = result.fingerprint.hash;
         // Sessiz başarı, sadece kısa süreli spinner göster
         setTimeout(() => this.isSaving.set(false), 400);
       },
       error: (error) => {
-        this.showToast(`❌ Otomatik kaydetme hatası: ${error}`);
+        if (this.isConflict(error)) {
+          this.showToast('⚠️ Dosya diskte değişmiş, otomatik kaydetme üzerine yazmadı');
+        } else {
+          this.showToast(`❌ Otomatik kaydetme hatası: ${this.errorMessage(error)}`);
+        }
         this.isSaving.set(false);
       }
     });
@@ -468,6 +584,10 @@ export class AppComponent implements OnInit, OnDestroy {
     try { this.unlistenFileDrop?.(); } catch {}
     try { this.unlistenFileDropHover?.(); } catch {}
     try { this.unlistenFileDropCancel?.(); } catch {}
+    try { this.unlistenFsChange?.(); } catch {}
+    if (this.watchedRoot) {
+      this.fileService.unwatchWorkspace(this.watchedRoot).subscribe({ error: () => {} });
+    }
 
     // Global dinleyicileri kaldır
     try { window.removeEventListener('contextmenu', this.onContextMenuCapture as any, true); } catch {}
@@ -501,6 +621,7 @@ export class AppComponent implements OnInit, OnDestroy {
           next: (content) => {
             this.currentFilePath.set(firstPath);
             this.currentContent.set(content);
+            this.trackFingerprint(firstPath);
           },
           error: (error) => {
             this.showToast(`❌ Dosya açılamadı: ${error}`);
@@ -508,6 +629,8 @@ export class AppComponent implements OnInit, OnDestroy {
         });
       });
 
+      this.unlistenFsChange = await this.fileService.onFsChange((batch) => this.onFsChange(batch));
+
       // Hover ve iptal event'lerini de bağla (isteğe bağlı)
       this.unlistenFileDropHover = await listen('tauri://file-drop-hover', () => {});
       this.unlistenFileDropCancel = await listen('tauri://file-drop-cancelled', () => {});
@@ -578,7 +701,8 @@ export class AppComponent implements OnInit, OnDestroy {
   }
 
   private isSupportedTextOrMarkdown(path: string): boolean {
-    const ext = path?.split('.').pop()?.toLowerCase();
+    // Şifreli notlar (not.md.enc) da açılabilir
+    const ext = path?.toLowerCase().replace(/\.enc$/, '').split('.').pop();
     return ext === 'md' || ext === 'markdown' || ext === 'txt';
   }
 }
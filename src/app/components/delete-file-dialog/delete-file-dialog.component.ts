@@ -37,10 +37,10 @@ export interface DeleteFileOptions {
             <h4>{{ options.isDirectory ? 'Klasörü' : 'Dosyayı' }} silmek istediğinizden emin misiniz?</h4>
             <p class="file-name">{{ options.fileName }}</p>
             <p class="warning-text">
-              {{ options.isDirectory ? 'Bu klasör ve içindeki tüm dosyalar kalıcı olarak silinecektir.' : 'Bu dosya kalıcı olarak silinecektir.' }}
+              {{ options.isDirectory ? 'Bu klasör ve içindeki tüm dosyalar çöp kutusuna taşınacaktır.' : 'Bu dosya çöp kutusuna taşınacaktır.' }}
             </p>
             <p class="irreversible-warning">
-              <strong>Bu işlem geri alınamaz.</strong>
+              <strong>Çöp kutusundan geri yüklenebilir.</strong>
             </p>
           </div>
         </div>
@@ -1,10 +1,20 @@
 import { Component, EventEmitter, Output, Input, OnInit } from '@angular/core';
 import { CommonModule } from '@angular/common';
 import { FormsModule } from '@angular/forms';
+import { BrandingOptions, ExportTheme, FileService, FooterPosition, OverwritePolicy, PageSetup, PaperSize, TocOptions, WatermarkPosition } from '../../services/file.service';
 
 export interface ExportOptions {
   format: 'pdf' | 'html' | 'docx';
   filename: string;
+  // Boşsa Masaüstü (yoksa Belgeler, ev dizini) kullanılır
+  outputDir?: string;
+  overwrite?: OverwritePolicy;
+  // Boşsa önizlemenin açık/koyu teması kullanılır
+  theme?: string;
+  // Yalnızca PDF
+  branding?: BrandingOptions;
+  pageSetup?: PageSetup;
+  toc?: TocOptions;
 }
 
 @Component({
@@ -77,6 +87,98 @@ export interface ExportOptions {
               placeholder="Dosya adını girin"
               class="filename-input">
           </div>
+
+          <div class="form-group" *ngIf="themes.length">
+            <label for="theme">Tema:</label>
+            <select id="theme" [(ngModel)]="options.theme" class="filename-input">
+              <option [ngValue]="undefined">Önizleme ile aynı</option>
+              <option *ngFor="let theme of themes" [ngValue]="theme.id">{{ theme.name }}</option>
+            </select>
+          </div>
+
+          <div class="form-group">
+            <label class="inline-option">
+              <input type="checkbox" [(ngModel)]="toc.auto"> İçindekiler ekle
+            </label>
+            <div class="option-row">
+              <label for="toc-depth">Başlık düzeyi:</label>
+              <select id="toc-depth" [(ngModel)]="toc.depth" class="filename-input">
+                <option *ngFor="let level of [1, 2, 3, 4, 5, 6]" [ngValue]="level">H{{ level }}</option>
+              </select>
+            </div>
+          </div>
+
+          <ng-container *ngIf="options.format === 'pdf'">
+            <div class="form-group">
+              <label for="paper">Sayfa:</label>
+              <div class="option-row">
+                <select id="paper" [(ngModel)]="pageSetup.size" class="filename-input">
+                  <option value="a4">A4</option>
+                  <option value="a3">A3</option>
+                  <option value="a5">A5</option>
+                  <option value="letter">Letter</option>
+                  <option value="custom">Özel</option>
+                </select>
+                <label class="inline-option">
+                  <input type="checkbox" [(ngModel)]="pageSetup.landscape"> Yatay
+                </label>
+              </div>
+              <div class="option-row" *ngIf="pageSetup.size === 'custom'">
+                <input type="number" min="50" max="1500" [(ngModel)]="pageSetup.width" title="Genişlik (mm)" placeholder="Genişlik (mm)" class="filename-input">
+                <input type="number" min="50" max="1500" [(ngModel)]="pageSetup.height" title="Yükseklik (mm)" placeholder="Yükseklik (mm)" class="filename-input">
+              </div>
+              <div class="option-row">
+                <span>Kenar (mm):</span>
+                <input type="number" min="0" step="1" [(ngModel)]="pageSetup.margins.top" title="Üst" class="filename-input number-input">
+                <input type="number" min="0" step="1" [(ngModel)]="pageSetup.margins.right" title="Sağ" class="filename-input number-input">
+                <input type="number" min="0" step="1" [(ngModel)]="pageSetup.margins.bottom" title="Alt" class="filename-input number-input">
+                <input type="number" min="0" step="1" [(ngModel)]="pageSetup.margins.left" title="Sol" class="filename-input number-input">
+              </div>
+              <div class="option-row">
+                <span>Ölçek (%):</span>
+                <input type="number" min="10" max="200" step="5" [(ngModel)]="pageScale" title="İçerik ölçeği (%)" class="filename-input number-input">
+              </div>
+            </div>
+
+            <div class="form-group">
+              <label class="inline-option">
+                <input type="checkbox" [(ngModel)]="watermark.enabled"> Filigran
+              </label>
+              <div class="option-row" *ngIf="watermark.enabled">
+                <input type="text" [(ngModel)]="watermark.text" placeholder="PigNote" class="filename-input">
+                <select [(ngModel)]="watermark.position" class="filename-input">
+                  <option value="diagonal">Eğik</option>
+                  <option value="center">Orta</option>
+                  <option value="top">Üst</option>
+                  <option value="bottom">Alt</option>
+                </select>
+                <input type="number" min="0" max="100" step="1" [(ngModel)]="watermarkOpacity" title="Saydamlık (%)" class="filename-input opacity-input">
+              </div>
+              <label class="inline-option">
+                <input type="checkbox" [(ngModel)]="watermark.fromMetadata"> Nottaki işaret (taslak, gizli) basılsın
+              </label>
+            </div>
+
+            <div class="form-group">
+              <label class="inline-option">
+                <input type="checkbox" [(ngModel)]="footer.enabled"> Alt bilgi
+              </label>
+              <div class="option-row" *ngIf="footer.enabled">
+                <input type="text" [(ngModel)]="footer.text" placeholder="PigNote" class="filename-input">
+                <select [(ngModel)]="footer.position" class="filename-input">
+                  <option value="left">Sol</option>
+                  <option value="center">Orta</option>
+                  <option value="right">Sağ</option>
+                </select>
+              </div>
+              <div class="option-row" *ngIf="footer.enabled">
+                <label class="inline-option">
+                  <input type="checkbox" [(ngModel)]="footer.showLogo"> Logo
+                </label>
+                <input type="text" *ngIf="footer.showLogo" [(ngModel)]="footer.logo" placeholder="Logo yolu (boşsa PigNote ikonu)" class="filename-input">
+              </div>
+            </div>
+          </ng-container>
         </div>
         
         <div class="dialog-actions">
@@ -153,6 +255,36 @@ export interface ExportOptions {
     .dialog-content {
       padding: 24px;
       line-height: 1.6;
+      max-height: 70vh;
+      overflow-y: auto;
+    }
+
+    .form-group label.inline-option {
+      display: flex;
+      align-items: center;
+      gap: 8px;
+      font-weight: 500;
+    }
+
+    .option-row {
+      display: flex;
+      align-items: center;
+      gap: 8px;
+      margin-bottom: 8px;
+    }
+
+    .option-row .filename-input {
+      padding: 10px 12px;
+    }
+
+    .option-row .opacity-input {
+      width: 90px;
+      flex-shrink: 0;
+    }
+
+    .option-row .number-input {
+      width: 70px;
+      flex-shrink: 0;
     }
     
     .form-group {
@@ -461,13 +593,38 @@ export class ExportDialogComponent implements OnInit {
     format: 'pdf',
     filename: ''
   };
+  themes: ExportTheme[] = [];
+  watermark: { enabled: boolean; text: string; position: WatermarkPosition; fromMetadata: boolean } = { enabled: true, text: '', position: 'diagonal', fromMetadata: true };
+  footer: { enabled: boolean; text: string; position: FooterPosition; showLogo: boolean; logo: string } = { enabled: true, text: '', position: 'left', showLogo: true, logo: '' };
+  // Nottaki "[TOC]" satırı kutu işaretli olmasa da genişletilir; düzey onun için de geçerli
+  toc: { auto: boolean; depth: number } = { auto: false, depth: 3 };
+  // Yüzde olarak; PDF'e 0-1 gönderilir
+  watermarkOpacity = 6;
+  pageSetup: { size: PaperSize; width: number; height: number; landscape: boolean; margins: { top: number; right: number; bottom: number; left: number } } = {
+    size: 'a4', width: 210, height: 297, landscape: false, margins: { top: 12, right: 12, bottom: 12, left: 12 }
+  };
+  // Yüzde olarak; PDF'e 0.1-2 gönderilir
+  pageScale = 100;
+
+  constructor(private fileService: FileService) {}
 
   ngOnInit() {
     this.options.filename = this.defaultFilename;
+    this.fileService.listExportThemes().subscribe({
+      next: (themes) => this.themes = themes,
+      error: (error) => console.error('Export temaları alınamadı:', error)
+    });
   }
 
   onExport() {
     if (this.options.filename.trim()) {
+      // Boş metin alanları varsayılan markayı korur
+      this.options.branding = {
+        watermark: { ...this.watermark, text: this.watermark.text.trim() || undefined, opacity: this.watermarkOpacity / 100 },
+        footer: { ...this.footer, text: this.footer.text.trim() || undefined, logo: this.footer.logo.trim() || undefined }
+      };
+      this.options.toc = { ...this.toc };
+      this.options.pageSetup = { ...this.pageSetup, margins: { ...this.pageSetup.margins }, scale: this.pageScale / 100 };
       this.export.emit(this.options);
     }
   }
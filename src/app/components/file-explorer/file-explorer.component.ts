@@ -6,7 +6,7 @@ import { DeleteFileOptions } from '../delete-file-dialog/delete-file-dialog.comp
 import { ContextMenuComponent } from '../context-menu/context-menu.component';
 import { RenameDialogComponent } from '../rename-dialog/rename-dialog.component';
 import { PortalService } from '../../services/portal.service';
-import { Observable, of } from 'rxjs';
+import { Observable, firstValueFrom, of } from 'rxjs';
 import { catchError, tap } from 'rxjs/operators';
 import { desktopDir } from '@tauri-apps/api/path';
 
@@ -37,6 +37,12 @@ import { desktopDir } from '@tauri-apps/api/path';
                  <polyline points="15,18 9,12 15,6"></polyline>
                </svg>
              </button>
+             <button (click)="openWorkspace()" class="action-btn" [title]="t('openWorkspace')">
+               <svg class="icon" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2">
+                 <path d="M22 19a2 2 0 0 1-2 2H4a2 2 0 0 1-2-2V5a2 2 0 0 1 2-2h5l2 3h9a2 2 0 0 1 2 2z"></path>
+                 <polyline points="9,13 12,10 15,13"></polyline>
+               </svg>
+             </button>
              <button (click)="goHome()" class="action-btn" [title]="t('home')">
                <svg class="icon" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2">
                  <path d="M3 9l9-7 9 7v11a2 2 0 0 1-2 2H5a2 2 0 0 1-2-2z"></path>
@@ -190,6 +196,7 @@ export class FileExplorerComponent implements OnInit, OnChanges, OnDestroy {
       explorer: 'Gezgin',
       back: 'Geri',
       home: 'Ana Klasör',
+      openWorkspace: 'Klasör Aç',
       newFile: 'Yeni Dosya',
       newFolder: 'Yeni Klasör',
       refresh: 'Yenile',
@@ -201,6 +208,7 @@ export class FileExplorerComponent implements OnInit, OnChanges, OnDestroy {
       explorer: 'Explorer',
       back: 'Back',
       home: 'Home',
+      openWorkspace: 'Open Folder',
       newFile: 'New File',
       newFolder: 'New Folder',
       refresh: 'Refresh',
@@ -227,11 +235,22 @@ export class FileExplorerComponent implements OnInit, OnChanges, OnDestroy {
     this.initHomeDirectory();
   }
 
+  // Dosya komutları yalnızca çalışma alanı kökünün içinde çalışır; başlangıç klasörü odur
+  private async workspaceRoot(): Promise<string | null> {
+    return firstValueFrom(this.fileService.getWorkspace()).catch(() => null);
+  }
+
   private async initHomeDirectory() {
     try {
-      const desktopPath = this.normalizeWindowsPath(await desktopDir());
-      this.navigateToDirectoryInternal(desktopPath, false);
-      this.currentPathChanged.emit(desktopPath);
+      const rootPath = await this.workspaceRoot();
+      // Henüz çalışma alanı seçilmediyse kullanıcıdan bir klasör istenir
+      if (!rootPath) {
+        this.error = 'Önce bir çalışma alanı klasörü açın';
+        await this.openWorkspace();
+        return;
+      }
+      this.navigateToDirectoryInternal(rootPath, false);
+      this.currentPathChanged.emit(rootPath);
     } catch {
       const fallback = 'C\\Users\\Public\\Desktop';
       this.navigateToDirectoryInternal(fallback, false);
@@ -298,9 +317,9 @@ export class FileExplorerComponent implements OnInit, OnChanges, OnDestroy {
   }
 
   openFile() {
-    this.fileService.openFileDialog().subscribe(path => {
-      if (path) {
-        this.loadFileContent(path);
+    this.fileService.openFileDialog().subscribe(paths => {
+      if (paths && paths.length > 0) {
+        this.loadFileContent(paths[0]);
       }
     });
   }
@@ -449,10 +468,25 @@ export class FileExplorerComponent implements OnInit, OnChanges, OnDestroy {
     }
   }
 
+  async openWorkspace() {
+    const root = await firstValueFrom(this.fileService.openWorkspaceDialog()).catch(error => {
+      this.error = String(error);
+      return null;
+    });
+    if (!root) return;
+    this.navigateToDirectoryInternal(root, true);
+    this.pathHistory = [root];
+    this.pathHistoryIndex = 0;
+  }
+
   async goHome() {
-    // Ana dizine git (mevcut kullanıcının Masaüstü)
+    // Ana dizine git (çalışma alanı kökü)
     try {
-      const homePath = this.normalizeWindowsPath(await desktopDir());
+      const homePath = await this.workspaceRoot();
+      if (!homePath) {
+        await this.openWorkspace();
+        return;
+      }
       this.navigateToDirectoryInternal(homePath, true);
     } catch {
       const fallback = 'C\\Users\\Public\\Desktop';
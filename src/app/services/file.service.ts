@@ -1,13 +1,343 @@
 import { Injectable } from '@angular/core';
 import { invoke } from '@tauri-apps/api/core';
+import { listen, UnlistenFn } from '@tauri-apps/api/event';
 import { Observable, from } from 'rxjs';
 
+export interface PdfEngineInfo {
+  path: string;
+  source: 'override' | 'path' | 'install_dir' | 'flatpak' | 'snap';
+}
+
+// Tarayıcı bulunamadığında PDF işi bu yapısal hatayla biter
+export type BrowserDiscoveryError =
+  | { kind: 'invalid_override'; path: string }
+  | { kind: 'no_engine_found'; searched: string[] };
+
+export type PdfEngine = 'auto' | 'chrome' | 'builtin';
+
+export type OverwritePolicy = 'overwrite' | 'auto_suffix' | 'fail';
+
+// Export hedefi: outputPath verilirse dosya adı yok sayılır, yoksa outputDir ya da Masaüstü > Belgeler > ev dizini
+export interface ExportDestination {
+  outputPath?: string;
+  outputDir?: string;
+  overwrite?: OverwritePolicy;
+}
+
+export interface ExportResult {
+  path: string;
+  directory: string;
+  fileName: string;
+  source: 'output_path' | 'output_dir' | 'desktop' | 'documents' | 'home';
+  overwritten: boolean;
+  renamed: boolean;
+  // Dosya yazıldı ama kullanıcının bilmesi gereken sorunlar (ör. okunamayan ön bilgi)
+  warnings: string[];
+}
+
+export type ExportFormat = 'pdf' | 'html' | 'docx';
+
+export type ExportStage = 'queued' | 'convert' | 'inline_images' | 'render' | 'post_process';
+
+export interface ExportProgress {
+  jobId: number;
+  format: ExportFormat;
+  stage: ExportStage;
+}
+
+export interface ExportFinished {
+  jobId: number;
+  format: ExportFormat;
+  result?: ExportResult;
+  error?: string;
+  browserError?: BrowserDiscoveryError;
+  cancelled: boolean;
+}
+
+export type WatermarkPosition = 'diagonal' | 'center' | 'top' | 'bottom';
+export type FooterPosition = 'left' | 'center' | 'right';
+
+// PDF filigranı ve alt bilgisi; verilmeyen alanlar varsayılanı (PigNote markası) korur
+export interface BrandingOptions {
+  watermark?: {
+    enabled?: boolean;
+    text?: string;
+    opacity?: number;
+    position?: WatermarkPosition;
+    // Notun "watermark" ya da "status: draft/confidential" ön bilgisi basılsın
+    fromMetadata?: boolean;
+  };
+  footer?: {
+    enabled?: boolean;
+    // Boş metinle yalnızca logo basılır
+    text?: string;
+    // PNG/JPEG logo yolu; verilmezse PigNote ikonu
+    logo?: string;
+    showLogo?: boolean;
+    opacity?: number;
+    position?: FooterPosition;
+  };
+}
+
+// İçindekiler: "[TOC]" satırı her zaman genişletilir; auto ile satır yoksa belgenin başına eklenir
+export interface TocOptions {
+  auto?: boolean;
+  // Listeye girecek en derin başlık düzeyi (1-6, varsayılan 3)
+  depth?: number;
+}
+
+export type PaperSize = 'a3' | 'a4' | 'a5' | 'letter' | 'custom';
+
+// PDF sayfa düzeni; ölçüler mm. Verilmeyen alanlar dikey A4, 12mm kenar boşluğu ve %100 ölçektir
+export interface PageSetup {
+  size?: PaperSize;
+  // Yalnızca 'custom' için
+  width?: number;
+  height?: number;
+  landscape?: boolean;
+  margins?: { top?: number; right?: number; bottom?: number; left?: number };
+  // 0.1-2
+  scale?: number;
+}
+
+// Export teması; yerleşik (light, dark) ya da ayar klasöründeki themes/<id>/ altından
+export interface ExportTheme {
+  id: string;
+  name: string;
+  dark: boolean;
+  builtin: boolean;
+}
+
+export interface WriteOptions {
+  backup?: boolean;
+  expectedModified?: number;
+  expectedHash?: string;
+}
+
+export interface FileFingerprint {
+  modified?: number;
+  hash: string;
+  size: number;
+}
+
+export interface WriteResult {
+  path: string;
+  fingerprint: FileFingerprint;
+  backupPath?: string;
+}
+
+// write_file hatası: kind === 'conflict' ise dosya yüklendikten sonra diskte değişmiştir,
+// 'permission_denied' ise yol çalışma alanının dışındadır, 'locked' ise şifreli not için parola gerekir
+export interface WriteError {
+  kind: 'conflict' | 'io' | 'permission_denied' | 'locked';
+  path: string;
+  message: string;
+}
+
+export type FsChangeKind = 'created' | 'modified' | 'removed' | 'renamed';
+
+export interface FsChange {
+  kind: FsChangeKind;
+  path: string;
+  oldPath?: string;
+}
+
+// Debounce edilmiş dış değişiklikler ('fs-change' olayı)
+export interface FsChangeBatch {
+  root: string;
+  changes: FsChange[];
+}
+
+export interface TrashEntry {
+  // Yoksa öğe sistem çöp kutusuna taşındı ama kimlikle geri yüklenemiyor
+  id?: string;
+  name: string;
+  originalPath: string;
+  deletedAt?: string;
+  location: 'system' | 'workspace';
+}
+
+export interface SearchOptions {
+  regex?: boolean;
+  caseSensitive?: boolean;
+  wholeWord?: boolean;
+  ignore?: string[];
+  extensions?: string[];
+  contextLines?: number;
+  maxResults?: number;
+}
+
+export interface SearchMatch {
+  line: number;
+  // UTF-16 birimi, 1 tabanlı (Monaco ile aynı)
+  column: number;
+  length: number;
+  text: string;
+  before: string[];
+  after: string[];
+}
+
+export interface SearchFileResult {
+  searchId: number;
+  path: string;
+  matches: SearchMatch[];
+}
+
+export interface SearchSummary {
+  searchId: number;
+  filesScanned: number;
+  filesMatched: number;
+  matchCount: number;
+  truncated: boolean;
+  cancelled: boolean;
+}
+
+export interface IndexHit {
+  path: string;
+  score: number;
+  line: number | null;
+  snippet: string | null;
+}
+
+export interface IndexStats {
+  root: string;
+  documents: number;
+  terms: number;
+}
+
+export interface NoteLink {
+  source: string;
+  line: number;
+  target: string;
+  heading: string | null;
+  alias: string | null;
+  resolved: string | null;
+}
+
+export interface LinkGraph {
+  notes: string[];
+  links: NoteLink[];
+}
+
+export interface TagInfo {
+  name: string;
+  // Alt etiketlerdeki kullanımlar dahil
+  count: number;
+  files: string[];
+  nested: boolean;
+}
+
+export interface RenameTagResult {
+  files: string[];
+  replacements: number;
+}
+
+export type GitChange = 'added' | 'modified' | 'deleted' | 'renamed' | 'typechange';
+
+export interface GitFileStatus {
+  path: string;
+  relativePath: string;
+  staged?: GitChange;
+  unstaged?: GitChange;
+  untracked: boolean;
+  conflicted: boolean;
+}
+
+export interface GitStatus {
+  repository: string;
+  branch?: string;
+  files: GitFileStatus[];
+}
+
+export interface CommitInfo {
+  id: string;
+  shortId: string;
+  summary: string;
+  message: string;
+  author: string;
+  email: string;
+  time?: string;
+}
+
+export interface DiffLine {
+  origin: '+' | '-' | ' ';
+  content: string;
+  oldLine?: number;
+  newLine?: number;
+}
+
+export interface DiffHunk {
+  header: string;
+  oldStart: number;
+  oldLines: number;
+  newStart: number;
+  newLines: number;
+  lines: DiffLine[];
+}
+
+export interface FileDiff {
+  path: string;
+  change?: GitChange;
+  binary: boolean;
+  additions: number;
+  deletions: number;
+  hunks: DiffHunk[];
+}
+
+export interface NoteVersion {
+  // Kayıt zamanı (Unix ms), aynı zamanda sürüm kimliği
+  id: number;
+  hash: string;
+  size: number;
+}
+
+export interface EncryptionStatus {
+  unlocked: boolean;
+  // Otomatik kilide kalan süre (saniye)
+  locksIn?: number;
+}
+
+// unlock_notes hatası: 'wrong_passphrase' ise parola yeniden sorulabilir
+export interface CryptoError {
+  kind: 'locked' | 'wrong_passphrase' | 'invalid';
+  path?: string;
+  message: string;
+}
+
+export interface NoteMetadata {
+  title?: string;
+  author?: string;
+  date?: string;
+  tags: string[];
+  // "subject", "description" ya da "summary"
+  subject?: string;
+  // PDF'e filigran olarak basılan işaret ("watermark" ya da "status: draft/confidential")
+  watermark?: string;
+}
+
 export interface FileInfo {
   name: string;
   path: string;
   is_directory: boolean;
   size?: number;
   modified?: string;
+  created?: string;
+  metadata?: NoteMetadata;
+  // Ağaç modunda klasör içeriği; derinlik sınırındaki klasörlerde yok
+  children?: FileInfo[];
+}
+
+export interface ListOptions {
+  sortBy?: 'name' | 'modified' | 'created' | 'size';
+  descending?: boolean;
+  foldersFirst?: boolean;
+  extensions?: string[];
+  patterns?: string[];
+  showHidden?: boolean;
+  respectGitignore?: boolean;
+  recursive?: boolean;
+  maxDepth?: number;
+  includeMetadata?: boolean;
 }
 
 @Injectable({
@@ -18,13 +348,23 @@ export class FileService {
   constructor() { }
 
   // Dosya açma dialog'u
-  openFileDialog(): Observable<string | null> {
-    return from(invoke<string | null>('open_file_dialog'));
+  openFileDialog(multiple: boolean = false): Observable<string[] | null> {
+    return from(invoke<string[] | null>('open_file_dialog', { multiple }));
+  }
+
+  // Farklı kaydet: içerik seçilen yola yazılır, iptal edilirse null döner
+  saveFileDialog(content: string, defaultName?: string): Observable<string | null> {
+    return from(invoke<string | null>('save_file_dialog', { content, defaultName }));
   }
 
-  // Dosya kaydetme dialog'u
-  saveFileDialog(content: string): Observable<string | null> {
-    return from(invoke<string | null>('save_file_dialog', { content }));
+  // Dosya komutları yalnızca bu kökün içinde çalışır
+  getWorkspace(): Observable<string | null> {
+    return from(invoke<string | null>('get_workspace'));
+  }
+
+  // Yerel klasör seçme penceresiyle yeni çalışma alanı açar; iptal edilirse null döner
+  openWorkspaceDialog(): Observable<string | null> {
+    return from(invoke<string | null>('open_workspace_dialog'));
   }
 
   // Dosya okuma
@@ -33,13 +373,19 @@ export class FileService {
   }
 
   // Dosya yazma
-  writeFile(path: string, content: string): Observable<void> {
-    return from(invoke<void>('write_file', { path, content }));
+  // Atomik kaydetme; expectedHash/expectedModified verilirse diskte değişen dosyanın üzerine yazılmaz
+  writeFile(path: string, content: string, options?: WriteOptions): Observable<WriteResult> {
+    return from(invoke<WriteResult>('write_file', { path, content, options }));
+  }
+
+  // Dosyanın diskteki parmak izi (yüklendiği andaki hâli, çakışma kontrolü için)
+  fileFingerprint(path: string): Observable<FileFingerprint> {
+    return from(invoke<FileFingerprint>('file_fingerprint', { path }));
   }
 
   // Klasör listeleme
-  listDirectory(path: string): Observable<FileInfo[]> {
-    return from(invoke<FileInfo[]>('list_directory', { path }));
+  listDirectory(path: string, options?: ListOptions): Observable<FileInfo[]> {
+    return from(invoke<FileInfo[]>('list_directory', { path, options }));
   }
 
   // Klasör oluşturma
@@ -48,8 +394,18 @@ export class FileService {
   }
 
   // Dosya/klasör silme
-  deleteFile(path: string): Observable<void> {
-    return from(invoke<void>('delete_file', { path }));
+  // Kalıcı silme yok: sistem çöp kutusuna (yoksa <root>/.pignote-trash) taşır
+  deleteFile(path: string, root?: string): Observable<TrashEntry> {
+    return from(invoke<TrashEntry>('delete_file', { path, root }));
+  }
+
+  listTrash(root?: string): Observable<TrashEntry[]> {
+    return from(invoke<TrashEntry[]>('list_trash', { root }));
+  }
+
+  // Geri yüklenen yolu döndürür
+  restoreFromTrash(id: string): Observable<string> {
+    return from(invoke<string>('restore_from_trash', { id }));
   }
 
   // Dosya yeniden adlandırma
@@ -57,29 +413,235 @@ export class FileService {
     return from(invoke<string>('rename_file', { oldPath, newName }));
   }
 
-  // PDF export (dark param: preview teması ile eşleşsin)
-  exportToPdf(content: string, filename: string, dark: boolean = false, baseDir?: string): Observable<string> {
-    return from(invoke<string>('export_to_pdf', { content, filename, dark, base_dir: baseDir }));
+  // PDF export (theme: export teması kimliği; verilmezse 'light')
+  // engine: 'auto' Chrome/Edge bulamazsa yerleşik motora düşer ('builtin' yalnızca Markdown içerik alır)
+  // root: [[wiki]] bağlantılarının çözüleceği çalışma alanı kökü
+  // branding: filigran ve alt bilgi (kapatma, metin/logo, saydamlık, konum)
+  // onStart: iş kimliği (iptal için) export başlar başlamaz bildirilir
+  exportToPdf(content: string, filename: string, theme?: string, baseDir?: string, root?: string, engine?: PdfEngine, destination?: ExportDestination, branding?: BrandingOptions, toc?: TocOptions, pageSetup?: PageSetup, onStart?: (jobId: number) => void): Observable<ExportResult> {
+    return this.runExportJob('export_to_pdf', { content, filename, theme, baseDir, root, engine, destination, branding, toc, pageSetup }, onStart);
+  }
+
+  // PDF için kullanılacak Chrome/Edge/Chromium yolunu tespit et
+  detectPdfEngine(): Observable<PdfEngineInfo> {
+    return from(invoke<PdfEngineInfo>('detect_pdf_engine'));
   }
 
   // HTML export
-  exportToHtml(content: string, filename: string, dark: boolean = false, baseDir?: string): Observable<string> {
-    return from(invoke<string>('export_to_html', { content, filename, dark, base_dir: baseDir }));
+  exportToHtml(content: string, filename: string, theme?: string, baseDir?: string, root?: string, destination?: ExportDestination, toc?: TocOptions, onStart?: (jobId: number) => void): Observable<ExportResult> {
+    return this.runExportJob('export_to_html', { content, filename, theme, baseDir, root, destination, toc }, onStart);
+  }
+
+  // DOCX export (Markdown'dan yerel Word yapıları); temadan yalnızca açık/koyu renk seçimi alınır
+  exportToDocx(markdown: string, filename: string, theme?: string, baseDir?: string, root?: string, destination?: ExportDestination, toc?: TocOptions, onStart?: (jobId: number) => void): Observable<ExportResult> {
+    return this.runExportJob('export_to_docx', { content: markdown, filename, theme, baseDir, root, destination, toc }, onStart);
+  }
+
+  // Export komutu iş kimliğini hemen döndürür; sonuç 'export-finished' olayıyla gelir.
+  // Olay komut dönmeden gelebileceği için dinleyici komuttan önce kurulur.
+  private runExportJob(command: string, args: Record<string, unknown>, onStart?: (jobId: number) => void): Observable<ExportResult> {
+    return new Observable<ExportResult>((subscriber) => {
+      let unlisten: UnlistenFn | undefined;
+      let jobId: number | undefined;
+      const early: ExportFinished[] = [];
+      const finish = (event: ExportFinished) => {
+        if (event.result) {
+          subscriber.next(event.result);
+          subscriber.complete();
+        } else {
+          subscriber.error(event.browserError ?? event.error ?? 'İşlem iptal edildi');
+        }
+      };
+      listen<ExportFinished>('export-finished', (e) => {
+        if (jobId === undefined) {
+          early.push(e.payload);
+        } else if (e.payload.jobId === jobId) {
+          finish(e.payload);
+        }
+      })
+        .then((fn) => {
+          unlisten = fn;
+          return invoke<number>(command, args);
+        })
+        .then((id) => {
+          jobId = id;
+          onStart?.(id);
+          const done = early.find((e) => e.jobId === id);
+          if (done) {
+            finish(done);
+          }
+        })
+        .catch((error) => subscriber.error(error));
+      return () => unlisten?.();
+    });
+  }
+
+  onExportProgress(handler: (progress: ExportProgress) => void): Promise<UnlistenFn> {
+    return listen<ExportProgress>('export-progress', (e) => handler(e.payload));
+  }
+
+  listExportJobs(): Observable<ExportProgress[]> {
+    return from(invoke<ExportProgress[]>('list_export_jobs'));
+  }
+
+  listExportThemes(): Observable<ExportTheme[]> {
+    return from(invoke<ExportTheme[]>('list_export_themes'));
+  }
+
+  // Kullanıcı temalarının klasörü (yoksa oluşturulur)
+  getThemesDir(): Observable<string> {
+    return from(invoke<string>('get_themes_dir'));
+  }
+
+  // Aynı anda çalışabilecek export sayısı (1-8); uygulanan değer döner
+  setExportLimit(limit: number): Observable<number> {
+    return from(invoke<number>('set_export_limit', { limit }));
+  }
+
+  // Çalışma alanını dış değişikliklere karşı izle
+  watchWorkspace(root: string): Observable<void> {
+    return from(invoke<void>('watch_workspace', { root }));
+  }
+
+  unwatchWorkspace(root: string): Observable<void> {
+    return from(invoke<void>('unwatch_workspace', { root }));
+  }
+
+  onFsChange(handler: (batch: FsChangeBatch) => void): Promise<UnlistenFn> {
+    return listen<FsChangeBatch>('fs-change', (e) => handler(e.payload));
+  }
+
+  // Tam metin arama: kimlik hemen döner, sonuçlar 'search-result', özet 'search-finished' ile gelir
+  searchWorkspace(root: string, query: string, options?: SearchOptions): Observable<number> {
+    return from(invoke<number>('search_workspace', { root, query, options }));
+  }
+
+  cancelSearch(): Observable<void> {
+    return from(invoke<void>('cancel_search'));
+  }
+
+  onSearchResult(handler: (result: SearchFileResult) => void): Promise<UnlistenFn> {
+    return listen<SearchFileResult>('search-result', (e) => handler(e.payload));
+  }
+
+  onSearchFinished(handler: (summary: SearchSummary) => void): Promise<UnlistenFn> {
+    return listen<SearchSummary>('search-finished', (e) => handler(e.payload));
+  }
+
+  // Dizinli arama: kelimelerin hepsi aranır, "tırnak içi" ifade, kelime* önek olarak eşleşir
+  querySearchIndex(root: string, query: string, limit?: number): Observable<IndexHit[]> {
+    return from(invoke<IndexHit[]>('query_search_index', { root, query, limit }));
+  }
+
+  rebuildSearchIndex(root: string): Observable<IndexStats> {
+    return from(invoke<IndexStats>('rebuild_search_index', { root }));
+  }
+
+  // [[wiki]] bağlantıları
+  getBacklinks(root: string, path: string): Observable<NoteLink[]> {
+    return from(invoke<NoteLink[]>('get_backlinks', { root, path }));
+  }
+
+  listUnresolvedLinks(root: string): Observable<NoteLink[]> {
+    return from(invoke<NoteLink[]>('list_unresolved_links', { root }));
+  }
+
+  getLinkGraph(root: string): Observable<LinkGraph> {
+    return from(invoke<LinkGraph>('get_link_graph', { root }));
+  }
+
+  // #etiketler: "#proje/alfa" iç içe etiketi "proje" altında da sayılır
+  listTags(root: string): Observable<TagInfo[]> {
+    return from(invoke<TagInfo[]>('list_tags', { root }));
+  }
+
+  findNotesByTag(root: string, tag: string): Observable<string[]> {
+    return from(invoke<string[]>('find_notes_by_tag', { root, tag }));
+  }
+
+  renameTag(root: string, from: string, to: string): Observable<RenameTagResult> {
+    return from(invoke<RenameTagResult>('rename_tag', { root, from, to }));
+  }
+
+  // Klasör bir git deposunda değilse null
+  gitStatus(root: string): Observable<GitStatus | null> {
+    return from(invoke<GitStatus | null>('git_status', { root }));
+  }
+
+  gitStage(root: string, paths: string[]): Observable<void> {
+    return from(invoke<void>('git_stage', { root, paths }));
+  }
+
+  gitUnstage(root: string, paths: string[]): Observable<void> {
+    return from(invoke<void>('git_unstage', { root, paths }));
+  }
+
+  gitCommit(root: string, message: string): Observable<CommitInfo> {
+    return from(invoke<CommitInfo>('git_commit', { root, message }));
+  }
+
+  gitFileLog(path: string, limit?: number): Observable<CommitInfo[]> {
+    return from(invoke<CommitInfo[]>('git_file_log', { path, limit }));
+  }
+
+  // Çalışma kopyasının HEAD'e göre farkı
+  gitDiff(path: string, contextLines?: number): Observable<FileDiff> {
+    return from(invoke<FileDiff>('git_diff', { path, contextLines }));
+  }
+
+  // Notun yerel sürüm geçmişi, yeniden eskiye
+  listVersions(path: string): Observable<NoteVersion[]> {
+    return from(invoke<NoteVersion[]>('list_versions', { path }));
+  }
+
+  readVersion(path: string, id: number): Observable<string> {
+    return from(invoke<string>('read_version', { path, id }));
+  }
+
+  // `to` verilmezse dosyanın diskteki hâliyle karşılaştırılır
+  diffVersions(path: string, fromId: number, toId?: number, contextLines?: number): Observable<FileDiff> {
+    return from(invoke<FileDiff>('diff_versions', { path, from: fromId, to: toId, contextLines }));
+  }
+
+  restoreVersion(path: string, id: number, options?: WriteOptions): Observable<WriteResult> {
+    return from(invoke<WriteResult>('restore_version', { path, id, options }));
+  }
+
+  // Şifreli notlar (.md.enc): parola yalnızca bellekte tutulur, süre dolunca 'notes-locked' olayı gelir.
+  // `path` verilirse parola o notla doğrulanır
+  unlockNotes(passphrase: string, path?: string, timeoutMinutes?: number): Observable<EncryptionStatus> {
+    return from(invoke<EncryptionStatus>('unlock_notes', { passphrase, path, timeoutMinutes }));
+  }
+
+  lockNotes(): Observable<void> {
+    return from(invoke<void>('lock_notes'));
+  }
+
+  encryptionStatus(): Observable<EncryptionStatus> {
+    return from(invoke<EncryptionStatus>('encryption_status'));
+  }
+
+  // Yeni yolu döndürür; düz metin dosyası ve sürüm geçmişi silinir
+  encryptNote(path: string): Observable<string> {
+    return from(invoke<string>('encrypt_note', { path }));
+  }
+
+  decryptNote(path: string): Observable<string> {
+    return from(invoke<string>('decrypt_note', { path }));
   }
 
-  // DOCX export (preview HTML ile birebir görünüm)
-  exportToDocx(htmlOrMarkdown: string, filename: string, dark?: boolean, baseDir?: string): Observable<string> {
-    return from(invoke<string>('export_to_docx', { content: htmlOrMarkdown, filename, dark, base_dir: baseDir }));
+  onNotesLocked(handler: () => void): Promise<UnlistenFn> {
+    return listen<void>('notes-locked', () => handler());
   }
 
-  // Export iptali
-  cancelExport(): Observable<void> {
-    return from(invoke<void>('cancel_export'));
+  // Yalnızca verilen export işi iptal edilir
+  cancelExport(jobId: number): Observable<void> {
+    return from(invoke<void>('cancel_export', { jobId }));
   }
 
   // Dosya uzantısını kontrol etme
   isMarkdownFile(filename: string): boolean {
-    const ext = filename.toLowerCase().split('.').pop();
+    const ext = filename.toLowerCase().replace(/\.enc$/, '').split('.').pop();
     return ext === 'md' || ext === 'markdown';
   }
 